@@ -91,4 +91,13 @@ impl<M: ManagedTypeApi> RawResultWrapper<M> {
 
         result
     }
+
+    pub fn decode_remaining_results<T: TopDecode + ManagedVecItem>(&mut self) -> ManagedVec<M, T> {
+        let mut results = ManagedVec::new();
+        while !self.raw_results.is_empty() {
+            results.push(self.decode_next_result());
+        }
+
+        results
+    }
 }
@@ -17,6 +17,13 @@ pub trait ConfigModule: pausable::PausableModule + permissions_module::Permissio
         state == State::Active
     }
 
+    /// Once migrated through `migrateToPerSecondRewards`, the rewards are emitted
+    /// by timestamp instead of block nonce.
+    #[inline]
+    fn is_per_second_emission(&self) -> bool {
+        !self.per_second_reward_amount().is_empty()
+    }
+
     fn is_old_farm_position(&self, token_nonce: Nonce) -> bool {
         let farm_position_migration_nonce = self.farm_position_migration_nonce().get();
         token_nonce > 0 && token_nonce < farm_position_migration_nonce
@@ -56,6 +63,14 @@ pub trait ConfigModule: pausable::PausableModule + permissions_module::Permissio
     #[storage_mapper("per_block_reward_amount")]
     fn per_block_reward_amount(&self) -> SingleValueMapper<BigUint>;
 
+    #[view(getPerSecondRewardAmount)]
+    #[storage_mapper("perSecondRewardAmount")]
+    fn per_second_reward_amount(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getLastRewardTimestamp)]
+    #[storage_mapper("lastRewardTimestamp")]
+    fn last_reward_timestamp(&self) -> SingleValueMapper<u64>;
+
     #[storage_mapper("produce_rewards_enabled")]
     fn produce_rewards_enabled(&self) -> SingleValueMapper<bool>;
 
@@ -64,15 +64,57 @@ pub trait FarmContract {
         }
 
         let per_block_reward = sc.per_block_reward_amount().get();
-        let block_nonce_diff = current_block_nonce - last_reward_block_nonce;
 
-        per_block_reward * block_nonce_diff
+        sc.calculate_scheduled_rewards(
+            last_reward_block_nonce,
+            current_block_nonce,
+            &per_block_reward,
+        )
+    }
+
+    fn calculate_per_second_rewards(
+        sc: &Self::FarmSc,
+        current_timestamp: u64,
+        last_reward_timestamp: u64,
+    ) -> BigUint<<Self::FarmSc as ContractBase>::Api> {
+        if current_timestamp <= last_reward_timestamp || !sc.produces_per_block_rewards() {
+            return BigUint::zero();
+        }
+
+        let per_second_reward = sc.per_second_reward_amount().get();
+
+        sc.calculate_scheduled_rewards(last_reward_timestamp, current_timestamp, &per_second_reward)
+    }
+
+    fn mint_per_second_rewards(
+        sc: &Self::FarmSc,
+        token_id: &TokenIdentifier<<Self::FarmSc as ContractBase>::Api>,
+    ) -> BigUint<<Self::FarmSc as ContractBase>::Api> {
+        let current_timestamp = sc.blockchain().get_block_timestamp();
+        let last_reward_timestamp = sc.last_reward_timestamp().get();
+        if current_timestamp <= last_reward_timestamp {
+            return BigUint::zero();
+        }
+
+        let to_mint =
+            Self::calculate_per_second_rewards(sc, current_timestamp, last_reward_timestamp);
+        if to_mint != 0 {
+            Self::mint_rewards(sc, token_id, &to_mint);
+        }
+
+        sc.last_reward_timestamp().set(current_timestamp);
+
+        to_mint
     }
 
     fn mint_per_block_rewards(
         sc: &Self::FarmSc,
         token_id: &TokenIdentifier<<Self::FarmSc as ContractBase>::Api>,
     ) -> BigUint<<Self::FarmSc as ContractBase>::Api> {
+        if sc.is_per_second_emission() {
+            return Self::mint_per_second_rewards(sc, token_id);
+        }
+
         let current_block_nonce = sc.blockchain().get_block_nonce();
         let last_reward_nonce = sc.last_reward_block_nonce().get();
         if current_block_nonce > last_reward_nonce {
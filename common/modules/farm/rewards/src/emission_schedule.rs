@@ -0,0 +1,103 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+pub const MAX_EMISSION_SEGMENTS: usize = 20;
+
+/// How the rate of a segment changes, relative to its start.
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub enum EmissionCurve<M: ManagedTypeApi> {
+    Constant,
+    /// The rate decreases by `decay` each block (or second), until it reaches zero.
+    LinearDecay {
+        decay: BigUint<M>,
+    },
+    /// The rate is halved every `period` blocks (or seconds).
+    Halving {
+        period: u64,
+    },
+}
+
+/// A segment applies from its `start` until the start of the next one. `start` is a block
+/// nonce, or a timestamp once the farm emits rewards per second.
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct EmissionSegment<M: ManagedTypeApi> {
+    pub start: u64,
+    pub rate: BigUint<M>,
+    pub curve: EmissionCurve<M>,
+}
+
+impl<M: ManagedTypeApi> EmissionSegment<M> {
+    pub fn is_valid(&self) -> bool {
+        match &self.curve {
+            EmissionCurve::Constant => true,
+            EmissionCurve::LinearDecay { decay } => decay > &0u64,
+            EmissionCurve::Halving { period } => *period > 0,
+        }
+    }
+
+    /// The rewards emitted between `from` and `to`, both not lower than `start`.
+    pub fn rewards_between(&self, from: u64, to: u64) -> BigUint<M> {
+        if to <= from {
+            return BigUint::zero();
+        }
+
+        let from_offset = from - self.start;
+        let to_offset = to - self.start;
+        match &self.curve {
+            EmissionCurve::Constant => &self.rate * (to_offset - from_offset),
+            EmissionCurve::LinearDecay { decay } => {
+                self.linear_decay_rewards(decay, from_offset, to_offset)
+            }
+            EmissionCurve::Halving { period } => {
+                self.halving_rewards(*period, from_offset, to_offset)
+            }
+        }
+    }
+
+    fn linear_decay_rewards(
+        &self,
+        decay: &BigUint<M>,
+        from_offset: u64,
+        to_offset: u64,
+    ) -> BigUint<M> {
+        // the rate is rate - decay * offset, while not negative
+        let last_offset = (&self.rate / decay).to_u64();
+        let to_offset = match last_offset {
+            Some(last_offset) if last_offset < to_offset => last_offset + 1,
+            _ => to_offset,
+        };
+        if to_offset <= from_offset {
+            return BigUint::zero();
+        }
+
+        let nr_units = to_offset - from_offset;
+        let offsets_sum =
+            (BigUint::from(from_offset) + BigUint::from(to_offset - 1)) * nr_units / 2u64;
+
+        &self.rate * nr_units - decay * &offsets_sum
+    }
+
+    fn halving_rewards(&self, period: u64, from_offset: u64, to_offset: u64) -> BigUint<M> {
+        let mut total = BigUint::zero();
+        let max_halvings = match self.rate.log2_floor() {
+            Some(log2) => log2 as u64,
+            None => return total,
+        };
+
+        let mut offset = from_offset;
+        while offset < to_offset {
+            let halvings = offset / period;
+            if halvings > max_halvings {
+                break;
+            }
+
+            let period_end = core::cmp::min((halvings + 1).saturating_mul(period), to_offset);
+            let rate = &self.rate / &BigUint::from(2u64).pow(halvings as u32);
+            total += rate * (period_end - offset);
+
+            offset = period_end;
+        }
+
+        total
+    }
+}
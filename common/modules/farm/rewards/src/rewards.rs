@@ -2,13 +2,18 @@
 
 multiversx_sc::imports!();
 
+pub mod emission_schedule;
+
+use common_errors::ERROR_ZERO_AMOUNT;
+use emission_schedule::{EmissionSegment, MAX_EMISSION_SEGMENTS};
+
 #[multiversx_sc::module]
 pub trait RewardsModule:
     config::ConfigModule + pausable::PausableModule + permissions_module::PermissionsModule
 {
     fn start_produce_rewards(&self) {
         require!(
-            self.per_block_reward_amount().get() != 0u64,
+            self.get_base_reward_rate() != 0u64 || !self.emission_schedule().is_empty(),
             "Cannot produce zero reward amount"
         );
         require!(
@@ -16,8 +21,155 @@ pub trait RewardsModule:
             "Producing rewards is already enabled"
         );
         let current_nonce = self.blockchain().get_block_nonce();
+        let current_timestamp = self.blockchain().get_block_timestamp();
         self.produce_rewards_enabled().set(true);
         self.last_reward_block_nonce().set(current_nonce);
+        self.last_reward_timestamp().set(current_timestamp);
+    }
+
+    /// Converts the per block reward amount to a per second one, given the current block time.
+    /// The rewards up to the current block have to be generated beforehand, so that the
+    /// emission continues from the current timestamp at the same rate.
+    fn migrate_to_per_second_rewards(&self, seconds_per_block: u64) {
+        require!(
+            !self.is_per_second_emission(),
+            "Already using per second rewards"
+        );
+        require!(seconds_per_block > 0, "Invalid block time");
+        require!(
+            self.emission_schedule().is_empty(),
+            "Emission schedule must be cleared first"
+        );
+
+        let per_second_amount = self.per_block_reward_amount().get() / seconds_per_block;
+        require!(per_second_amount != 0u64, ERROR_ZERO_AMOUNT);
+
+        self.per_second_reward_amount().set(per_second_amount);
+        self.last_reward_timestamp()
+            .set(self.blockchain().get_block_timestamp());
+    }
+
+    /// The rewards up to the current timestamp have to be generated beforehand.
+    fn set_per_second_rewards(&self, per_second_amount: BigUint) {
+        require!(
+            self.is_per_second_emission(),
+            "Not using per second rewards"
+        );
+        require!(per_second_amount != 0u64, ERROR_ZERO_AMOUNT);
+
+        self.per_second_reward_amount().set(per_second_amount);
+    }
+
+    /// Replaces the emission schedule. The segments must start in the future, in increasing
+    /// order, so the rewards up to the current block (or timestamp) have to be generated
+    /// beforehand. Before the start of the first segment, the current reward amount is used.
+    fn set_emission_schedule(&self, segments: MultiValueEncoded<EmissionSegment<Self::Api>>) {
+        require!(
+            segments.len() <= MAX_EMISSION_SEGMENTS,
+            "Too many emission segments"
+        );
+
+        let mut schedule_mapper = self.emission_schedule();
+        schedule_mapper.clear();
+
+        let mut min_start = self.get_current_emission_point();
+        for segment in segments {
+            require!(
+                segment.start >= min_start && segment.is_valid(),
+                "Invalid emission segment"
+            );
+
+            min_start = segment.start.saturating_add(1);
+            let _ = schedule_mapper.push(&segment);
+        }
+    }
+
+    /// The block nonce, or the timestamp once the farm emits rewards per second.
+    fn get_current_emission_point(&self) -> u64 {
+        if self.is_per_second_emission() {
+            self.blockchain().get_block_timestamp()
+        } else {
+            self.blockchain().get_block_nonce()
+        }
+    }
+
+    /// Integrates the emission between `from` and `to`, across the segments of the schedule.
+    /// `base_rate` is used before the first segment, or when there is no schedule.
+    fn calculate_scheduled_rewards(&self, from: u64, to: u64, base_rate: &BigUint) -> BigUint {
+        if to <= from {
+            return BigUint::zero();
+        }
+
+        let schedule_mapper = self.emission_schedule();
+        let nr_segments = schedule_mapper.len();
+        if nr_segments == 0 {
+            return base_rate * (to - from);
+        }
+
+        let mut total = BigUint::zero();
+        let first_start = schedule_mapper.get(1).start;
+        if from < first_start {
+            total += base_rate * (core::cmp::min(to, first_start) - from);
+        }
+
+        for index in 1..=nr_segments {
+            let segment = schedule_mapper.get(index);
+            if segment.start >= to {
+                break;
+            }
+
+            let segment_end = if index < nr_segments {
+                schedule_mapper.get(index + 1).start
+            } else {
+                u64::MAX
+            };
+            if segment_end <= from {
+                continue;
+            }
+
+            let segment_from = core::cmp::max(from, segment.start);
+            let segment_to = core::cmp::min(to, segment_end);
+            total += segment.rewards_between(segment_from, segment_to);
+        }
+
+        total
+    }
+
+    fn get_base_reward_rate(&self) -> BigUint {
+        if self.is_per_second_emission() {
+            self.per_second_reward_amount().get()
+        } else {
+            self.per_block_reward_amount().get()
+        }
+    }
+
+    /// Returns the current segment and the ones following it.
+    #[view(getUpcomingEmissionSchedule)]
+    fn get_upcoming_emission_schedule(&self) -> MultiValueEncoded<EmissionSegment<Self::Api>> {
+        let current_point = self.get_current_emission_point();
+        let schedule_mapper = self.emission_schedule();
+        let nr_segments = schedule_mapper.len();
+
+        let mut result = MultiValueEncoded::new();
+        for index in 1..=nr_segments {
+            let is_over =
+                index < nr_segments && schedule_mapper.get(index + 1).start <= current_point;
+            if !is_over {
+                result.push(schedule_mapper.get(index));
+            }
+        }
+
+        result
+    }
+
+    /// The rewards to be emitted from the current block (or timestamp) until `end`, if the
+    /// reward production is not stopped in the meantime.
+    #[view(getProjectedEmissions)]
+    fn get_projected_emissions(&self, end: u64) -> BigUint {
+        let current_point = self.get_current_emission_point();
+        let base_rate = self.get_base_reward_rate();
+
+        self.calculate_scheduled_rewards(current_point, end, &base_rate)
     }
 
     #[inline]
@@ -32,4 +184,7 @@ pub trait RewardsModule:
     #[view(getRewardReserve)]
     #[storage_mapper("reward_reserve")]
     fn reward_reserve(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("emissionSchedule")]
+    fn emission_schedule(&self) -> VecMapper<EmissionSegment<Self::Api>>;
 }
@@ -0,0 +1,122 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+#[derive(TypeAbi, TopEncode)]
+pub struct DepositEvent<M: ManagedTypeApi> {
+    lp_amount: BigUint<M>,
+    shares: BigUint<M>,
+    total_lp_tokens: BigUint<M>,
+    total_shares: BigUint<M>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct WithdrawEvent<M: ManagedTypeApi> {
+    shares: BigUint<M>,
+    lp_amount: BigUint<M>,
+    total_lp_tokens: BigUint<M>,
+    total_shares: BigUint<M>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct HarvestEvent<M: ManagedTypeApi> {
+    rewards: BigUint<M>,
+    bounty: BigUint<M>,
+    lp_added: BigUint<M>,
+    total_lp_tokens: BigUint<M>,
+    total_shares: BigUint<M>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[multiversx_sc::module]
+pub trait EventsModule:
+    crate::shares::SharesModule
+    + crate::farm_interactions::FarmInteractionsModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+{
+    fn emit_deposit_event(&self, lp_amount: BigUint, shares: BigUint) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.deposit_event(
+            caller,
+            epoch,
+            DepositEvent {
+                lp_amount,
+                shares,
+                total_lp_tokens: self.get_total_lp_tokens(),
+                total_shares: self.total_shares().get(),
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_withdraw_event(&self, shares: BigUint, lp_amount: BigUint) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.withdraw_event(
+            caller,
+            epoch,
+            WithdrawEvent {
+                shares,
+                lp_amount,
+                total_lp_tokens: self.get_total_lp_tokens(),
+                total_shares: self.total_shares().get(),
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_harvest_event(&self, rewards: BigUint, bounty: BigUint, lp_added: BigUint) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.harvest_event(
+            caller,
+            epoch,
+            HarvestEvent {
+                rewards,
+                bounty,
+                lp_added,
+                total_lp_tokens: self.get_total_lp_tokens(),
+                total_shares: self.total_shares().get(),
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    #[event("deposit")]
+    fn deposit_event(
+        &self,
+        #[indexed] caller: ManagedAddress,
+        #[indexed] epoch: u64,
+        deposit_event: DepositEvent<Self::Api>,
+    );
+
+    #[event("withdraw")]
+    fn withdraw_event(
+        &self,
+        #[indexed] caller: ManagedAddress,
+        #[indexed] epoch: u64,
+        withdraw_event: WithdrawEvent<Self::Api>,
+    );
+
+    #[event("harvest")]
+    fn harvest_event(
+        &self,
+        #[indexed] caller: ManagedAddress,
+        #[indexed] epoch: u64,
+        harvest_event: HarvestEvent<Self::Api>,
+    );
+}
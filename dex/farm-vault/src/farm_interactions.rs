@@ -0,0 +1,219 @@
+multiversx_sc::imports!();
+
+use farm::{
+    base_functions::DoubleMultiPayment, AllRewardsResultType, EnterFarmResultType, ProxyTrait as _,
+};
+use multiversx_sc::storage::StorageKey;
+
+use crate::FARM_MINIMUM_FARMING_EPOCHS_STORAGE_KEY;
+
+/// The vault keeps a separate farm position for each epoch in which it entered the farm,
+/// as merging positions resets their entering epoch, on which the exit penalty of the farm
+/// is based. Withdrawals exit the oldest positions first.
+///
+/// Once the two oldest positions are past the minimum farming epochs of the farm, they are
+/// merged, at most once per call, which keeps the number of positions bounded.
+#[multiversx_sc::module]
+pub trait FarmInteractionsModule {
+    /// Claims the rewards of the vault positions, which are added to the pending rewards.
+    /// Returns the claimed farm rewards, without the additional ones.
+    fn claim_farm_rewards(&self) -> BigUint {
+        let farm_address = self.farm_address().get();
+        let mut positions_mapper = self.farm_positions();
+        let mut total_rewards = BigUint::zero();
+        for entering_epoch in positions_mapper.keys().collect::<ManagedVec<u64>>().iter() {
+            let position = positions_mapper.get(&entering_epoch).unwrap();
+            let result: AllRewardsResultType<Self::Api> = self
+                .farm_proxy(farm_address.clone())
+                .claim_rewards_endpoint(OptionalValue::<ManagedAddress>::None)
+                .with_esdt_transfer(position)
+                .execute_on_dest_context();
+            let (new_position, rewards, additional_rewards) = result.into_tuple();
+            positions_mapper.insert(entering_epoch, new_position);
+
+            total_rewards += &rewards.amount;
+            self.store_rewards(rewards.amount, additional_rewards);
+        }
+
+        total_rewards
+    }
+
+    /// The LP tokens are merged with the position entered in the current epoch, if any.
+    fn enter_farm(&self, lp_tokens: EsdtTokenPayment) {
+        let current_epoch = self.blockchain().get_block_epoch();
+        let mut positions_mapper = self.farm_positions();
+        let mut payments = ManagedVec::from_single_item(lp_tokens);
+        if let Some(current_epoch_position) = positions_mapper.get(&current_epoch) {
+            payments.push(current_epoch_position);
+        }
+
+        let result: EnterFarmResultType<Self::Api> = self
+            .farm_proxy(self.farm_address().get())
+            .enter_farm_endpoint(OptionalValue::<ManagedAddress>::None)
+            .with_multi_token_transfer(payments)
+            .execute_on_dest_context();
+        let (new_position, boosted_rewards) = result.into_tuple();
+        positions_mapper.insert(current_epoch, new_position);
+        self.store_rewards(boosted_rewards.amount, MultiValueEncoded::new());
+
+        self.merge_oldest_positions();
+    }
+
+    /// Exits `amount` from the oldest positions and returns the received LP tokens.
+    /// The farm may apply its exit penalty. The rewards of the exited part are kept.
+    fn exit_farm(&self, amount: BigUint) -> BigUint {
+        let farm_address = self.farm_address().get();
+        let mut positions_mapper = self.farm_positions();
+        let mut remaining_amount = amount;
+        let mut lp_amount = BigUint::zero();
+        for entering_epoch in positions_mapper.keys().collect::<ManagedVec<u64>>().iter() {
+            if remaining_amount == 0u64 {
+                break;
+            }
+
+            let mut position = positions_mapper.get(&entering_epoch).unwrap();
+            let exit_amount = core::cmp::min(remaining_amount.clone(), position.amount.clone());
+            let exit_payment = EsdtTokenPayment::new(
+                position.token_identifier.clone(),
+                position.token_nonce,
+                exit_amount.clone(),
+            );
+
+            let result: AllRewardsResultType<Self::Api> = self
+                .farm_proxy(farm_address.clone())
+                .exit_farm_endpoint(OptionalValue::<ManagedAddress>::None)
+                .with_esdt_transfer(exit_payment)
+                .execute_on_dest_context();
+            let (lp_tokens, rewards, additional_rewards) = result.into_tuple();
+            lp_amount += lp_tokens.amount;
+            self.store_rewards(rewards.amount, additional_rewards);
+
+            remaining_amount -= &exit_amount;
+            position.amount -= exit_amount;
+            if position.amount == 0u64 {
+                let _ = positions_mapper.remove(&entering_epoch);
+            } else {
+                positions_mapper.insert(entering_epoch, position);
+            }
+        }
+
+        require!(remaining_amount == 0u64, "Not enough farm tokens");
+
+        lp_amount
+    }
+
+    /// Merges the two oldest positions, if both are past the minimum farming epochs.
+    /// The merged position keeps the entering epoch of the newer one.
+    fn merge_oldest_positions(&self) {
+        let mut positions_mapper = self.farm_positions();
+        let mut entering_epochs = positions_mapper.keys();
+        let (older_epoch, newer_epoch) = match (entering_epochs.next(), entering_epochs.next()) {
+            (Some(older_epoch), Some(newer_epoch)) => (older_epoch, newer_epoch),
+            _ => return,
+        };
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        if newer_epoch + self.get_farm_minimum_farming_epochs() > current_epoch {
+            return;
+        }
+
+        let mut payments = ManagedVec::new();
+        payments.push(positions_mapper.remove(&older_epoch).unwrap());
+        payments.push(positions_mapper.get(&newer_epoch).unwrap());
+
+        let result: DoubleMultiPayment<Self::Api> = self
+            .farm_proxy(self.farm_address().get())
+            .merge_farm_tokens_endpoint(OptionalValue::<ManagedAddress>::None)
+            .with_multi_token_transfer(payments)
+            .execute_on_dest_context();
+        let (merged_position, boosted_rewards) = result.into_tuple();
+        positions_mapper.insert(newer_epoch, merged_position);
+        self.store_rewards(boosted_rewards.amount, MultiValueEncoded::new());
+    }
+
+    /// Only the rewards received from the farm are compounded, not the whole balance of the
+    /// vault. The additional rewards are kept for the share holders, who receive their part
+    /// when withdrawing.
+    fn store_rewards(
+        &self,
+        rewards_amount: BigUint,
+        additional_rewards: MultiValueEncoded<EsdtTokenPayment>,
+    ) {
+        self.pending_rewards()
+            .update(|pending| *pending += rewards_amount);
+        for additional_reward in additional_rewards {
+            if additional_reward.amount == 0u64 {
+                continue;
+            }
+
+            let mut additional_rewards_mapper = self.additional_rewards();
+            let amount = additional_rewards_mapper
+                .get(&additional_reward.token_identifier)
+                .unwrap_or_default();
+            additional_rewards_mapper.insert(
+                additional_reward.token_identifier,
+                amount + additional_reward.amount,
+            );
+        }
+    }
+
+    fn get_farm_minimum_farming_epochs(&self) -> u64 {
+        SingleValueMapper::<_, _, ManagedAddress>::new_from_address(
+            self.farm_address().get(),
+            StorageKey::new(FARM_MINIMUM_FARMING_EPOCHS_STORAGE_KEY),
+        )
+        .get()
+    }
+
+    /// The farm tokens are issued one to one for the LP tokens.
+    #[view(getTotalLpTokens)]
+    fn get_total_lp_tokens(&self) -> BigUint {
+        let mut total_lp_tokens = BigUint::zero();
+        for position in self.farm_positions().values() {
+            total_lp_tokens += position.amount;
+        }
+
+        total_lp_tokens
+    }
+
+    #[view(getAdditionalRewards)]
+    fn get_additional_rewards(&self) -> MultiValueEncoded<EsdtTokenPayment> {
+        let mut result = MultiValueEncoded::new();
+        for (token_id, amount) in self.additional_rewards().iter() {
+            result.push(EsdtTokenPayment::new(token_id, 0, amount));
+        }
+
+        result
+    }
+
+    #[view(getFarmPositions)]
+    fn get_farm_positions(&self) -> MultiValueEncoded<MultiValue2<u64, EsdtTokenPayment>> {
+        let mut result = MultiValueEncoded::new();
+        for (entering_epoch, position) in self.farm_positions().iter() {
+            result.push((entering_epoch, position).into());
+        }
+
+        result
+    }
+
+    #[proxy]
+    fn farm_proxy(&self, to: ManagedAddress) -> farm::Proxy<Self::Api>;
+
+    #[view(getFarmAddress)]
+    #[storage_mapper("farmAddress")]
+    fn farm_address(&self) -> SingleValueMapper<ManagedAddress>;
+
+    /// The rewards received from the farm which are not compounded yet.
+    #[view(getPendingRewards)]
+    #[storage_mapper("pendingRewards")]
+    fn pending_rewards(&self) -> SingleValueMapper<BigUint>;
+
+    /// The additional rewards of the farm, by token, which are not paid to the share holders
+    /// yet.
+    #[storage_mapper("additionalRewards")]
+    fn additional_rewards(&self) -> MapMapper<TokenIdentifier, BigUint>;
+
+    /// The farm positions of the vault, by entering epoch, from the oldest one.
+    #[storage_mapper("farmPositions")]
+    fn farm_positions(&self) -> MapMapper<u64, EsdtTokenPayment>;
+}
@@ -0,0 +1,342 @@
+#![no_std]
+
+multiversx_sc::imports!();
+
+pub mod events;
+pub mod farm_interactions;
+pub mod pair_interactions;
+pub mod shares;
+
+use multiversx_sc::storage::StorageKey;
+use pair::read_pair_storage;
+
+pub const MAX_PERCENTAGE: u64 = 10_000;
+pub const MAX_HARVEST_BOUNTY_PERCENT: u64 = 500;
+
+pub static FARM_FARMING_TOKEN_ID_STORAGE_KEY: &[u8] = b"farming_token_id";
+pub static FARM_REWARD_TOKEN_ID_STORAGE_KEY: &[u8] = b"reward_token_id";
+pub static FARM_MINIMUM_FARMING_EPOCHS_STORAGE_KEY: &[u8] = b"minimum_farming_epochs";
+pub static PAIR_LP_TOKEN_ID_STORAGE_KEY: &[u8] = b"lpTokenIdentifier";
+
+/// Holds a position in an LP farm on behalf of its depositors, who receive fungible shares.
+/// The rewards of the position are compounded by swapping half of them through the pair,
+/// adding liquidity and entering the farm again, which increases the LP tokens of each share.
+///
+/// The reward token of the farm has to be one of the tokens of the pair.
+#[multiversx_sc::contract]
+pub trait FarmVault:
+    farm_interactions::FarmInteractionsModule
+    + pair_interactions::PairInteractionsModule
+    + shares::SharesModule
+    + events::EventsModule
+    + read_pair_storage::ReadPairStorageModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+    + multiversx_sc_modules::pause::PauseModule
+{
+    #[init]
+    fn init(
+        &self,
+        pair_address: ManagedAddress,
+        farm_address: ManagedAddress,
+        harvest_bounty_percent: u64,
+        min_compound_amount: BigUint,
+        max_slippage_percent: u64,
+    ) {
+        require!(
+            self.blockchain().is_smart_contract(&pair_address),
+            "Invalid pair address"
+        );
+        require!(
+            self.blockchain().is_smart_contract(&farm_address),
+            "Invalid farm address"
+        );
+
+        let lp_token_id = self.read_token_id(&pair_address, PAIR_LP_TOKEN_ID_STORAGE_KEY);
+        let farming_token_id = self.read_token_id(&farm_address, FARM_FARMING_TOKEN_ID_STORAGE_KEY);
+        let reward_token_id = self.read_token_id(&farm_address, FARM_REWARD_TOKEN_ID_STORAGE_KEY);
+        require!(
+            farming_token_id == lp_token_id,
+            "Farm does not match the pair"
+        );
+
+        let first_token_id = self.get_first_token_id_mapper(pair_address.clone()).get();
+        let second_token_id = self.get_second_token_id_mapper(pair_address.clone()).get();
+        let other_token_id = if reward_token_id == first_token_id {
+            second_token_id
+        } else if reward_token_id == second_token_id {
+            first_token_id
+        } else {
+            sc_panic!("Reward token not in the pair");
+        };
+
+        self.pair_address().set(pair_address);
+        self.farm_address().set(farm_address);
+        self.lp_token_id().set(lp_token_id);
+        self.reward_token_id().set(reward_token_id);
+        self.other_token_id().set(other_token_id);
+        self.set_harvest_bounty_percent(harvest_bounty_percent);
+        self.set_min_compound_amount(min_compound_amount);
+        self.set_max_slippage_percent(max_slippage_percent);
+    }
+
+    #[upgrade]
+    fn upgrade(&self) {}
+
+    /// The callers of `harvest` receive this part of the claimed rewards, in basis points.
+    #[only_owner]
+    #[endpoint(setHarvestBountyPercent)]
+    fn set_harvest_bounty_percent(&self, harvest_bounty_percent: u64) {
+        require!(
+            harvest_bounty_percent <= MAX_HARVEST_BOUNTY_PERCENT,
+            "Harvest bounty percent too high"
+        );
+
+        self.harvest_bounty_percent().set(harvest_bounty_percent);
+    }
+
+    /// Below this amount, the rewards are kept in the vault until the next compounding,
+    /// as the swap output would be too low.
+    #[only_owner]
+    #[endpoint(setMinCompoundAmount)]
+    fn set_min_compound_amount(&self, min_compound_amount: BigUint) {
+        require!(min_compound_amount > 1u64, "Invalid min compound amount");
+
+        self.min_compound_amount().set(min_compound_amount);
+    }
+
+    /// Deposits LP tokens in the farm, in exchange for vault shares. The pending rewards
+    /// are compounded first, so that they go to the existing shares. The first deposit locks
+    /// MINIMUM_SHARES in the vault.
+    #[payable("*")]
+    #[endpoint]
+    fn deposit(&self) -> EsdtTokenPayment {
+        self.require_not_paused();
+        require!(!self.share_token().is_empty(), "Share token not issued");
+
+        let payment = self.call_value().single_esdt();
+        require!(
+            payment.token_identifier == self.lp_token_id().get() && payment.amount > 0u64,
+            "Invalid payment"
+        );
+
+        let _ = self.claim_farm_rewards();
+        let _ = self.compound_rewards();
+
+        let share_payment = self.mint_shares_for_lp_tokens(payment.amount.clone());
+        self.enter_farm(payment.clone());
+
+        let caller = self.blockchain().get_caller();
+        self.send().direct_esdt(
+            &caller,
+            &share_payment.token_identifier,
+            0,
+            &share_payment.amount,
+        );
+        self.emit_deposit_event(payment.amount, share_payment.amount.clone());
+
+        share_payment
+    }
+
+    /// Burns the shares and sends their part of the LP tokens and of the additional rewards.
+    /// The pending rewards are not compounded, so that withdrawals never depend on the pair,
+    /// and they are left to the remaining shares. The LP tokens are taken from the oldest farm
+    /// positions of the vault, so the exit penalty of the farm only applies to the part
+    /// entered during its minimum farming epochs, if the older positions are not enough.
+    #[payable("*")]
+    #[endpoint]
+    fn withdraw(&self) -> EsdtTokenPayment {
+        let payment = self.call_value().single_esdt();
+        self.share_token()
+            .require_same_token(&payment.token_identifier);
+
+        let lp_amount = self.get_lp_tokens_for_shares(payment.amount.clone());
+        require!(lp_amount > 0u64, "Withdraw amount too low");
+
+        let total_shares = self.total_shares().get();
+        self.burn_shares(&payment.amount);
+        let lp_amount_received = self.exit_farm(lp_amount);
+        let lp_tokens = EsdtTokenPayment::new(self.lp_token_id().get(), 0, lp_amount_received);
+
+        let caller = self.blockchain().get_caller();
+        self.send()
+            .direct_esdt(&caller, &lp_tokens.token_identifier, 0, &lp_tokens.amount);
+        self.send_additional_rewards(&caller, &payment.amount, &total_shares);
+        self.emit_withdraw_event(payment.amount, lp_tokens.amount.clone());
+
+        lp_tokens
+    }
+
+    /// Claims the rewards of the vault and compounds them. Anyone can call it, and receives
+    /// a part of the claimed rewards as bounty.
+    #[endpoint]
+    fn harvest(&self) -> EsdtTokenPayment {
+        self.require_not_paused();
+
+        let rewards = self.claim_farm_rewards();
+        let bounty_amount = &rewards * self.harvest_bounty_percent().get() / MAX_PERCENTAGE;
+        let reward_token_id = self.reward_token_id().get();
+        if bounty_amount > 0u64 {
+            self.pending_rewards()
+                .update(|pending| *pending -= &bounty_amount);
+
+            let caller = self.blockchain().get_caller();
+            self.send()
+                .direct_esdt(&caller, &reward_token_id, 0, &bounty_amount);
+        }
+
+        let lp_added = self.compound_rewards();
+        require!(lp_added > 0u64, "Not enough rewards to compound");
+
+        self.emit_harvest_event(rewards, bounty_amount.clone(), lp_added);
+
+        EsdtTokenPayment::new(reward_token_id, 0, bounty_amount)
+    }
+
+    /// Sends the tokens sent to the vault by mistake to the owner. The additional rewards of
+    /// the farm belong to the share holders, so only the amount above them is sent.
+    #[only_owner]
+    #[endpoint(recoverTokens)]
+    fn recover_tokens(&self, token_id: TokenIdentifier) -> EsdtTokenPayment {
+        require!(
+            token_id != self.lp_token_id().get()
+                && token_id != self.reward_token_id().get()
+                && token_id != self.other_token_id().get(),
+            "Token used by the vault"
+        );
+        require!(
+            self.share_token().is_empty() || token_id != self.share_token().get_token_id(),
+            "Token used by the vault"
+        );
+        require!(
+            !self
+                .farm_positions()
+                .values()
+                .any(|position| position.token_identifier == token_id),
+            "Token used by the vault"
+        );
+
+        let additional_rewards = self.additional_rewards().get(&token_id).unwrap_or_default();
+        let amount = self.get_vault_balance(&token_id) - additional_rewards;
+        let owner = self.blockchain().get_caller();
+        self.send().direct_esdt(&owner, &token_id, 0, &amount);
+
+        EsdtTokenPayment::new(token_id, 0, amount)
+    }
+
+    /// Sends the part of the additional rewards of the farm for `shares`, out of
+    /// `total_shares`.
+    fn send_additional_rewards(
+        &self,
+        to: &ManagedAddress,
+        shares: &BigUint,
+        total_shares: &BigUint,
+    ) {
+        let mut additional_rewards_mapper = self.additional_rewards();
+        let mut payments = ManagedVec::new();
+        for (token_id, amount) in additional_rewards_mapper.iter() {
+            let share_amount = &amount * shares / total_shares;
+            if share_amount > 0u64 {
+                payments.push(EsdtTokenPayment::new(token_id, 0, share_amount));
+            }
+        }
+
+        for payment in payments.iter() {
+            let amount = additional_rewards_mapper
+                .get(&payment.token_identifier)
+                .unwrap();
+            if amount == payment.amount {
+                let _ = additional_rewards_mapper.remove(&payment.token_identifier);
+            } else {
+                additional_rewards_mapper
+                    .insert(payment.token_identifier.clone(), amount - &payment.amount);
+            }
+        }
+
+        if !payments.is_empty() {
+            self.send().direct_multi(to, &payments);
+        }
+    }
+
+    /// Swaps half of the pending rewards, adds liquidity and enters the farm with the
+    /// received LP tokens. The amounts not used by the pair are kept for the next call.
+    fn compound_rewards(&self) -> BigUint {
+        let reward_amount = self.pending_rewards().get();
+        if reward_amount < self.min_compound_amount().get() {
+            return BigUint::zero();
+        }
+
+        let reward_token_id = self.reward_token_id().get();
+        let other_token_id = self.other_token_id().get();
+        let swap_amount = &reward_amount / 2u64;
+        let other_amount = self.swap_tokens(
+            EsdtTokenPayment::new(reward_token_id.clone(), 0, swap_amount.clone()),
+            other_token_id.clone(),
+        ) + self.other_token_leftover().get();
+
+        let reward_payment = EsdtTokenPayment::new(reward_token_id, 0, reward_amount - swap_amount);
+        let other_payment = EsdtTokenPayment::new(other_token_id, 0, other_amount);
+
+        let pair_address = self.pair_address().get();
+        let first_token_id = self.get_first_token_id_mapper(pair_address).get();
+        let reward_token_is_first = reward_payment.token_identifier == first_token_id;
+        let result = if reward_token_is_first {
+            self.add_liquidity(reward_payment.clone(), other_payment.clone())
+        } else {
+            self.add_liquidity(other_payment.clone(), reward_payment.clone())
+        };
+        let (lp_tokens, first_used, second_used) = result.into_tuple();
+        let (reward_used, other_used) = if reward_token_is_first {
+            (first_used, second_used)
+        } else {
+            (second_used, first_used)
+        };
+
+        self.pending_rewards()
+            .set(reward_payment.amount - reward_used.amount);
+        self.other_token_leftover()
+            .set(other_payment.amount - other_used.amount);
+
+        let lp_amount = lp_tokens.amount.clone();
+        self.enter_farm(lp_tokens);
+
+        lp_amount
+    }
+
+    fn get_vault_balance(&self, token_id: &TokenIdentifier) -> BigUint {
+        self.blockchain()
+            .get_sc_balance(&EgldOrEsdtTokenIdentifier::esdt(token_id.clone()), 0)
+    }
+
+    fn read_token_id(&self, address: &ManagedAddress, storage_key: &[u8]) -> TokenIdentifier {
+        SingleValueMapper::<_, _, ManagedAddress>::new_from_address(
+            address.clone(),
+            StorageKey::new(storage_key),
+        )
+        .get()
+    }
+
+    #[view(getLpTokenId)]
+    #[storage_mapper("lpTokenId")]
+    fn lp_token_id(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[view(getRewardTokenId)]
+    #[storage_mapper("rewardTokenId")]
+    fn reward_token_id(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[view(getOtherTokenId)]
+    #[storage_mapper("otherTokenId")]
+    fn other_token_id(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    /// The other token of the pair left over by the previous liquidity addition.
+    #[view(getOtherTokenLeftover)]
+    #[storage_mapper("otherTokenLeftover")]
+    fn other_token_leftover(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getHarvestBountyPercent)]
+    #[storage_mapper("harvestBountyPercent")]
+    fn harvest_bounty_percent(&self) -> SingleValueMapper<u64>;
+
+    #[view(getMinCompoundAmount)]
+    #[storage_mapper("minCompoundAmount")]
+    fn min_compound_amount(&self) -> SingleValueMapper<BigUint>;
+}
@@ -0,0 +1,122 @@
+multiversx_sc::imports!();
+
+use pair::{
+    pair_actions::{
+        add_liq::ProxyTrait as _, common_result_types::AddLiquidityResultType,
+        swap::ProxyTrait as _, views::ProxyTrait as _,
+    },
+    safe_price_view::ProxyTrait as _,
+};
+
+use crate::MAX_PERCENTAGE;
+
+/// The minimum amount of the swaps is derived from the safe price of the pair, with the
+/// configured slippage, so that compounding can't be sandwiched. The minimum amounts of the
+/// added liquidity are derived from the amounts the pair will actually use.
+#[multiversx_sc::module]
+pub trait PairInteractionsModule {
+    /// The maximum difference from the safe price of the pair accepted when compounding,
+    /// in basis points.
+    #[only_owner]
+    #[endpoint(setMaxSlippagePercent)]
+    fn set_max_slippage_percent(&self, max_slippage_percent: u64) {
+        require!(
+            max_slippage_percent > 0 && max_slippage_percent < MAX_PERCENTAGE,
+            "Invalid max slippage percent"
+        );
+
+        self.max_slippage_percent().set(max_slippage_percent);
+    }
+
+    fn swap_tokens(&self, payment: EsdtTokenPayment, token_out: TokenIdentifier) -> BigUint {
+        let pair_address = self.pair_address().get();
+        let safe_price: EsdtTokenPayment = self
+            .pair_proxy(pair_address.clone())
+            .update_and_get_safe_price(payment.clone())
+            .execute_on_dest_context();
+        let min_amount_out = self.apply_max_slippage(&safe_price.amount);
+        require!(min_amount_out > 0u64, "Swap amount too low");
+
+        let output: EsdtTokenPayment = self
+            .pair_proxy(pair_address)
+            .swap_tokens_fixed_input(
+                token_out,
+                min_amount_out,
+                OptionalValue::<ManagedAddress>::None,
+            )
+            .with_esdt_transfer(payment)
+            .execute_on_dest_context();
+
+        output.amount
+    }
+
+    /// The payments have to be in the order of the pair tokens. The unused amounts are sent
+    /// back to the vault by the pair, and the used ones are returned after the LP tokens.
+    fn add_liquidity(
+        &self,
+        first_payment: EsdtTokenPayment,
+        second_payment: EsdtTokenPayment,
+    ) -> AddLiquidityResultType<Self::Api> {
+        let (first_token_amount, second_token_amount) =
+            self.get_used_liquidity_amounts(&first_payment, &second_payment);
+        let first_token_amount_min = self.apply_max_slippage(&first_token_amount);
+        let second_token_amount_min = self.apply_max_slippage(&second_token_amount);
+        require!(
+            first_token_amount_min > 0u64 && second_token_amount_min > 0u64,
+            "Liquidity amount too low"
+        );
+
+        let mut payments = ManagedVec::from_single_item(first_payment);
+        payments.push(second_payment);
+
+        self.pair_proxy(self.pair_address().get())
+            .add_liquidity(first_token_amount_min, second_token_amount_min)
+            .with_multi_token_transfer(payments)
+            .execute_on_dest_context()
+    }
+
+    /// The pair uses all of one of the payments, and the equivalent amount of the other one,
+    /// at its current reserves ratio.
+    fn get_used_liquidity_amounts(
+        &self,
+        first_payment: &EsdtTokenPayment,
+        second_payment: &EsdtTokenPayment,
+    ) -> (BigUint, BigUint) {
+        let pair_address = self.pair_address().get();
+        let second_token_equivalent: BigUint = self
+            .pair_proxy(pair_address.clone())
+            .get_equivalent(
+                first_payment.token_identifier.clone(),
+                first_payment.amount.clone(),
+            )
+            .execute_on_dest_context();
+        if second_token_equivalent <= second_payment.amount {
+            return (first_payment.amount.clone(), second_token_equivalent);
+        }
+
+        let first_token_equivalent: BigUint = self
+            .pair_proxy(pair_address)
+            .get_equivalent(
+                second_payment.token_identifier.clone(),
+                second_payment.amount.clone(),
+            )
+            .execute_on_dest_context();
+
+        (first_token_equivalent, second_payment.amount.clone())
+    }
+
+    fn apply_max_slippage(&self, amount: &BigUint) -> BigUint {
+        amount * (MAX_PERCENTAGE - self.max_slippage_percent().get()) / MAX_PERCENTAGE
+    }
+
+    #[proxy]
+    fn pair_proxy(&self, to: ManagedAddress) -> pair::Proxy<Self::Api>;
+
+    #[view(getPairAddress)]
+    #[storage_mapper("pairAddress")]
+    fn pair_address(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getMaxSlippagePercent)]
+    #[storage_mapper("maxSlippagePercent")]
+    fn max_slippage_percent(&self) -> SingleValueMapper<u64>;
+}
@@ -0,0 +1,96 @@
+multiversx_sc::imports!();
+
+pub const SHARE_PRICE_PRECISION: u64 = 1_000_000_000_000_000_000;
+pub const MINIMUM_SHARES: u64 = 1_000;
+
+#[multiversx_sc::module]
+pub trait SharesModule:
+    crate::farm_interactions::FarmInteractionsModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+{
+    #[only_owner]
+    #[payable("EGLD")]
+    #[endpoint(registerShareToken)]
+    fn register_share_token(
+        &self,
+        token_display_name: ManagedBuffer,
+        token_ticker: ManagedBuffer,
+        num_decimals: usize,
+    ) {
+        let payment_amount = self.call_value().egld_value().clone_value();
+        self.share_token().issue_and_set_all_roles(
+            payment_amount,
+            token_display_name,
+            token_ticker,
+            num_decimals,
+            None,
+        );
+    }
+
+    /// The amount of LP tokens for one share, scaled by 10^18.
+    #[view(getSharePrice)]
+    fn get_share_price(&self) -> BigUint {
+        let total_shares = self.total_shares().get();
+        if total_shares == 0u64 {
+            return BigUint::from(SHARE_PRICE_PRECISION);
+        }
+
+        self.get_total_lp_tokens() * SHARE_PRICE_PRECISION / total_shares
+    }
+
+    /// The LP tokens received for the shares, before the exit penalty of the farm,
+    /// and without the rewards that are not compounded yet.
+    #[view(getLpTokensForShares)]
+    fn get_lp_tokens_for_shares(&self, shares: BigUint) -> BigUint {
+        let total_shares = self.total_shares().get();
+        if total_shares == 0u64 {
+            return BigUint::zero();
+        }
+
+        shares * self.get_total_lp_tokens() / total_shares
+    }
+
+    #[view(getSharesForLpTokens)]
+    fn get_shares_for_lp_tokens(&self, lp_amount: BigUint) -> BigUint {
+        let total_shares = self.total_shares().get();
+        let total_lp_tokens = self.get_total_lp_tokens();
+        if total_shares == 0u64 || total_lp_tokens == 0u64 {
+            return lp_amount;
+        }
+
+        lp_amount * total_shares / total_lp_tokens
+    }
+
+    /// The first deposit locks MINIMUM_SHARES in the vault, which can never be withdrawn, so
+    /// that the first depositor can't inflate the share price to round down the next deposits.
+    fn mint_shares_for_lp_tokens(&self, lp_amount: BigUint) -> EsdtTokenPayment {
+        let mut shares = self.get_shares_for_lp_tokens(lp_amount);
+        if self.total_shares().is_empty() {
+            require!(shares > MINIMUM_SHARES, "Deposit amount too low");
+
+            shares -= MINIMUM_SHARES;
+            self.total_shares().set(BigUint::from(MINIMUM_SHARES));
+        }
+        require!(shares > 0u64, "Deposit amount too low");
+
+        self.mint_shares(shares)
+    }
+
+    fn mint_shares(&self, amount: BigUint) -> EsdtTokenPayment {
+        self.total_shares().update(|total| *total += &amount);
+        self.share_token().mint(amount)
+    }
+
+    fn burn_shares(&self, amount: &BigUint) {
+        self.total_shares().update(|total| *total -= amount);
+        self.share_token().burn(amount);
+    }
+
+    #[view(getShareTokenId)]
+    #[storage_mapper("shareTokenId")]
+    fn share_token(&self) -> FungibleTokenMapper;
+
+    #[view(getTotalShares)]
+    #[storage_mapper("totalShares")]
+    fn total_shares(&self) -> SingleValueMapper<BigUint>;
+}
@@ -0,0 +1,829 @@
+#![allow(deprecated)]
+
+use config::ConfigModule;
+use farm::{additional_rewards::AdditionalRewardsModule, exit_penalty::ExitPenaltyModule, Farm};
+use farm_token::FarmTokenModule;
+use farm_vault::{farm_interactions::FarmInteractionsModule, shares::SharesModule, FarmVault};
+use multiversx_sc::{
+    codec::multi_types::OptionalValue,
+    storage::mappers::StorageTokenWrapper,
+    types::{Address, EsdtLocalRole, ManagedAddress, MultiValueEncoded},
+};
+use multiversx_sc_scenario::{
+    managed_address, managed_biguint, managed_token_id, rust_biguint,
+    whitebox_legacy::{BlockchainStateWrapper, ContractObjWrapper, TxTokenTransfer},
+    DebugApi,
+};
+use pair::{
+    config::ConfigModule as _,
+    pair_actions::{add_liq::AddLiquidityModule, swap::SwapModule},
+    Pair,
+};
+use pausable::{PausableModule, State};
+
+const FARM_VAULT_WASM_PATH: &str = "farm-vault/output/farm-vault.wasm";
+const FARM_WASM_PATH: &str = "farm/output/farm.wasm";
+const PAIR_WASM_PATH: &str = "pair/output/pair.wasm";
+
+const WEGLD_TOKEN_ID: &[u8] = b"WEGLD-abcdef";
+const MEX_TOKEN_ID: &[u8] = b"MEX-abcdef";
+const LP_TOKEN_ID: &[u8] = b"LPTOK-abcdef";
+const FARM_TOKEN_ID: &[u8] = b"FARM-abcdef";
+const SHARE_TOKEN_ID: &[u8] = b"VAULT-abcdef";
+const PARTNER_TOKEN_ID: &[u8] = b"PARTNER-abcdef";
+
+const INITIAL_RESERVE: u64 = 1_000_000_000;
+const USER_LP_BALANCE: u64 = 100_000;
+const PER_BLOCK_REWARD_AMOUNT: u64 = 5_000;
+const DIVISION_SAFETY_CONSTANT: u64 = 1_000_000_000_000;
+const MIN_FARMING_EPOCHS: u64 = 2;
+const PENALTY_PERCENT: u64 = 10;
+const HARVEST_BOUNTY_PERCENT: u64 = 100;
+const MIN_COMPOUND_AMOUNT: u64 = 1_000;
+const MAX_SLIPPAGE_PERCENT: u64 = 500;
+const SHARE_PRICE_PRECISION: u64 = 1_000_000_000_000_000_000;
+const MINIMUM_SHARES: u64 = 1_000;
+
+struct FarmVaultSetup<FarmVaultObjBuilder, FarmObjBuilder, PairObjBuilder>
+where
+    FarmVaultObjBuilder: 'static + Copy + Fn() -> farm_vault::ContractObj<DebugApi>,
+    FarmObjBuilder: 'static + Copy + Fn() -> farm::ContractObj<DebugApi>,
+    PairObjBuilder: 'static + Copy + Fn() -> pair::ContractObj<DebugApi>,
+{
+    b_mock: BlockchainStateWrapper,
+    owner_address: Address,
+    first_user: Address,
+    second_user: Address,
+    keeper_address: Address,
+    vault_wrapper: ContractObjWrapper<farm_vault::ContractObj<DebugApi>, FarmVaultObjBuilder>,
+    farm_wrapper: ContractObjWrapper<farm::ContractObj<DebugApi>, FarmObjBuilder>,
+    pair_wrapper: ContractObjWrapper<pair::ContractObj<DebugApi>, PairObjBuilder>,
+}
+
+impl<FarmVaultObjBuilder, FarmObjBuilder, PairObjBuilder>
+    FarmVaultSetup<FarmVaultObjBuilder, FarmObjBuilder, PairObjBuilder>
+where
+    FarmVaultObjBuilder: 'static + Copy + Fn() -> farm_vault::ContractObj<DebugApi>,
+    FarmObjBuilder: 'static + Copy + Fn() -> farm::ContractObj<DebugApi>,
+    PairObjBuilder: 'static + Copy + Fn() -> pair::ContractObj<DebugApi>,
+{
+    fn new(
+        vault_builder: FarmVaultObjBuilder,
+        farm_builder: FarmObjBuilder,
+        pair_builder: PairObjBuilder,
+    ) -> Self {
+        let rust_zero = rust_biguint!(0);
+        let mut b_mock = BlockchainStateWrapper::new();
+        let owner_address = b_mock.create_user_account(&rust_zero);
+        let first_user = b_mock.create_user_account(&rust_zero);
+        let second_user = b_mock.create_user_account(&rust_zero);
+        let keeper_address = b_mock.create_user_account(&rust_zero);
+
+        // pair
+
+        let pair_wrapper = b_mock.create_sc_account(
+            &rust_zero,
+            Some(&owner_address),
+            pair_builder,
+            PAIR_WASM_PATH,
+        );
+        b_mock
+            .execute_tx(&owner_address, &pair_wrapper, &rust_zero, |sc| {
+                sc.init(
+                    managed_token_id!(WEGLD_TOKEN_ID),
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_address!(&owner_address),
+                    managed_address!(&owner_address),
+                    300,
+                    50,
+                    ManagedAddress::zero(),
+                    MultiValueEncoded::new(),
+                );
+                sc.lp_token_identifier()
+                    .set(&managed_token_id!(LP_TOKEN_ID));
+                sc.state().set(State::Active);
+            })
+            .assert_ok();
+        b_mock.set_esdt_local_roles(
+            pair_wrapper.address_ref(),
+            LP_TOKEN_ID,
+            &[EsdtLocalRole::Mint, EsdtLocalRole::Burn],
+        );
+
+        b_mock.set_esdt_balance(
+            &owner_address,
+            WEGLD_TOKEN_ID,
+            &rust_biguint!(INITIAL_RESERVE * 2),
+        );
+        b_mock.set_esdt_balance(
+            &owner_address,
+            MEX_TOKEN_ID,
+            &rust_biguint!(INITIAL_RESERVE * 2),
+        );
+        let payments = vec![
+            TxTokenTransfer {
+                token_identifier: WEGLD_TOKEN_ID.to_vec(),
+                nonce: 0,
+                value: rust_biguint!(INITIAL_RESERVE),
+            },
+            TxTokenTransfer {
+                token_identifier: MEX_TOKEN_ID.to_vec(),
+                nonce: 0,
+                value: rust_biguint!(INITIAL_RESERVE),
+            },
+        ];
+        b_mock
+            .execute_esdt_multi_transfer(&owner_address, &pair_wrapper, &payments, |sc| {
+                let _ = sc.add_liquidity(managed_biguint!(1), managed_biguint!(1));
+            })
+            .assert_ok();
+
+        // records the first price observation, needed for the safe price of the pair
+        b_mock.set_block_round(1);
+        b_mock
+            .execute_esdt_multi_transfer(&owner_address, &pair_wrapper, &payments, |sc| {
+                let _ = sc.add_liquidity(managed_biguint!(1), managed_biguint!(1));
+            })
+            .assert_ok();
+
+        // farm
+
+        let farm_wrapper = b_mock.create_sc_account(
+            &rust_zero,
+            Some(&owner_address),
+            farm_builder,
+            FARM_WASM_PATH,
+        );
+        b_mock
+            .execute_tx(&owner_address, &farm_wrapper, &rust_zero, |sc| {
+                sc.init(
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_token_id!(LP_TOKEN_ID),
+                    managed_biguint!(DIVISION_SAFETY_CONSTANT),
+                    ManagedAddress::zero(),
+                    ManagedAddress::zero(),
+                    MultiValueEncoded::new(),
+                );
+
+                sc.farm_token()
+                    .set_token_id(managed_token_id!(FARM_TOKEN_ID));
+                sc.per_block_reward_amount()
+                    .set(&managed_biguint!(PER_BLOCK_REWARD_AMOUNT));
+                sc.minimum_farming_epochs().set(MIN_FARMING_EPOCHS);
+                sc.penalty_percent().set(PENALTY_PERCENT);
+
+                sc.state().set(State::Active);
+                sc.produce_rewards_enabled().set(true);
+            })
+            .assert_ok();
+        b_mock.set_esdt_local_roles(
+            farm_wrapper.address_ref(),
+            FARM_TOKEN_ID,
+            &[
+                EsdtLocalRole::NftCreate,
+                EsdtLocalRole::NftAddQuantity,
+                EsdtLocalRole::NftBurn,
+            ],
+        );
+        b_mock.set_esdt_local_roles(
+            farm_wrapper.address_ref(),
+            LP_TOKEN_ID,
+            &[EsdtLocalRole::Burn],
+        );
+        b_mock.set_esdt_local_roles(
+            farm_wrapper.address_ref(),
+            MEX_TOKEN_ID,
+            &[EsdtLocalRole::Mint],
+        );
+
+        // vault
+
+        let vault_wrapper = b_mock.create_sc_account(
+            &rust_zero,
+            Some(&owner_address),
+            vault_builder,
+            FARM_VAULT_WASM_PATH,
+        );
+        b_mock
+            .execute_tx(&owner_address, &vault_wrapper, &rust_zero, |sc| {
+                sc.init(
+                    managed_address!(pair_wrapper.address_ref()),
+                    managed_address!(farm_wrapper.address_ref()),
+                    HARVEST_BOUNTY_PERCENT,
+                    managed_biguint!(MIN_COMPOUND_AMOUNT),
+                    MAX_SLIPPAGE_PERCENT,
+                );
+                sc.share_token()
+                    .set_token_id(managed_token_id!(SHARE_TOKEN_ID));
+            })
+            .assert_ok();
+        b_mock.set_esdt_local_roles(
+            vault_wrapper.address_ref(),
+            SHARE_TOKEN_ID,
+            &[EsdtLocalRole::Mint, EsdtLocalRole::Burn],
+        );
+
+        b_mock.set_esdt_balance(&first_user, LP_TOKEN_ID, &rust_biguint!(USER_LP_BALANCE));
+        b_mock.set_esdt_balance(&second_user, LP_TOKEN_ID, &rust_biguint!(USER_LP_BALANCE));
+
+        FarmVaultSetup {
+            b_mock,
+            owner_address,
+            first_user,
+            second_user,
+            keeper_address,
+            vault_wrapper,
+            farm_wrapper,
+            pair_wrapper,
+        }
+    }
+
+    fn deposit(&mut self, user: &Address, amount: u64) -> u64 {
+        let mut shares = 0;
+        self.b_mock
+            .execute_esdt_transfer(
+                user,
+                &self.vault_wrapper,
+                LP_TOKEN_ID,
+                0,
+                &rust_biguint!(amount),
+                |sc| {
+                    let share_payment = sc.deposit();
+                    assert_eq!(
+                        share_payment.token_identifier,
+                        managed_token_id!(SHARE_TOKEN_ID)
+                    );
+                    shares = share_payment.amount.to_u64().unwrap();
+                },
+            )
+            .assert_ok();
+
+        shares
+    }
+
+    fn withdraw(&mut self, user: &Address, shares: u64) -> u64 {
+        let mut lp_amount = 0;
+        self.b_mock
+            .execute_esdt_transfer(
+                user,
+                &self.vault_wrapper,
+                SHARE_TOKEN_ID,
+                0,
+                &rust_biguint!(shares),
+                |sc| {
+                    let lp_tokens = sc.withdraw();
+                    assert_eq!(lp_tokens.token_identifier, managed_token_id!(LP_TOKEN_ID));
+                    lp_amount = lp_tokens.amount.to_u64().unwrap();
+                },
+            )
+            .assert_ok();
+
+        lp_amount
+    }
+
+    fn harvest(&mut self) -> u64 {
+        let keeper_address = self.keeper_address.clone();
+        let mut bounty = 0;
+        self.b_mock
+            .execute_tx(
+                &keeper_address,
+                &self.vault_wrapper,
+                &rust_biguint!(0),
+                |sc| {
+                    bounty = sc.harvest().amount.to_u64().unwrap();
+                },
+            )
+            .assert_ok();
+
+        bounty
+    }
+
+    fn check_farm_positions(&mut self, expected_positions: &[(u64, u64)]) {
+        self.b_mock
+            .execute_query(&self.vault_wrapper, |sc| {
+                let positions: Vec<(u64, u64)> = sc
+                    .get_farm_positions()
+                    .into_iter()
+                    .map(|position| {
+                        let (entering_epoch, farm_token) = position.into_tuple();
+                        (entering_epoch, farm_token.amount.to_u64().unwrap())
+                    })
+                    .collect();
+                assert_eq!(positions, expected_positions);
+            })
+            .assert_ok();
+    }
+
+    fn get_total_lp_tokens(&mut self) -> u64 {
+        let mut total_lp_tokens = 0;
+        self.b_mock
+            .execute_query(&self.vault_wrapper, |sc| {
+                total_lp_tokens = sc.get_total_lp_tokens().to_u64().unwrap();
+            })
+            .assert_ok();
+
+        total_lp_tokens
+    }
+}
+
+#[test]
+fn farm_vault_deposit_harvest_withdraw_test() {
+    let mut setup = FarmVaultSetup::new(
+        farm_vault::contract_obj,
+        farm::contract_obj,
+        pair::contract_obj,
+    );
+
+    let first_user = setup.first_user.clone();
+    let second_user = setup.second_user.clone();
+    let keeper_address = setup.keeper_address.clone();
+
+    // the first deposit locks the minimum shares in the vault
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &first_user,
+            &setup.vault_wrapper,
+            LP_TOKEN_ID,
+            0,
+            &rust_biguint!(MINIMUM_SHARES),
+            |sc| {
+                let _ = sc.deposit();
+            },
+        )
+        .assert_user_error("Deposit amount too low");
+
+    let first_user_shares = setup.deposit(&first_user, USER_LP_BALANCE);
+    assert_eq!(first_user_shares, USER_LP_BALANCE - MINIMUM_SHARES);
+    setup.b_mock.check_esdt_balance(
+        &first_user,
+        SHARE_TOKEN_ID,
+        &rust_biguint!(first_user_shares),
+    );
+
+    // the vault position is the only one in the farm
+    setup
+        .b_mock
+        .execute_query(&setup.farm_wrapper, |sc| {
+            assert_eq!(
+                sc.farm_token_supply().get(),
+                managed_biguint!(USER_LP_BALANCE)
+            );
+        })
+        .assert_ok();
+
+    // nothing to compound yet
+    setup
+        .b_mock
+        .execute_tx(
+            &keeper_address,
+            &setup.vault_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.harvest();
+            },
+        )
+        .assert_user_error("Not enough rewards to compound");
+
+    setup.b_mock.set_block_nonce(10);
+    setup.b_mock.set_block_round(10);
+
+    let mut lp_supply_before_harvest = 0u64;
+    setup
+        .b_mock
+        .execute_query(&setup.pair_wrapper, |sc| {
+            lp_supply_before_harvest = sc.lp_token_supply().get().to_u64().unwrap();
+        })
+        .assert_ok();
+
+    let rewards = 10 * PER_BLOCK_REWARD_AMOUNT;
+    let bounty = rewards * HARVEST_BOUNTY_PERCENT / 10_000;
+    setup
+        .b_mock
+        .execute_tx(
+            &keeper_address,
+            &setup.vault_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let bounty_payment = sc.harvest();
+                assert_eq!(bounty_payment.amount, managed_biguint!(bounty));
+            },
+        )
+        .assert_ok();
+    setup
+        .b_mock
+        .check_esdt_balance(&keeper_address, MEX_TOKEN_ID, &rust_biguint!(bounty));
+
+    // the compounded rewards increase the LP tokens of each share
+    let total_lp_after_harvest = setup.get_total_lp_tokens();
+    assert!(total_lp_after_harvest > USER_LP_BALANCE);
+    setup
+        .b_mock
+        .execute_query(&setup.pair_wrapper, |sc| {
+            assert_eq!(
+                sc.lp_token_supply().get(),
+                managed_biguint!(
+                    lp_supply_before_harvest + total_lp_after_harvest - USER_LP_BALANCE
+                )
+            );
+        })
+        .assert_ok();
+    setup
+        .b_mock
+        .execute_query(&setup.vault_wrapper, |sc| {
+            let expected_share_price =
+                managed_biguint!(total_lp_after_harvest) * SHARE_PRICE_PRECISION / USER_LP_BALANCE;
+            assert_eq!(sc.get_share_price(), expected_share_price);
+        })
+        .assert_ok();
+
+    // the second user receives fewer shares for the same LP tokens
+    let second_user_shares = setup.deposit(&second_user, USER_LP_BALANCE);
+    assert_eq!(
+        second_user_shares,
+        USER_LP_BALANCE * USER_LP_BALANCE / total_lp_after_harvest
+    );
+
+    // withdrawing after the minimum farming epochs avoids the exit penalty
+    setup.b_mock.set_block_epoch(10);
+
+    let total_lp_tokens = setup.get_total_lp_tokens();
+    let total_shares = MINIMUM_SHARES + first_user_shares + second_user_shares;
+    let first_user_lp = setup.withdraw(&first_user, first_user_shares);
+    assert_eq!(
+        first_user_lp,
+        first_user_shares * total_lp_tokens / total_shares
+    );
+    assert!(first_user_lp > first_user_shares);
+    setup
+        .b_mock
+        .check_esdt_balance(&first_user, LP_TOKEN_ID, &rust_biguint!(first_user_lp));
+    setup
+        .b_mock
+        .check_esdt_balance(&first_user, SHARE_TOKEN_ID, &rust_biguint!(0));
+
+    let second_user_lp = setup.withdraw(&second_user, second_user_shares);
+    assert_eq!(
+        second_user_lp,
+        second_user_shares * (total_lp_tokens - first_user_lp) / (total_shares - first_user_shares)
+    );
+
+    // the LP tokens of the locked shares stay in the farm
+    assert_eq!(
+        setup.get_total_lp_tokens(),
+        total_lp_tokens - first_user_lp - second_user_lp
+    );
+    setup
+        .b_mock
+        .execute_query(&setup.vault_wrapper, |sc| {
+            assert_eq!(sc.total_shares().get(), managed_biguint!(MINIMUM_SHARES));
+        })
+        .assert_ok();
+}
+
+#[test]
+fn farm_vault_recover_tokens_test() {
+    let mut setup = FarmVaultSetup::new(
+        farm_vault::contract_obj,
+        farm::contract_obj,
+        pair::contract_obj,
+    );
+
+    let owner_address = setup.owner_address.clone();
+    setup
+        .b_mock
+        .execute_tx(
+            &owner_address,
+            &setup.vault_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.recover_tokens(managed_token_id!(LP_TOKEN_ID));
+            },
+        )
+        .assert_user_error("Token used by the vault");
+
+    setup.b_mock.set_esdt_balance(
+        setup.vault_wrapper.address_ref(),
+        PARTNER_TOKEN_ID,
+        &rust_biguint!(1_000),
+    );
+    setup
+        .b_mock
+        .execute_tx(
+            &owner_address,
+            &setup.vault_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let payment = sc.recover_tokens(managed_token_id!(PARTNER_TOKEN_ID));
+                assert_eq!(payment.amount, managed_biguint!(1_000));
+            },
+        )
+        .assert_ok();
+    setup
+        .b_mock
+        .check_esdt_balance(&owner_address, PARTNER_TOKEN_ID, &rust_biguint!(1_000));
+}
+
+#[test]
+fn farm_vault_withdraw_after_harvest_test() {
+    let mut setup = FarmVaultSetup::new(
+        farm_vault::contract_obj,
+        farm::contract_obj,
+        pair::contract_obj,
+    );
+
+    let first_user = setup.first_user.clone();
+    let second_user = setup.second_user.clone();
+
+    let first_user_shares = setup.deposit(&first_user, USER_LP_BALANCE);
+    let second_user_shares = setup.deposit(&second_user, USER_LP_BALANCE);
+    setup.check_farm_positions(&[(0, 2 * USER_LP_BALANCE)]);
+
+    // the compounded rewards are kept in a separate position, entered in the current epoch
+    setup.b_mock.set_block_epoch(5);
+    setup.b_mock.set_block_nonce(10);
+    setup.b_mock.set_block_round(10);
+    let _ = setup.harvest();
+    let total_lp_tokens = setup.get_total_lp_tokens();
+    let compounded_lp = total_lp_tokens - 2 * USER_LP_BALANCE;
+    setup.check_farm_positions(&[(0, 2 * USER_LP_BALANCE), (5, compounded_lp)]);
+
+    // the LP tokens are taken from the oldest position, so no exit penalty applies
+    let total_shares = MINIMUM_SHARES + first_user_shares + second_user_shares;
+    let first_user_lp = setup.withdraw(&first_user, first_user_shares);
+    assert_eq!(
+        first_user_lp,
+        first_user_shares * total_lp_tokens / total_shares
+    );
+    setup.check_farm_positions(&[(0, 2 * USER_LP_BALANCE - first_user_lp), (5, compounded_lp)]);
+
+    // once both are past the minimum farming epochs, the oldest positions are merged
+    setup.b_mock.set_block_epoch(5 + MIN_FARMING_EPOCHS);
+    setup.b_mock.set_block_nonce(20);
+    setup.b_mock.set_block_round(20);
+    let _ = setup.harvest();
+    let total_lp_tokens = setup.get_total_lp_tokens();
+    let second_compounded_lp =
+        total_lp_tokens - (2 * USER_LP_BALANCE - first_user_lp) - compounded_lp;
+    setup.check_farm_positions(&[
+        (5, 2 * USER_LP_BALANCE - first_user_lp + compounded_lp),
+        (5 + MIN_FARMING_EPOCHS, second_compounded_lp),
+    ]);
+
+    setup.b_mock.set_block_epoch(5 + 2 * MIN_FARMING_EPOCHS);
+    let second_user_lp = setup.withdraw(&second_user, second_user_shares);
+    assert_eq!(
+        second_user_lp,
+        second_user_shares * total_lp_tokens / (MINIMUM_SHARES + second_user_shares)
+    );
+    assert_eq!(
+        setup.get_total_lp_tokens(),
+        total_lp_tokens - second_user_lp
+    );
+}
+
+#[test]
+fn farm_vault_harvest_slippage_test() {
+    let mut setup = FarmVaultSetup::new(
+        farm_vault::contract_obj,
+        farm::contract_obj,
+        pair::contract_obj,
+    );
+
+    let first_user = setup.first_user.clone();
+    let _ = setup.deposit(&first_user, USER_LP_BALANCE);
+
+    // the price of the pair is moved right before compounding
+    setup.b_mock.set_block_nonce(10);
+    setup.b_mock.set_block_round(10);
+    let owner_address = setup.owner_address.clone();
+    setup.b_mock.set_esdt_balance(
+        &owner_address,
+        MEX_TOKEN_ID,
+        &rust_biguint!(INITIAL_RESERVE),
+    );
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &owner_address,
+            &setup.pair_wrapper,
+            MEX_TOKEN_ID,
+            0,
+            &rust_biguint!(INITIAL_RESERVE),
+            |sc| {
+                let _ = sc.swap_tokens_fixed_input(
+                    managed_token_id!(WEGLD_TOKEN_ID),
+                    managed_biguint!(1),
+                    OptionalValue::None,
+                );
+            },
+        )
+        .assert_ok();
+
+    let keeper_address = setup.keeper_address.clone();
+    setup
+        .b_mock
+        .execute_tx(
+            &keeper_address,
+            &setup.vault_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.harvest();
+            },
+        )
+        .assert_user_error("Slippage exceeded");
+}
+
+#[test]
+fn farm_vault_withdraw_without_compounding_test() {
+    let mut setup = FarmVaultSetup::new(
+        farm_vault::contract_obj,
+        farm::contract_obj,
+        pair::contract_obj,
+    );
+
+    let first_user = setup.first_user.clone();
+    let first_user_shares = setup.deposit(&first_user, USER_LP_BALANCE);
+
+    // reward tokens sent to the vault are not compounded
+    let dust_amount = 1_000_000u64;
+    setup.b_mock.set_esdt_balance(
+        setup.vault_wrapper.address_ref(),
+        MEX_TOKEN_ID,
+        &rust_biguint!(dust_amount),
+    );
+
+    // compounding is not possible while the pair is inactive
+    setup.b_mock.set_block_nonce(10);
+    setup.b_mock.set_block_round(10);
+    let owner_address = setup.owner_address.clone();
+    setup
+        .b_mock
+        .execute_tx(
+            &owner_address,
+            &setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.state().set(State::Inactive);
+            },
+        )
+        .assert_ok();
+
+    let keeper_address = setup.keeper_address.clone();
+    setup
+        .b_mock
+        .execute_tx(
+            &keeper_address,
+            &setup.vault_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.harvest();
+            },
+        )
+        .assert_user_error("Swap is not enabled");
+
+    // withdrawals still work, and the rewards of the exited part are kept for compounding
+    setup.b_mock.set_block_epoch(MIN_FARMING_EPOCHS);
+    let total_lp_tokens = setup.get_total_lp_tokens();
+    let withdrawn_shares = first_user_shares / 2;
+    let first_user_lp = setup.withdraw(&first_user, withdrawn_shares);
+    assert_eq!(
+        first_user_lp,
+        withdrawn_shares * total_lp_tokens / (MINIMUM_SHARES + first_user_shares)
+    );
+
+    let exit_rewards = 10 * PER_BLOCK_REWARD_AMOUNT * first_user_lp / total_lp_tokens;
+    setup
+        .b_mock
+        .execute_query(&setup.vault_wrapper, |sc| {
+            assert_eq!(sc.pending_rewards().get(), managed_biguint!(exit_rewards));
+        })
+        .assert_ok();
+
+    setup
+        .b_mock
+        .execute_tx(
+            &owner_address,
+            &setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.state().set(State::Active);
+            },
+        )
+        .assert_ok();
+    let _ = setup.harvest();
+
+    // only the leftovers of the pair are pending after compounding
+    let mut pending_rewards = 0u64;
+    setup
+        .b_mock
+        .execute_query(&setup.vault_wrapper, |sc| {
+            pending_rewards = sc.pending_rewards().get().to_u64().unwrap();
+        })
+        .assert_ok();
+    setup.b_mock.check_esdt_balance(
+        setup.vault_wrapper.address_ref(),
+        MEX_TOKEN_ID,
+        &rust_biguint!(dust_amount + pending_rewards),
+    );
+}
+
+#[test]
+fn farm_vault_additional_rewards_test() {
+    let mut setup = FarmVaultSetup::new(
+        farm_vault::contract_obj,
+        farm::contract_obj,
+        pair::contract_obj,
+    );
+
+    // 1_000 partner tokens per block, between blocks 0 and 10
+    let owner_address = setup.owner_address.clone();
+    setup
+        .b_mock
+        .set_esdt_balance(&owner_address, PARTNER_TOKEN_ID, &rust_biguint!(10_000));
+    setup
+        .b_mock
+        .execute_tx(
+            &owner_address,
+            &setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.add_additional_reward_token(managed_token_id!(PARTNER_TOKEN_ID));
+            },
+        )
+        .assert_ok();
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &owner_address,
+            &setup.farm_wrapper,
+            PARTNER_TOKEN_ID,
+            0,
+            &rust_biguint!(10_000),
+            |sc| {
+                sc.deposit_additional_rewards(0, 10);
+            },
+        )
+        .assert_ok();
+
+    let first_user = setup.first_user.clone();
+    let second_user = setup.second_user.clone();
+    let first_user_shares = setup.deposit(&first_user, USER_LP_BALANCE);
+    let second_user_shares = setup.deposit(&second_user, USER_LP_BALANCE);
+
+    // the additional rewards are claimed by the vault, and kept for the share holders
+    setup.b_mock.set_block_nonce(10);
+    setup.b_mock.set_block_round(10);
+    let _ = setup.harvest();
+    setup
+        .b_mock
+        .execute_query(&setup.vault_wrapper, |sc| {
+            let additional_rewards: Vec<_> = sc.get_additional_rewards().into_iter().collect();
+            assert_eq!(additional_rewards.len(), 1);
+            assert_eq!(additional_rewards[0].amount, managed_biguint!(10_000));
+        })
+        .assert_ok();
+
+    // only the amount above the additional rewards can be recovered
+    setup.b_mock.set_esdt_balance(
+        setup.vault_wrapper.address_ref(),
+        PARTNER_TOKEN_ID,
+        &rust_biguint!(10_500),
+    );
+    setup
+        .b_mock
+        .execute_tx(
+            &owner_address,
+            &setup.vault_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let payment = sc.recover_tokens(managed_token_id!(PARTNER_TOKEN_ID));
+                assert_eq!(payment.amount, managed_biguint!(500));
+            },
+        )
+        .assert_ok();
+
+    // each withdrawal receives the part of the shares
+    let total_shares = MINIMUM_SHARES + first_user_shares + second_user_shares;
+    let first_user_rewards = 10_000 * first_user_shares / total_shares;
+    let _ = setup.withdraw(&first_user, first_user_shares);
+    setup.b_mock.check_esdt_balance(
+        &first_user,
+        PARTNER_TOKEN_ID,
+        &rust_biguint!(first_user_rewards),
+    );
+
+    let second_user_rewards =
+        (10_000 - first_user_rewards) * second_user_shares / (total_shares - first_user_shares);
+    let _ = setup.withdraw(&second_user, second_user_shares);
+    setup.b_mock.check_esdt_balance(
+        &second_user,
+        PARTNER_TOKEN_ID,
+        &rust_biguint!(second_user_rewards),
+    );
+    setup.b_mock.check_esdt_balance(
+        setup.vault_wrapper.address_ref(),
+        PARTNER_TOKEN_ID,
+        &rust_biguint!(10_000 - first_user_rewards - second_user_rewards),
+    );
+}
@@ -0,0 +1,55 @@
+// Code generated by the multiversx-sc build system. DO NOT EDIT.
+
+////////////////////////////////////////////////////
+////////////////// AUTO-GENERATED //////////////////
+////////////////////////////////////////////////////
+
+// Init:                                 1
+// Upgrade:                              1
+// Endpoints:                           29
+// Async Callback:                       1
+// Total number of exported functions:  32
+
+#![no_std]
+
+multiversx_sc_wasm_adapter::allocator!();
+multiversx_sc_wasm_adapter::panic_handler!();
+
+multiversx_sc_wasm_adapter::endpoints! {
+    farm_vault
+    (
+        init => init
+        upgrade => upgrade
+        setHarvestBountyPercent => set_harvest_bounty_percent
+        setMinCompoundAmount => set_min_compound_amount
+        deposit => deposit
+        withdraw => withdraw
+        harvest => harvest
+        recoverTokens => recover_tokens
+        getLpTokenId => lp_token_id
+        getRewardTokenId => reward_token_id
+        getOtherTokenId => other_token_id
+        getOtherTokenLeftover => other_token_leftover
+        getHarvestBountyPercent => harvest_bounty_percent
+        getMinCompoundAmount => min_compound_amount
+        getTotalLpTokens => get_total_lp_tokens
+        getAdditionalRewards => get_additional_rewards
+        getFarmPositions => get_farm_positions
+        getFarmAddress => farm_address
+        getPendingRewards => pending_rewards
+        setMaxSlippagePercent => set_max_slippage_percent
+        getPairAddress => pair_address
+        getMaxSlippagePercent => max_slippage_percent
+        registerShareToken => register_share_token
+        getSharePrice => get_share_price
+        getLpTokensForShares => get_lp_tokens_for_shares
+        getSharesForLpTokens => get_shares_for_lp_tokens
+        getShareTokenId => share_token
+        getTotalShares => total_shares
+        pause => pause_endpoint
+        unpause => unpause_endpoint
+        isPaused => paused_status
+    )
+}
+
+multiversx_sc_wasm_adapter::async_callback! { farm_vault }
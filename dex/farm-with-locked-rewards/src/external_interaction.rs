@@ -2,6 +2,7 @@ multiversx_sc::imports!();
 
 use common_structs::FarmTokenAttributes;
 use farm::{
+    additional_rewards,
     base_functions::{self, ClaimRewardsResultType},
     exit_penalty, EnterFarmResultType,
 };
@@ -22,7 +23,9 @@ pub trait ExternalInteractionsModule:
     + events::EventsModule
     + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
     + base_functions::BaseFunctionsModule
+    + additional_rewards::AdditionalRewardsModule
     + exit_penalty::ExitPenaltyModule
+    + farm::position_lock::PositionLockModule
     + locking_module::lock_with_energy_module::LockWithEnergyModule
     + farm_base_impl::base_farm_init::BaseFarmInitModule
     + farm_base_impl::base_farm_validation::BaseFarmValidationModule
@@ -59,7 +62,9 @@ pub trait ExternalInteractionsModule:
         self.migrate_old_farm_positions(&user);
 
         let boosted_rewards = self.claim_only_boosted_payment(&user);
+        let carried_rewards = self.carry_additional_rewards(&payments);
         let new_farm_token = self.enter_farm::<NoMintWrapper<Self>>(user.clone());
+        self.set_additional_reward_checkpoints(&new_farm_token, &carried_rewards);
         self.send_payment_non_zero(&caller, &new_farm_token);
 
         let locked_rewards_payment = self.send_to_lock_contract_non_zero(
@@ -85,9 +90,16 @@ pub trait ExternalInteractionsModule:
 
         self.migrate_old_farm_positions(&user);
 
+        let additional_rewards =
+            self.claim_additional_rewards(&self.call_value().all_esdt_transfers());
         let claim_rewards_result = self.claim_rewards::<NoMintWrapper<Self>>(user.clone());
+        self.set_additional_reward_checkpoints(
+            &claim_rewards_result.new_farm_token,
+            &ManagedVec::new(),
+        );
 
         self.send_payment_non_zero(&caller, &claim_rewards_result.new_farm_token);
+        self.send_additional_rewards(&user, &additional_rewards);
 
         let rewards_payment = claim_rewards_result.rewards;
         let locked_rewards_payment = self.send_to_lock_contract_non_zero(
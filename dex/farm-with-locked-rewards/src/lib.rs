@@ -5,19 +5,20 @@ multiversx_sc::derive_imports!();
 
 pub mod external_interaction;
 
-use common_structs::FarmTokenAttributes;
+use common_structs::{Epoch, FarmTokenAttributes};
 use contexts::storage_cache::StorageCache;
 use core::marker::PhantomData;
 use fixed_supply_token::FixedSupplyToken;
 
 use farm::{
-    base_functions::{BaseFunctionsModule, ClaimRewardsResultType, DoubleMultiPayment, Wrapper},
+    base_functions::{BaseFunctionsModule, DoubleMultiPayment, Wrapper},
     exit_penalty::{
         DEFAULT_BURN_GAS_LIMIT, DEFAULT_MINUMUM_FARMING_EPOCHS, DEFAULT_PENALTY_PERCENT,
     },
-    EnterFarmResultType, ExitFarmWithPartialPosResultType, MAX_PERCENT,
+    AllRewardsResultType, EnterFarmResultType, MAX_PERCENT,
 };
 use farm_base_impl::base_traits_impl::FarmContract;
+use rewards::emission_schedule::EmissionSegment;
 
 #[multiversx_sc::contract]
 pub trait Farm:
@@ -35,7 +36,9 @@ pub trait Farm:
     + events::EventsModule
     + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
     + farm::base_functions::BaseFunctionsModule
+    + farm::additional_rewards::AdditionalRewardsModule
     + farm::exit_penalty::ExitPenaltyModule
+    + farm::position_lock::PositionLockModule
     + external_interaction::ExternalInteractionsModule
     + farm_base_impl::base_farm_init::BaseFarmInitModule
     + farm_base_impl::base_farm_validation::BaseFarmValidationModule
@@ -101,6 +104,28 @@ pub trait Farm:
     fn enter_farm_endpoint(
         &self,
         opt_orig_caller: OptionalValue<ManagedAddress>,
+    ) -> EnterFarmResultType<Self::Api> {
+        self.enter_farm_and_send(0, opt_orig_caller)
+    }
+
+    /// Locks the new position for `lock_epochs`, which has to be one of the lock options.
+    /// Until the unlock epoch, its base farm rewards are multiplied by the option multiplier.
+    #[payable("*")]
+    #[endpoint(enterFarmLocked)]
+    fn enter_farm_locked_endpoint(
+        &self,
+        lock_epochs: Epoch,
+        opt_orig_caller: OptionalValue<ManagedAddress>,
+    ) -> EnterFarmResultType<Self::Api> {
+        require!(lock_epochs > 0, "Invalid lock period");
+
+        self.enter_farm_and_send(lock_epochs, opt_orig_caller)
+    }
+
+    fn enter_farm_and_send(
+        &self,
+        lock_epochs: Epoch,
+        opt_orig_caller: OptionalValue<ManagedAddress>,
     ) -> EnterFarmResultType<Self::Api> {
         let caller = self.blockchain().get_caller();
         let orig_caller = self.get_orig_caller_from_opt(&caller, opt_orig_caller);
@@ -113,8 +138,12 @@ pub trait Farm:
             caller.clone(),
             orig_caller.clone(),
         );
+        let carried_rewards =
+            self.carry_additional_rewards(&self.call_value().all_esdt_transfers());
 
-        let new_farm_token = self.enter_farm::<NoMintWrapper<Self>>(orig_caller.clone());
+        let new_farm_token =
+            self.enter_farm_with_lock::<NoMintWrapper<Self>>(orig_caller.clone(), lock_epochs);
+        self.set_additional_reward_checkpoints(&new_farm_token, &carried_rewards);
         self.send_payment_non_zero(&caller, &new_farm_token);
 
         self.update_energy_and_progress(&orig_caller);
@@ -127,15 +156,22 @@ pub trait Farm:
     fn claim_rewards_endpoint(
         &self,
         opt_orig_caller: OptionalValue<ManagedAddress>,
-    ) -> ClaimRewardsResultType<Self::Api> {
+    ) -> AllRewardsResultType<Self::Api> {
         let caller = self.blockchain().get_caller();
         let orig_caller = self.get_orig_caller_from_opt(&caller, opt_orig_caller);
 
         self.migrate_old_farm_positions(&orig_caller);
 
+        let additional_rewards =
+            self.claim_additional_rewards(&self.call_value().all_esdt_transfers());
         let claim_rewards_result = self.claim_rewards::<NoMintWrapper<Self>>(orig_caller.clone());
+        self.set_additional_reward_checkpoints(
+            &claim_rewards_result.new_farm_token,
+            &ManagedVec::new(),
+        );
 
         self.send_payment_non_zero(&caller, &claim_rewards_result.new_farm_token);
+        self.send_additional_rewards(&caller, &additional_rewards);
 
         let rewards_payment = claim_rewards_result.rewards;
         let locked_rewards_payment = self.send_to_lock_contract_non_zero(
@@ -145,7 +181,12 @@ pub trait Farm:
             orig_caller,
         );
 
-        (claim_rewards_result.new_farm_token, locked_rewards_payment).into()
+        (
+            claim_rewards_result.new_farm_token,
+            locked_rewards_payment,
+            additional_rewards.into(),
+        )
+            .into()
     }
 
     #[payable("*")]
@@ -153,7 +194,7 @@ pub trait Farm:
     fn exit_farm_endpoint(
         &self,
         opt_orig_caller: OptionalValue<ManagedAddress>,
-    ) -> ExitFarmWithPartialPosResultType<Self::Api> {
+    ) -> AllRewardsResultType<Self::Api> {
         let caller = self.blockchain().get_caller();
         let orig_caller = self.get_orig_caller_from_opt(&caller, opt_orig_caller);
 
@@ -161,12 +202,15 @@ pub trait Farm:
 
         let migrated_amount = self.migrate_old_farm_positions(&orig_caller);
 
+        let additional_rewards =
+            self.claim_additional_rewards(&ManagedVec::from_single_item(payment.clone()));
         let exit_farm_result = self.exit_farm::<NoMintWrapper<Self>>(orig_caller.clone(), payment);
 
         self.decrease_old_farm_positions(migrated_amount, &orig_caller);
 
         let rewards = exit_farm_result.rewards;
         self.send_payment_non_zero(&caller, &exit_farm_result.farming_tokens);
+        self.send_additional_rewards(&caller, &additional_rewards);
 
         let locked_rewards_payment = self.send_to_lock_contract_non_zero(
             rewards.token_identifier.clone(),
@@ -177,7 +221,12 @@ pub trait Farm:
 
         self.clear_user_energy_if_needed(&orig_caller);
 
-        (exit_farm_result.farming_tokens, locked_rewards_payment).into()
+        (
+            exit_farm_result.farming_tokens,
+            locked_rewards_payment,
+            additional_rewards.into(),
+        )
+            .into()
     }
 
     #[payable("*")]
@@ -191,8 +240,11 @@ pub trait Farm:
 
         self.migrate_old_farm_positions(&orig_caller);
         let boosted_rewards = self.claim_only_boosted_payment(&orig_caller);
+        let carried_rewards =
+            self.carry_additional_rewards(&self.call_value().all_esdt_transfers());
 
         let merged_farm_token = self.merge_and_update_farm_tokens(orig_caller.clone());
+        self.set_additional_reward_checkpoints(&merged_farm_token, &carried_rewards);
 
         self.send_payment_non_zero(&caller, &merged_farm_token);
         let locked_rewards_payment = self.send_to_lock_contract_non_zero(
@@ -206,13 +258,22 @@ pub trait Farm:
     }
 
     fn merge_and_update_farm_tokens(&self, orig_caller: ManagedAddress) -> EsdtTokenPayment {
-        let mut output_attributes =
+        let (mut output_attributes, merged_position_lock) =
             self.merge_and_return_attributes::<NoMintWrapper<Self>>(&orig_caller);
         output_attributes.original_owner = orig_caller;
 
         let new_token_amount = output_attributes.get_total_supply();
-        self.farm_token()
-            .nft_create(new_token_amount, &output_attributes)
+        let new_farm_token = self
+            .farm_token()
+            .nft_create(new_token_amount, &output_attributes);
+        self.set_position_lock(
+            &new_farm_token,
+            merged_position_lock.position_lock,
+            merged_position_lock.carried_rewards,
+            &merged_position_lock.reward_per_share,
+        );
+
+        new_farm_token
     }
 
     #[endpoint(claimBoostedRewards)]
@@ -271,6 +332,31 @@ pub trait Farm:
         self.set_per_block_rewards::<NoMintWrapper<Self>>(per_block_amount);
     }
 
+    /// Switches the emission from per block to per second, converting the current rate
+    /// and the additional reward schedules with the given block time.
+    #[endpoint(migrateToPerSecondRewards)]
+    fn migrate_to_per_second_rewards_endpoint(&self, seconds_per_block: u64) {
+        self.require_caller_has_admin_permissions();
+        self.migrate_additional_rewards_to_per_second(seconds_per_block);
+        self.migrate_to_per_second_rewards_base::<NoMintWrapper<Self>>(seconds_per_block);
+    }
+
+    #[endpoint(setPerSecondRewardAmount)]
+    fn set_per_second_rewards_endpoint(&self, per_second_amount: BigUint) {
+        self.require_caller_has_admin_permissions();
+        self.set_per_second_rewards_base::<NoMintWrapper<Self>>(per_second_amount);
+    }
+
+    /// Replaces the emission schedule of the farm. An empty list removes it.
+    #[endpoint(setEmissionSchedule)]
+    fn set_emission_schedule_endpoint(
+        &self,
+        segments: MultiValueEncoded<EmissionSegment<Self::Api>>,
+    ) {
+        self.require_caller_has_admin_permissions();
+        self.set_emission_schedule_base::<NoMintWrapper<Self>>(segments);
+    }
+
     #[endpoint(setBoostedYieldsRewardsPercentage)]
     fn set_boosted_yields_rewards_percentage(&self, percentage: u64) {
         self.require_caller_has_admin_permissions();
@@ -332,11 +418,14 @@ where
             let split_rewards = sc.take_reward_slice(total_reward);
 
             if storage_cache.farm_token_supply != 0u64 {
+                let weighted_supply = sc.get_weighted_farm_supply(&storage_cache.farm_token_supply);
                 let increase = (&split_rewards.base_farm * &storage_cache.division_safety_constant)
-                    / &storage_cache.farm_token_supply;
+                    / weighted_supply;
                 storage_cache.reward_per_share += &increase;
             }
         }
+
+        sc.update_lock_boosts(&storage_cache.reward_per_share);
     }
 
     fn calculate_rewards(
@@ -273,11 +273,14 @@ where
                 &self.energy_factory_wrapper,
                 &rust_biguint!(0),
                 |sc| {
-                    sc.user_energy(&managed_address!(user)).set(&Energy::new(
-                        BigInt::from(managed_biguint!(energy)),
-                        last_update_epoch,
-                        managed_biguint!(locked_tokens),
-                    ));
+                    sc.set_energy_entry(
+                        &managed_address!(user),
+                        Energy::new(
+                            BigInt::from(managed_biguint!(energy)),
+                            last_update_epoch,
+                            managed_biguint!(locked_tokens),
+                        ),
+                    );
                 },
             )
             .assert_ok();
@@ -411,7 +414,7 @@ where
                 farm_token_nonce,
                 &rust_biguint!(farm_token_amount),
                 |sc| {
-                    let (out_farm_token, out_reward_token) = sc
+                    let (out_farm_token, out_reward_token, _) = sc
                         .claim_rewards_endpoint(OptionalValue::Some(managed_address!(user)))
                         .into_tuple();
                     assert_eq!(
@@ -0,0 +1,406 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use common_structs::{Nonce, PaymentsVec};
+
+pub const MAX_ADDITIONAL_REWARD_TOKENS: usize = 5;
+
+pub type AdditionalRewardsPayments<M> = ManagedVec<M, EsdtTokenPayment<M>>;
+
+/// Rewards distributed besides the main farm reward, e.g. by partner projects.
+/// Each token has its own schedule and reward per share, which are updated separately
+/// from the ones of the main reward. Same as for the main reward, the schedule is in
+/// block nonces, or in timestamps once the farm emits rewards per second.
+#[derive(
+    ManagedVecItem,
+    TypeAbi,
+    TopEncode,
+    TopDecode,
+    NestedEncode,
+    NestedDecode,
+    Clone,
+    PartialEq,
+    Debug,
+)]
+pub struct AdditionalRewardInfo<M: ManagedTypeApi> {
+    pub token_id: TokenIdentifier<M>,
+    pub reward_rate: BigUint<M>,
+    pub start: u64,
+    pub end: u64,
+    pub last_update: u64,
+    pub reward_per_share: BigUint<M>,
+    pub reserve: BigUint<M>,
+}
+
+impl<M: ManagedTypeApi> AdditionalRewardInfo<M> {
+    pub fn new(token_id: TokenIdentifier<M>) -> Self {
+        AdditionalRewardInfo {
+            token_id,
+            reward_rate: BigUint::zero(),
+            start: 0,
+            end: 0,
+            last_update: 0,
+            reward_per_share: BigUint::zero(),
+            reserve: BigUint::zero(),
+        }
+    }
+
+    pub fn update_reward_per_share(
+        &mut self,
+        current_point: u64,
+        farm_token_supply: &BigUint<M>,
+        division_safety_constant: &BigUint<M>,
+    ) {
+        let from = core::cmp::max(self.last_update, self.start);
+        let to = core::cmp::min(current_point, self.end);
+        if to > from && farm_token_supply > &0u64 {
+            let rewards = &self.reward_rate * (to - from);
+            self.reward_per_share += rewards * division_safety_constant / farm_token_supply;
+        }
+
+        if current_point > self.last_update {
+            self.last_update = current_point;
+        }
+    }
+}
+
+/// The farm tokens keep a reward per share checkpoint for each additional reward token,
+/// stored by token nonce, so that the attributes of the existing positions stay unchanged.
+/// Missing checkpoints are zero, which is the reward per share of a newly added token.
+/// The additional rewards are computed on the farm token amount only, the lock multiplier
+/// of locked positions applies to the base farm rewards.
+#[multiversx_sc::module]
+pub trait AdditionalRewardsModule:
+    rewards::RewardsModule
+    + config::ConfigModule
+    + farm_token::FarmTokenModule
+    + pausable::PausableModule
+    + permissions_module::PermissionsModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+{
+    #[endpoint(addAdditionalRewardToken)]
+    fn add_additional_reward_token(&self, token_id: TokenIdentifier) {
+        self.require_caller_has_owner_or_admin_permissions();
+        require!(token_id.is_valid_esdt_identifier(), "Invalid token ID");
+        require!(
+            token_id != self.reward_token_id().get() && token_id != self.farming_token_id().get(),
+            "Invalid additional reward token"
+        );
+
+        let mut tokens_mapper = self.additional_reward_tokens();
+        require!(
+            tokens_mapper.len() < MAX_ADDITIONAL_REWARD_TOKENS,
+            "Too many additional reward tokens"
+        );
+
+        let is_new = tokens_mapper.insert(token_id.clone());
+        require!(is_new, "Additional reward token already added");
+
+        self.additional_reward_info(&token_id)
+            .set(AdditionalRewardInfo::new(token_id));
+    }
+
+    /// Schedules the payment to be distributed evenly between `start` and `end`, which are
+    /// block nonces, or timestamps once the farm emits rewards per second.
+    /// The previous schedule of the token has to be over.
+    #[payable("*")]
+    #[endpoint(depositAdditionalRewards)]
+    fn deposit_additional_rewards(&self, start: u64, end: u64) {
+        self.require_caller_has_owner_or_admin_permissions();
+
+        let payment = self.call_value().single_esdt();
+        require!(
+            self.additional_reward_tokens()
+                .contains(&payment.token_identifier),
+            "Unknown additional reward token"
+        );
+
+        let current_point = self.get_current_emission_point();
+        require!(
+            start >= current_point && end > start,
+            "Invalid reward schedule"
+        );
+
+        self.update_additional_rewards();
+
+        let info_mapper = self.additional_reward_info(&payment.token_identifier);
+        let mut info = info_mapper.get();
+        require!(
+            info.end <= current_point,
+            "Previous reward schedule not over"
+        );
+
+        let reward_rate = &payment.amount / (end - start);
+        require!(reward_rate > 0u64, "Reward amount too low");
+
+        info.reward_rate = reward_rate;
+        info.start = start;
+        info.end = end;
+        info.reserve += &payment.amount;
+        info_mapper.set(info);
+    }
+
+    #[view(getAdditionalRewardsInfo)]
+    fn get_additional_rewards_info(&self) -> MultiValueEncoded<AdditionalRewardInfo<Self::Api>> {
+        let mut result = MultiValueEncoded::new();
+        for token_id in self.additional_reward_tokens().iter() {
+            result.push(self.get_updated_additional_reward_info(&token_id));
+        }
+
+        result
+    }
+
+    #[view(calculateAdditionalRewardsForGivenPosition)]
+    fn calculate_additional_rewards_for_given_position(
+        &self,
+        farm_token_nonce: Nonce,
+        farm_token_amount: BigUint,
+    ) -> MultiValueEncoded<EsdtTokenPayment> {
+        let division_safety_constant = self.division_safety_constant().get();
+        let mut result = MultiValueEncoded::new();
+        for token_id in self.additional_reward_tokens().iter() {
+            let info = self.get_updated_additional_reward_info(&token_id);
+            let amount = self.calculate_additional_reward(
+                &info,
+                farm_token_nonce,
+                &farm_token_amount,
+                &division_safety_constant,
+            );
+            result.push(EsdtTokenPayment::new(token_id, 0, amount));
+        }
+
+        result
+    }
+
+    /// Converts the schedules from block nonces to timestamps, given the current block time,
+    /// so that the remaining rewards are distributed over the same period. Has to be called
+    /// before the farm switches to per second emission. The rounding leftover of the
+    /// rate stays in the reserve.
+    fn migrate_additional_rewards_to_per_second(&self, seconds_per_block: u64) {
+        require!(
+            !self.is_per_second_emission(),
+            "Already using per second rewards"
+        );
+        require!(seconds_per_block > 0, "Invalid block time");
+
+        self.update_additional_rewards();
+
+        let current_block = self.blockchain().get_block_nonce();
+        let current_timestamp = self.blockchain().get_block_timestamp();
+        let to_timestamp = |block: Nonce| {
+            current_timestamp + block.saturating_sub(current_block) * seconds_per_block
+        };
+        for token_id in self.additional_reward_tokens().iter() {
+            self.additional_reward_info(&token_id).update(|info| {
+                info.reward_rate /= seconds_per_block;
+                info.start = to_timestamp(info.start);
+                info.end = to_timestamp(info.end);
+                info.last_update = current_timestamp;
+            });
+        }
+    }
+
+    /// Has to be called before the farm token supply changes.
+    fn update_additional_rewards(&self) {
+        for token_id in self.additional_reward_tokens().iter() {
+            let info = self.get_updated_additional_reward_info(&token_id);
+            self.additional_reward_info(&token_id).set(info);
+        }
+    }
+
+    fn get_updated_additional_reward_info(
+        &self,
+        token_id: &TokenIdentifier,
+    ) -> AdditionalRewardInfo<Self::Api> {
+        let mut info = self.additional_reward_info(token_id).get();
+        info.update_reward_per_share(
+            self.get_current_emission_point(),
+            &self.farm_token_supply().get(),
+            &self.division_safety_constant().get(),
+        );
+
+        info
+    }
+
+    fn calculate_additional_reward(
+        &self,
+        info: &AdditionalRewardInfo<Self::Api>,
+        farm_token_nonce: Nonce,
+        farm_token_amount: &BigUint,
+        division_safety_constant: &BigUint,
+    ) -> BigUint {
+        let checkpoint = self
+            .additional_reward_checkpoint(farm_token_nonce, &info.token_id)
+            .get();
+        if info.reward_per_share > checkpoint {
+            farm_token_amount * &(&info.reward_per_share - &checkpoint) / division_safety_constant
+        } else {
+            BigUint::zero()
+        }
+    }
+
+    /// Computes the additional rewards of the farm tokens in `payments` and removes them
+    /// from the reserves. Has to be called before the farm token supply changes.
+    fn claim_additional_rewards(
+        &self,
+        payments: &PaymentsVec<Self::Api>,
+    ) -> AdditionalRewardsPayments<Self::Api> {
+        let rewards = self.compute_additional_rewards(payments);
+        for reward in &rewards {
+            self.additional_reward_info(&reward.token_identifier)
+                .update(|info| info.reserve -= &reward.amount);
+        }
+        self.release_additional_reward_checkpoints(payments);
+
+        rewards
+    }
+
+    /// Computes the additional rewards of the farm tokens in `payments`, which are then
+    /// carried over to the new farm token through its checkpoints, the same way the
+    /// reward per share of merged positions is averaged. Used when the position is merged
+    /// or compounded, as the additional rewards can't be added to it.
+    /// Has to be called before the farm token supply changes.
+    fn carry_additional_rewards(
+        &self,
+        payments: &PaymentsVec<Self::Api>,
+    ) -> AdditionalRewardsPayments<Self::Api> {
+        let rewards = self.compute_additional_rewards(payments);
+        self.release_additional_reward_checkpoints(payments);
+
+        rewards
+    }
+
+    fn compute_additional_rewards(
+        &self,
+        payments: &PaymentsVec<Self::Api>,
+    ) -> AdditionalRewardsPayments<Self::Api> {
+        self.update_additional_rewards();
+
+        let farm_token_id = self.farm_token().get_token_id();
+        let division_safety_constant = self.division_safety_constant().get();
+        let mut rewards = ManagedVec::new();
+        for token_id in self.additional_reward_tokens().iter() {
+            let info = self.additional_reward_info(&token_id).get();
+
+            let mut total_reward = BigUint::zero();
+            for payment in payments {
+                if payment.token_identifier != farm_token_id {
+                    continue;
+                }
+
+                total_reward += self.calculate_additional_reward(
+                    &info,
+                    payment.token_nonce,
+                    &payment.amount,
+                    &division_safety_constant,
+                );
+            }
+
+            if total_reward > 0u64 {
+                rewards.push(EsdtTokenPayment::new(token_id, 0, total_reward));
+            }
+        }
+
+        rewards
+    }
+
+    /// The checkpoints are set so that the new farm token is owed exactly the `carried_rewards`
+    /// at the current reward per share, rounded down.
+    fn set_additional_reward_checkpoints(
+        &self,
+        new_farm_token: &EsdtTokenPayment,
+        carried_rewards: &AdditionalRewardsPayments<Self::Api>,
+    ) {
+        if new_farm_token.amount == 0u64 {
+            return;
+        }
+
+        let division_safety_constant = self.division_safety_constant().get();
+        let mut has_checkpoints = false;
+        for token_id in self.additional_reward_tokens().iter() {
+            let reward_per_share = self
+                .additional_reward_info(&token_id)
+                .get()
+                .reward_per_share;
+            let carried_per_share = carried_rewards
+                .iter()
+                .find(|reward| reward.token_identifier == token_id)
+                .map(|reward| &reward.amount * &division_safety_constant / &new_farm_token.amount)
+                .unwrap_or_default();
+            if reward_per_share <= carried_per_share {
+                continue;
+            }
+
+            self.additional_reward_checkpoint(new_farm_token.token_nonce, &token_id)
+                .set(reward_per_share - carried_per_share);
+            has_checkpoints = true;
+        }
+
+        if has_checkpoints {
+            self.additional_reward_checkpoint_supply(new_farm_token.token_nonce)
+                .set(&new_farm_token.amount);
+        }
+    }
+
+    /// The farm tokens in `payments` are burned, so the checkpoints of a nonce
+    /// are cleared once its whole supply is burned.
+    fn release_additional_reward_checkpoints(&self, payments: &PaymentsVec<Self::Api>) {
+        let farm_token_id = self.farm_token().get_token_id();
+        for payment in payments {
+            if payment.token_identifier != farm_token_id {
+                continue;
+            }
+
+            let supply_mapper = self.additional_reward_checkpoint_supply(payment.token_nonce);
+            if supply_mapper.is_empty() {
+                continue;
+            }
+
+            let supply = supply_mapper.get();
+            if supply > payment.amount {
+                supply_mapper.set(supply - &payment.amount);
+                continue;
+            }
+
+            supply_mapper.clear();
+            for token_id in self.additional_reward_tokens().iter() {
+                self.additional_reward_checkpoint(payment.token_nonce, &token_id)
+                    .clear();
+            }
+        }
+    }
+
+    fn send_additional_rewards(
+        &self,
+        to: &ManagedAddress,
+        rewards: &AdditionalRewardsPayments<Self::Api>,
+    ) {
+        if !rewards.is_empty() {
+            self.send().direct_multi(to, rewards);
+        }
+    }
+
+    #[view(getAdditionalRewardTokens)]
+    #[storage_mapper("additionalRewardTokens")]
+    fn additional_reward_tokens(&self) -> UnorderedSetMapper<TokenIdentifier>;
+
+    #[storage_mapper("additionalRewardInfo")]
+    fn additional_reward_info(
+        &self,
+        token_id: &TokenIdentifier,
+    ) -> SingleValueMapper<AdditionalRewardInfo<Self::Api>>;
+
+    #[storage_mapper("additionalRewardCheckpoint")]
+    fn additional_reward_checkpoint(
+        &self,
+        farm_token_nonce: Nonce,
+        token_id: &TokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
+
+    /// The amount of the farm token nonce that has checkpoints and wasn't burned yet.
+    #[storage_mapper("additionalRewardCheckpointSupply")]
+    fn additional_reward_checkpoint_supply(
+        &self,
+        farm_token_nonce: Nonce,
+    ) -> SingleValueMapper<BigUint>;
+}
@@ -7,12 +7,13 @@ multiversx_sc::derive_imports!();
 use core::marker::PhantomData;
 
 use common_errors::ERROR_ZERO_AMOUNT;
-use common_structs::FarmTokenAttributes;
+use common_structs::{Epoch, FarmTokenAttributes};
 use contexts::storage_cache::StorageCache;
 
 use farm_base_impl::base_traits_impl::{DefaultFarmWrapper, FarmContract};
+use rewards::emission_schedule::EmissionSegment;
 
-use crate::{exit_penalty, MAX_PERCENT};
+use crate::{exit_penalty, position_lock, MAX_PERCENT};
 
 pub type DoubleMultiPayment<M> = MultiValue2<EsdtTokenPayment<M>, EsdtTokenPayment<M>>;
 pub type ClaimRewardsResultType<M> = DoubleMultiPayment<M>;
@@ -20,6 +21,12 @@ pub type ExitFarmResultType<M> = DoubleMultiPayment<M>;
 
 pub const DEFAULT_FARM_POSITION_MIGRATION_NONCE: u64 = 1;
 
+pub struct MergedPositionLock<M: ManagedTypeApi> {
+    pub position_lock: Option<position_lock::LockTerms>,
+    pub carried_rewards: BigUint<M>,
+    pub reward_per_share: BigUint<M>,
+}
+
 pub struct ClaimRewardsResultWrapper<M: ManagedTypeApi> {
     pub new_farm_token: EsdtTokenPayment<M>,
     pub rewards: EsdtTokenPayment<M>,
@@ -53,6 +60,7 @@ pub trait BaseFunctionsModule:
     + events::EventsModule
     + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
     + exit_penalty::ExitPenaltyModule
+    + position_lock::PositionLockModule
     + farm_base_impl::base_farm_init::BaseFarmInitModule
     + farm_base_impl::base_farm_validation::BaseFarmValidationModule
     + farm_base_impl::enter_farm::BaseEnterFarmModule
@@ -74,9 +82,36 @@ pub trait BaseFunctionsModule:
     fn enter_farm<FC: FarmContract<FarmSc = Self>>(
         &self,
         caller: ManagedAddress,
+    ) -> EsdtTokenPayment {
+        self.enter_farm_with_lock::<FC>(caller, 0)
+    }
+
+    /// Zero `lock_epochs` creates a position without lock. The additional farm tokens are
+    /// merged with the new position, along with their locks.
+    fn enter_farm_with_lock<FC: FarmContract<FarmSc = Self>>(
+        &self,
+        caller: ManagedAddress,
+        lock_epochs: Epoch,
     ) -> EsdtTokenPayment {
         let payments = self.call_value().all_esdt_transfers().clone_value();
-        let base_enter_farm_result = self.enter_farm_base::<FC>(caller.clone(), payments);
+        let new_position_lock = self.get_new_position_lock(lock_epochs);
+        let position_lock = self.get_merged_position_lock(&payments, new_position_lock);
+
+        let base_enter_farm_result = self.enter_farm_base::<FC>(caller.clone(), payments.clone());
+
+        let carried_lock_boost_rewards = self.take_lock_boost_rewards(
+            &payments,
+            &base_enter_farm_result.storage_cache.reward_per_share,
+            &base_enter_farm_result
+                .storage_cache
+                .division_safety_constant,
+        );
+        self.set_position_lock(
+            &base_enter_farm_result.new_farm_token.payment,
+            position_lock,
+            carried_lock_boost_rewards,
+            &base_enter_farm_result.storage_cache.reward_per_share,
+        );
 
         self.set_farm_supply_for_current_week(
             &base_enter_farm_result.storage_cache.farm_token_supply,
@@ -98,10 +133,27 @@ pub trait BaseFunctionsModule:
         caller: ManagedAddress,
     ) -> ClaimRewardsResultWrapper<Self::Api> {
         let payments = self.call_value().all_esdt_transfers().clone_value();
-        let base_claim_rewards_result = self.claim_rewards_base::<FC>(caller.clone(), payments);
+        let position_lock = self.get_merged_position_lock(&payments, None);
+        let mut base_claim_rewards_result =
+            self.claim_rewards_base::<FC>(caller.clone(), payments.clone());
 
         let output_farm_token_payment = base_claim_rewards_result.new_farm_token.payment.clone();
-        let rewards_payment = base_claim_rewards_result.rewards;
+        let mut rewards_payment = base_claim_rewards_result.rewards;
+
+        let storage_cache = &mut base_claim_rewards_result.storage_cache;
+        let lock_boost_rewards = self.take_lock_boost_rewards(
+            &payments,
+            &storage_cache.reward_per_share,
+            &storage_cache.division_safety_constant,
+        );
+        storage_cache.reward_reserve -= &lock_boost_rewards;
+        rewards_payment.amount += lock_boost_rewards;
+        self.set_position_lock(
+            &output_farm_token_payment,
+            position_lock,
+            BigUint::zero(),
+            &storage_cache.reward_per_share,
+        );
 
         self.set_farm_supply_for_current_week(
             &base_claim_rewards_result.storage_cache.farm_token_supply,
@@ -122,16 +174,32 @@ pub trait BaseFunctionsModule:
         }
     }
 
+    /// The lock boost rewards are not compounded, but carried over to the new position.
     fn compound_rewards<FC: FarmContract<FarmSc = Self>>(
         &self,
         caller: ManagedAddress,
     ) -> EsdtTokenPayment<Self::Api> {
         let payments = self.call_value().all_esdt_transfers().clone_value();
+        let position_lock = self.get_merged_position_lock(&payments, None);
         let base_compound_rewards_result =
-            self.compound_rewards_base::<FC>(caller.clone(), payments);
+            self.compound_rewards_base::<FC>(caller.clone(), payments.clone());
 
         let output_farm_token_payment = base_compound_rewards_result.new_farm_token.payment.clone();
 
+        let carried_lock_boost_rewards = self.take_lock_boost_rewards(
+            &payments,
+            &base_compound_rewards_result.storage_cache.reward_per_share,
+            &base_compound_rewards_result
+                .storage_cache
+                .division_safety_constant,
+        );
+        self.set_position_lock(
+            &output_farm_token_payment,
+            position_lock,
+            carried_lock_boost_rewards,
+            &base_compound_rewards_result.storage_cache.reward_per_share,
+        );
+
         self.set_farm_supply_for_current_week(
             &base_compound_rewards_result.storage_cache.farm_token_supply,
         );
@@ -153,21 +221,45 @@ pub trait BaseFunctionsModule:
         caller: ManagedAddress,
         payment: EsdtTokenPayment,
     ) -> ExitFarmResultWrapper<Self::Api> {
-        let base_exit_farm_result = self.exit_farm_base::<FC>(caller.clone(), payment);
+        let is_locked = self.get_active_position_lock(payment.token_nonce).is_some();
+        require!(
+            !is_locked || !self.early_unlock_penalty_percent().is_empty(),
+            "Farm position is locked"
+        );
+
+        let payments = ManagedVec::from_single_item(payment.clone());
+        let mut base_exit_farm_result = self.exit_farm_base::<FC>(caller.clone(), payment);
 
         let mut farming_token_payment = base_exit_farm_result.farming_token_payment;
-        let reward_payment = base_exit_farm_result.reward_payment;
+        let mut reward_payment = base_exit_farm_result.reward_payment;
+
+        let storage_cache = &mut base_exit_farm_result.storage_cache;
+        let lock_boost_rewards = self.take_lock_boost_rewards(
+            &payments,
+            &storage_cache.reward_per_share,
+            &storage_cache.division_safety_constant,
+        );
+        storage_cache.reward_reserve -= &lock_boost_rewards;
+        reward_payment.amount += lock_boost_rewards;
 
         self.set_farm_supply_for_current_week(
             &base_exit_farm_result.storage_cache.farm_token_supply,
         );
 
+        let farming_amount = farming_token_payment.amount.clone();
         FC::apply_penalty(
             self,
             &mut farming_token_payment.amount,
             &base_exit_farm_result.context.farm_token.attributes,
             &base_exit_farm_result.storage_cache,
         );
+        if is_locked {
+            self.apply_early_unlock_penalty(
+                &farming_amount,
+                &mut farming_token_payment.amount,
+                &base_exit_farm_result.storage_cache,
+            );
+        }
 
         self.emit_exit_farm_event(
             &caller,
@@ -183,17 +275,61 @@ pub trait BaseFunctionsModule:
         }
     }
 
+    /// The early unlock penalty replaces the regular exit penalty, if larger.
+    fn apply_early_unlock_penalty(
+        &self,
+        total_exit_amount: &BigUint,
+        remaining_amount: &mut BigUint,
+        storage_cache: &StorageCache<Self>,
+    ) {
+        let penalty_amount =
+            total_exit_amount * self.early_unlock_penalty_percent().get() / MAX_PERCENT;
+        let applied_penalty_amount = total_exit_amount - &*remaining_amount;
+        if penalty_amount <= applied_penalty_amount {
+            return;
+        }
+
+        let extra_penalty_amount = penalty_amount - applied_penalty_amount;
+        *remaining_amount -= &extra_penalty_amount;
+
+        self.burn_farming_tokens(
+            &extra_penalty_amount,
+            &storage_cache.farming_token_id,
+            &storage_cache.reward_token_id,
+        );
+    }
+
+    /// Also returns the lock of the merged position, along with the lock boost rewards
+    /// carried over to it, which have to be set once the new farm token is created.
     fn merge_and_return_attributes<FC: FarmContract<FarmSc = Self>>(
         &self,
         orig_caller: &ManagedAddress,
-    ) -> FC::AttributesType {
+    ) -> (FC::AttributesType, MergedPositionLock<Self::Api>) {
         let payments = self.get_non_empty_payments();
         let token_mapper = self.farm_token();
         token_mapper.require_all_same_token(&payments);
 
         FC::check_and_update_user_farm_position(self, orig_caller, &payments);
 
-        self.merge_from_payments_and_burn(payments, &token_mapper)
+        let position_lock = self.get_merged_position_lock(&payments, None);
+        let mut storage_cache = StorageCache::new(self);
+        FC::generate_aggregated_rewards(self, &mut storage_cache);
+        let carried_rewards = self.take_lock_boost_rewards(
+            &payments,
+            &storage_cache.reward_per_share,
+            &storage_cache.division_safety_constant,
+        );
+        let merged_position_lock = MergedPositionLock {
+            position_lock,
+            carried_rewards,
+            reward_per_share: storage_cache.reward_per_share.clone(),
+        };
+        drop(storage_cache);
+
+        let output_attributes: FC::AttributesType =
+            self.merge_from_payments_and_burn(payments, &token_mapper);
+
+        (output_attributes, merged_position_lock)
     }
 
     fn claim_only_boosted_payment(&self, caller: &ManagedAddress) -> BigUint {
@@ -251,6 +387,10 @@ pub trait BaseFunctionsModule:
 
     fn set_per_block_rewards<FC: FarmContract<FarmSc = Self>>(&self, per_block_amount: BigUint) {
         require!(per_block_amount != 0u64, ERROR_ZERO_AMOUNT);
+        require!(
+            !self.is_per_second_emission(),
+            "Per block rewards are no longer used"
+        );
 
         let mut storage = StorageCache::new(self);
         FC::generate_aggregated_rewards(self, &mut storage);
@@ -258,6 +398,36 @@ pub trait BaseFunctionsModule:
         self.per_block_reward_amount().set(&per_block_amount);
     }
 
+    fn migrate_to_per_second_rewards_base<FC: FarmContract<FarmSc = Self>>(
+        &self,
+        seconds_per_block: u64,
+    ) {
+        let mut storage = StorageCache::new(self);
+        FC::generate_aggregated_rewards(self, &mut storage);
+
+        self.migrate_to_per_second_rewards(seconds_per_block);
+    }
+
+    fn set_per_second_rewards_base<FC: FarmContract<FarmSc = Self>>(
+        &self,
+        per_second_amount: BigUint,
+    ) {
+        let mut storage = StorageCache::new(self);
+        FC::generate_aggregated_rewards(self, &mut storage);
+
+        self.set_per_second_rewards(per_second_amount);
+    }
+
+    fn set_emission_schedule_base<FC: FarmContract<FarmSc = Self>>(
+        &self,
+        segments: MultiValueEncoded<EmissionSegment<Self::Api>>,
+    ) {
+        let mut storage = StorageCache::new(self);
+        FC::generate_aggregated_rewards(self, &mut storage);
+
+        self.set_emission_schedule(segments);
+    }
+
     fn require_queried(&self) {
         let caller = self.blockchain().get_caller();
         let sc_address = self.blockchain().get_sc_address();
@@ -271,7 +441,8 @@ pub trait BaseFunctionsModule:
 pub struct Wrapper<
     T: BaseFunctionsModule
         + farm_boosted_yields::FarmBoostedYieldsModule
-        + crate::exit_penalty::ExitPenaltyModule,
+        + crate::exit_penalty::ExitPenaltyModule
+        + crate::position_lock::PositionLockModule,
 > {
     _phantom: PhantomData<T>,
 }
@@ -280,7 +451,8 @@ impl<T> Wrapper<T>
 where
     T: BaseFunctionsModule
         + farm_boosted_yields::FarmBoostedYieldsModule
-        + crate::exit_penalty::ExitPenaltyModule,
+        + crate::exit_penalty::ExitPenaltyModule
+        + crate::position_lock::PositionLockModule,
 {
     pub fn calculate_boosted_rewards(
         sc: &<Self as FarmContract>::FarmSc,
@@ -296,7 +468,8 @@ impl<T> FarmContract for Wrapper<T>
 where
     T: BaseFunctionsModule
         + farm_boosted_yields::FarmBoostedYieldsModule
-        + crate::exit_penalty::ExitPenaltyModule,
+        + crate::exit_penalty::ExitPenaltyModule
+        + crate::position_lock::PositionLockModule,
 {
     type FarmSc = T;
     type AttributesType = FarmTokenAttributes<<Self::FarmSc as ContractBase>::Api>;
@@ -311,11 +484,14 @@ where
             let split_rewards = sc.take_reward_slice(total_reward);
 
             if storage_cache.farm_token_supply != 0u64 {
+                let weighted_supply = sc.get_weighted_farm_supply(&storage_cache.farm_token_supply);
                 let increase = (&split_rewards.base_farm * &storage_cache.division_safety_constant)
-                    / &storage_cache.farm_token_supply;
+                    / weighted_supply;
                 storage_cache.reward_per_share += &increase;
             }
         }
+
+        sc.update_lock_boosts(&storage_cache.reward_per_share);
     }
 
     fn calculate_rewards(
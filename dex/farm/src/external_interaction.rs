@@ -21,7 +21,9 @@ pub trait ExternalInteractionsModule:
     + events::EventsModule
     + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
     + base_functions::BaseFunctionsModule
+    + crate::additional_rewards::AdditionalRewardsModule
     + exit_penalty::ExitPenaltyModule
+    + crate::position_lock::PositionLockModule
     + farm_base_impl::base_farm_init::BaseFarmInitModule
     + farm_base_impl::base_farm_validation::BaseFarmValidationModule
     + farm_base_impl::enter_farm::BaseEnterFarmModule
@@ -60,8 +62,10 @@ pub trait ExternalInteractionsModule:
 
         let boosted_rewards_payment =
             EsdtTokenPayment::new(self.reward_token_id().get(), 0, boosted_rewards);
+        let carried_rewards = self.carry_additional_rewards(&payments);
 
         let new_farm_token = self.enter_farm::<Wrapper<Self>>(user.clone());
+        self.set_additional_reward_checkpoints(&new_farm_token, &carried_rewards);
         self.send_payment_non_zero(&caller, &new_farm_token);
         self.send_payment_non_zero(&user, &boosted_rewards_payment);
 
@@ -82,10 +86,17 @@ pub trait ExternalInteractionsModule:
 
         self.migrate_old_farm_positions(&user);
 
+        let additional_rewards =
+            self.claim_additional_rewards(&self.call_value().all_esdt_transfers());
         let claim_rewards_result = self.claim_rewards::<Wrapper<Self>>(user.clone());
+        self.set_additional_reward_checkpoints(
+            &claim_rewards_result.new_farm_token,
+            &ManagedVec::new(),
+        );
 
         self.send_payment_non_zero(&caller, &claim_rewards_result.new_farm_token);
         self.send_payment_non_zero(&user, &claim_rewards_result.rewards);
+        self.send_additional_rewards(&user, &additional_rewards);
 
         claim_rewards_result.into()
     }
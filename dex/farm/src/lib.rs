@@ -3,12 +3,14 @@
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
+pub mod additional_rewards;
 pub mod base_functions;
 pub mod exit_penalty;
 pub mod external_interaction;
+pub mod position_lock;
 
-use base_functions::{ClaimRewardsResultType, DoubleMultiPayment, Wrapper};
-use common_structs::FarmTokenAttributes;
+use base_functions::{DoubleMultiPayment, Wrapper};
+use common_structs::{Epoch, FarmTokenAttributes};
 use contexts::storage_cache::StorageCache;
 
 use exit_penalty::{
@@ -16,9 +18,15 @@ use exit_penalty::{
 };
 use farm_base_impl::base_traits_impl::FarmContract;
 use fixed_supply_token::FixedSupplyToken;
+use rewards::emission_schedule::EmissionSegment;
 
 pub type EnterFarmResultType<M> = DoubleMultiPayment<M>;
 pub type ExitFarmWithPartialPosResultType<M> = DoubleMultiPayment<M>;
+pub type AllRewardsResultType<M> = MultiValue3<
+    EsdtTokenPayment<M>,
+    EsdtTokenPayment<M>,
+    MultiValueEncoded<M, EsdtTokenPayment<M>>,
+>;
 
 pub const MAX_PERCENT: u64 = 10_000;
 
@@ -36,7 +44,9 @@ pub trait Farm:
     + events::EventsModule
     + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
     + base_functions::BaseFunctionsModule
+    + additional_rewards::AdditionalRewardsModule
     + exit_penalty::ExitPenaltyModule
+    + position_lock::PositionLockModule
     + external_interaction::ExternalInteractionsModule
     + farm_base_impl::base_farm_init::BaseFarmInitModule
     + farm_base_impl::base_farm_validation::BaseFarmValidationModule
@@ -103,6 +113,28 @@ pub trait Farm:
     fn enter_farm_endpoint(
         &self,
         opt_orig_caller: OptionalValue<ManagedAddress>,
+    ) -> EnterFarmResultType<Self::Api> {
+        self.enter_farm_and_send(0, opt_orig_caller)
+    }
+
+    /// Locks the new position for `lock_epochs`, which has to be one of the lock options.
+    /// Until the unlock epoch, its base farm rewards are multiplied by the option multiplier.
+    #[payable("*")]
+    #[endpoint(enterFarmLocked)]
+    fn enter_farm_locked_endpoint(
+        &self,
+        lock_epochs: Epoch,
+        opt_orig_caller: OptionalValue<ManagedAddress>,
+    ) -> EnterFarmResultType<Self::Api> {
+        require!(lock_epochs > 0, "Invalid lock period");
+
+        self.enter_farm_and_send(lock_epochs, opt_orig_caller)
+    }
+
+    fn enter_farm_and_send(
+        &self,
+        lock_epochs: Epoch,
+        opt_orig_caller: OptionalValue<ManagedAddress>,
     ) -> EnterFarmResultType<Self::Api> {
         let caller = self.blockchain().get_caller();
         let orig_caller = self.get_orig_caller_from_opt(&caller, opt_orig_caller);
@@ -111,8 +143,12 @@ pub trait Farm:
         let boosted_rewards = self.claim_only_boosted_payment(&orig_caller);
         let boosted_rewards_payment =
             EsdtTokenPayment::new(self.reward_token_id().get(), 0, boosted_rewards);
+        let carried_rewards =
+            self.carry_additional_rewards(&self.call_value().all_esdt_transfers());
 
-        let new_farm_token = self.enter_farm::<Wrapper<Self>>(orig_caller.clone());
+        let new_farm_token =
+            self.enter_farm_with_lock::<Wrapper<Self>>(orig_caller.clone(), lock_epochs);
+        self.set_additional_reward_checkpoints(&new_farm_token, &carried_rewards);
         self.send_payment_non_zero(&caller, &new_farm_token);
         self.send_payment_non_zero(&caller, &boosted_rewards_payment);
 
@@ -126,18 +162,30 @@ pub trait Farm:
     fn claim_rewards_endpoint(
         &self,
         opt_orig_caller: OptionalValue<ManagedAddress>,
-    ) -> ClaimRewardsResultType<Self::Api> {
+    ) -> AllRewardsResultType<Self::Api> {
         let caller = self.blockchain().get_caller();
         let orig_caller = self.get_orig_caller_from_opt(&caller, opt_orig_caller);
 
         self.migrate_old_farm_positions(&orig_caller);
 
-        let claim_rewards_result = self.claim_rewards::<Wrapper<Self>>(orig_caller);
+        let additional_rewards =
+            self.claim_additional_rewards(&self.call_value().all_esdt_transfers());
+        let claim_rewards_result = self.claim_rewards::<Wrapper<Self>>(orig_caller.clone());
+        self.set_additional_reward_checkpoints(
+            &claim_rewards_result.new_farm_token,
+            &ManagedVec::new(),
+        );
 
         self.send_payment_non_zero(&caller, &claim_rewards_result.new_farm_token);
         self.send_payment_non_zero(&caller, &claim_rewards_result.rewards);
+        self.send_additional_rewards(&caller, &additional_rewards);
 
-        claim_rewards_result.into()
+        (
+            claim_rewards_result.new_farm_token,
+            claim_rewards_result.rewards,
+            additional_rewards.into(),
+        )
+            .into()
     }
 
     #[payable("*")]
@@ -151,7 +199,10 @@ pub trait Farm:
 
         self.migrate_old_farm_positions(&orig_caller);
 
+        let carried_rewards =
+            self.carry_additional_rewards(&self.call_value().all_esdt_transfers());
         let output_farm_token_payment = self.compound_rewards::<Wrapper<Self>>(orig_caller.clone());
+        self.set_additional_reward_checkpoints(&output_farm_token_payment, &carried_rewards);
 
         self.send_payment_non_zero(&caller, &output_farm_token_payment);
 
@@ -165,7 +216,7 @@ pub trait Farm:
     fn exit_farm_endpoint(
         &self,
         opt_orig_caller: OptionalValue<ManagedAddress>,
-    ) -> ExitFarmWithPartialPosResultType<Self::Api> {
+    ) -> AllRewardsResultType<Self::Api> {
         let caller = self.blockchain().get_caller();
         let orig_caller = self.get_orig_caller_from_opt(&caller, opt_orig_caller);
 
@@ -173,16 +224,24 @@ pub trait Farm:
 
         let migrated_amount = self.migrate_old_farm_positions(&orig_caller);
 
+        let additional_rewards =
+            self.claim_additional_rewards(&ManagedVec::from_single_item(payment.clone()));
         let exit_farm_result = self.exit_farm::<Wrapper<Self>>(orig_caller.clone(), payment);
 
         self.decrease_old_farm_positions(migrated_amount, &orig_caller);
 
         self.send_payment_non_zero(&caller, &exit_farm_result.farming_tokens);
         self.send_payment_non_zero(&caller, &exit_farm_result.rewards);
+        self.send_additional_rewards(&caller, &additional_rewards);
 
         self.clear_user_energy_if_needed(&orig_caller);
 
-        (exit_farm_result.farming_tokens, exit_farm_result.rewards).into()
+        (
+            exit_farm_result.farming_tokens,
+            exit_farm_result.rewards,
+            additional_rewards.into(),
+        )
+            .into()
     }
 
     #[payable("*")]
@@ -198,8 +257,11 @@ pub trait Farm:
         let boosted_rewards = self.claim_only_boosted_payment(&orig_caller);
         let boosted_rewards_payment =
             EsdtTokenPayment::new(self.reward_token_id().get(), 0, boosted_rewards);
+        let carried_rewards =
+            self.carry_additional_rewards(&self.call_value().all_esdt_transfers());
 
-        let merged_farm_token = self.merge_and_update_farm_tokens(orig_caller);
+        let merged_farm_token = self.merge_and_update_farm_tokens(orig_caller.clone());
+        self.set_additional_reward_checkpoints(&merged_farm_token, &carried_rewards);
 
         self.send_payment_non_zero(&caller, &merged_farm_token);
         self.send_payment_non_zero(&caller, &boosted_rewards_payment);
@@ -208,12 +270,22 @@ pub trait Farm:
     }
 
     fn merge_and_update_farm_tokens(&self, orig_caller: ManagedAddress) -> EsdtTokenPayment {
-        let mut output_attributes = self.merge_and_return_attributes::<Wrapper<Self>>(&orig_caller);
+        let (mut output_attributes, merged_position_lock) =
+            self.merge_and_return_attributes::<Wrapper<Self>>(&orig_caller);
         output_attributes.original_owner = orig_caller;
 
         let new_token_amount = output_attributes.get_total_supply();
-        self.farm_token()
-            .nft_create(new_token_amount, &output_attributes)
+        let new_farm_token = self
+            .farm_token()
+            .nft_create(new_token_amount, &output_attributes);
+        self.set_position_lock(
+            &new_farm_token,
+            merged_position_lock.position_lock,
+            merged_position_lock.carried_rewards,
+            &merged_position_lock.reward_per_share,
+        );
+
+        new_farm_token
     }
 
     #[endpoint(claimBoostedRewards)]
@@ -271,6 +343,31 @@ pub trait Farm:
         self.set_per_block_rewards::<Wrapper<Self>>(per_block_amount);
     }
 
+    /// Switches the emission from per block to per second, converting the current rate
+    /// and the additional reward schedules with the given block time.
+    #[endpoint(migrateToPerSecondRewards)]
+    fn migrate_to_per_second_rewards_endpoint(&self, seconds_per_block: u64) {
+        self.require_caller_has_admin_permissions();
+        self.migrate_additional_rewards_to_per_second(seconds_per_block);
+        self.migrate_to_per_second_rewards_base::<Wrapper<Self>>(seconds_per_block);
+    }
+
+    #[endpoint(setPerSecondRewardAmount)]
+    fn set_per_second_rewards_endpoint(&self, per_second_amount: BigUint) {
+        self.require_caller_has_admin_permissions();
+        self.set_per_second_rewards_base::<Wrapper<Self>>(per_second_amount);
+    }
+
+    /// Replaces the emission schedule of the farm. An empty list removes it.
+    #[endpoint(setEmissionSchedule)]
+    fn set_emission_schedule_endpoint(
+        &self,
+        segments: MultiValueEncoded<EmissionSegment<Self::Api>>,
+    ) {
+        self.require_caller_has_admin_permissions();
+        self.set_emission_schedule_base::<Wrapper<Self>>(segments);
+    }
+
     #[endpoint(setBoostedYieldsRewardsPercentage)]
     fn set_boosted_yields_rewards_percentage(&self, percentage: u64) {
         self.require_caller_has_admin_permissions();
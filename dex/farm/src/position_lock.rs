@@ -0,0 +1,384 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use common_structs::{Epoch, Nonce, PaymentsVec};
+
+use crate::MAX_PERCENT;
+
+pub const MAX_LOCK_OPTIONS: usize = 10;
+pub const MAX_LOCK_EPOCHS: Epoch = 1_440;
+pub const MAX_LOCK_MULTIPLIER: u64 = 50_000;
+
+/// The unlock epoch of a position and its reward weight, in basis points.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct LockTerms {
+    pub unlock_epoch: Epoch,
+    pub lock_multiplier: u64,
+}
+
+/// The lock of a farm token nonce. A position whose lock expired keeps it only until
+/// its pending lock boost rewards are paid.
+#[derive(TypeAbi, TopEncode, TopDecode, Clone, PartialEq, Debug)]
+pub struct PositionLock<M: ManagedTypeApi> {
+    pub unlock_epoch: Epoch,
+    pub lock_multiplier: u64,
+    /// The reward per share from which the lock boost rewards are computed.
+    pub reward_per_share: BigUint<M>,
+    /// The lock boost rewards carried over from the merged or compounded positions.
+    pub carried_rewards: BigUint<M>,
+    /// The amount of the nonce that wasn't burned yet.
+    pub remaining_amount: BigUint<M>,
+}
+
+/// Positions can be locked for one of the configured periods when entering the farm.
+/// Until their unlock epoch, their share of the base farm rewards is multiplied by the
+/// multiplier of the chosen period.
+///
+/// The lock is kept in storage, by farm token nonce, so the farm token attributes are
+/// the same for locked and unlocked positions. Changing the attributes would break their
+/// decoding in the contracts holding farm tokens, e.g. the proxies, and the existing positions.
+///
+/// Positions with different locks are merged into a position locked until the latest unlock
+/// epoch, with the multiplier averaged by amount. The unlocked parts, including the expired
+/// locks, count with a multiplier of MAX_PERCENT. The reward weight of the merged position
+/// is thus at most the sum of the weights of the merged ones, while their locks are extended.
+///
+/// The extra weight of the locked positions is added to the farm token supply when
+/// computing the reward per share. The weight is kept until the first reward update
+/// in or after the unlock epoch, which also saves the reward per share the rewards of
+/// the expired positions are computed with.
+#[multiversx_sc::module]
+pub trait PositionLockModule:
+    config::ConfigModule
+    + farm_token::FarmTokenModule
+    + pausable::PausableModule
+    + permissions_module::PermissionsModule
+    + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
+{
+    /// Replaces the lock options, given as pairs of lock epochs and reward multiplier,
+    /// in basis points. The existing positions keep their lock. An empty list disables locking.
+    #[only_owner]
+    #[endpoint(setLockOptions)]
+    fn set_lock_options(&self, options: MultiValueEncoded<MultiValue2<Epoch, u64>>) {
+        require!(options.len() <= MAX_LOCK_OPTIONS, "Too many lock options");
+
+        let mut options_mapper = self.lock_options();
+        options_mapper.clear();
+        for option in options {
+            let (lock_epochs, multiplier) = option.into_tuple();
+            require!(
+                lock_epochs > 0 && lock_epochs <= MAX_LOCK_EPOCHS,
+                "Invalid lock epochs"
+            );
+            require!(
+                multiplier > MAX_PERCENT && multiplier <= MAX_LOCK_MULTIPLIER,
+                "Invalid lock multiplier"
+            );
+
+            let previous_multiplier = options_mapper.insert(lock_epochs, multiplier);
+            require!(previous_multiplier.is_none(), "Duplicate lock epochs");
+        }
+    }
+
+    /// Part of the farming tokens burned when exiting a position before its unlock epoch,
+    /// in basis points. Locked positions cannot be exited while it is zero.
+    #[only_owner]
+    #[endpoint(setEarlyUnlockPenaltyPercent)]
+    fn set_early_unlock_penalty_percent(&self, percent: u64) {
+        require!(percent < MAX_PERCENT, "Invalid percentage");
+
+        self.early_unlock_penalty_percent().set(percent);
+    }
+
+    #[view(getLockOptions)]
+    fn get_lock_options(&self) -> MultiValueEncoded<MultiValue2<Epoch, u64>> {
+        let mut result = MultiValueEncoded::new();
+        for (lock_epochs, multiplier) in self.lock_options().iter() {
+            result.push((lock_epochs, multiplier).into());
+        }
+
+        result
+    }
+
+    /// Returns the lock of a position locked for `lock_epochs`. Zero lock epochs means no lock.
+    fn get_new_position_lock(&self, lock_epochs: Epoch) -> Option<LockTerms> {
+        if lock_epochs == 0 {
+            return None;
+        }
+
+        let lock_multiplier = match self.lock_options().get(&lock_epochs) {
+            Some(multiplier) => multiplier,
+            None => sc_panic!("Invalid lock period"),
+        };
+        let current_epoch = self.blockchain().get_block_epoch();
+
+        Some(LockTerms {
+            unlock_epoch: current_epoch + lock_epochs,
+            lock_multiplier,
+        })
+    }
+
+    /// Returns the lock of the farm token nonce, if it didn't expire yet.
+    fn get_active_position_lock(&self, farm_token_nonce: Nonce) -> Option<LockTerms> {
+        let position_lock_mapper = self.position_lock(farm_token_nonce);
+        if position_lock_mapper.is_empty() {
+            return None;
+        }
+
+        let position_lock = position_lock_mapper.get();
+        let current_epoch = self.blockchain().get_block_epoch();
+        if position_lock.lock_multiplier == 0 || position_lock.unlock_epoch <= current_epoch {
+            return None;
+        }
+
+        Some(LockTerms {
+            unlock_epoch: position_lock.unlock_epoch,
+            lock_multiplier: position_lock.lock_multiplier,
+        })
+    }
+
+    /// Returns the lock of the position merged from `payments`. The farm tokens keep their
+    /// lock, while the other payments, i.e. the farming tokens of a new position, get
+    /// `new_position_lock`.
+    fn get_merged_position_lock(
+        &self,
+        payments: &PaymentsVec<Self::Api>,
+        new_position_lock: Option<LockTerms>,
+    ) -> Option<LockTerms> {
+        let farm_token_id = self.farm_token().get_token_id();
+        let mut unlock_epoch = 0;
+        let mut total_amount = BigUint::zero();
+        let mut weighted_amount = BigUint::zero();
+        for payment in payments {
+            let position_lock = if payment.token_identifier == farm_token_id {
+                self.get_active_position_lock(payment.token_nonce)
+            } else {
+                new_position_lock
+            };
+            let lock_multiplier = match position_lock {
+                Some(lock) => {
+                    unlock_epoch = core::cmp::max(unlock_epoch, lock.unlock_epoch);
+                    lock.lock_multiplier
+                },
+                None => MAX_PERCENT,
+            };
+
+            weighted_amount += &payment.amount * lock_multiplier;
+            total_amount += payment.amount;
+        }
+
+        if unlock_epoch == 0 || total_amount == 0u64 {
+            return None;
+        }
+
+        let lock_multiplier = (weighted_amount / total_amount).to_u64().unwrap();
+
+        Some(LockTerms {
+            unlock_epoch,
+            lock_multiplier,
+        })
+    }
+
+    /// Computes the lock boost rewards of the farm tokens in `payments` and releases their
+    /// locks, as the tokens are burned. Has to be called after the rewards are aggregated.
+    fn take_lock_boost_rewards(
+        &self,
+        payments: &PaymentsVec<Self::Api>,
+        reward_per_share: &BigUint,
+        division_safety_constant: &BigUint,
+    ) -> BigUint {
+        let farm_token_id = self.farm_token().get_token_id();
+        let mut total_rewards = BigUint::zero();
+        for payment in payments {
+            if payment.token_identifier != farm_token_id {
+                continue;
+            }
+
+            let position_lock_mapper = self.position_lock(payment.token_nonce);
+            if position_lock_mapper.is_empty() {
+                continue;
+            }
+
+            let mut position_lock = position_lock_mapper.get();
+            let lock_boost = self.get_lock_boost(&payment.amount, position_lock.lock_multiplier);
+            total_rewards += self.calculate_lock_boost_rewards(
+                &lock_boost,
+                &position_lock,
+                reward_per_share,
+                division_safety_constant,
+            );
+            self.remove_lock_boost(position_lock.unlock_epoch, &lock_boost);
+
+            if position_lock.remaining_amount <= payment.amount {
+                total_rewards += position_lock.carried_rewards;
+                position_lock_mapper.clear();
+                continue;
+            }
+
+            let carried_rewards =
+                &position_lock.carried_rewards * &payment.amount / &position_lock.remaining_amount;
+            position_lock.carried_rewards -= &carried_rewards;
+            position_lock.remaining_amount -= &payment.amount;
+            total_rewards += carried_rewards;
+
+            position_lock_mapper.set(position_lock);
+        }
+
+        total_rewards
+    }
+
+    /// Saves the lock of a new farm token, along with the lock boost rewards it is owed.
+    fn set_position_lock(
+        &self,
+        new_farm_token: &EsdtTokenPayment,
+        position_lock: Option<LockTerms>,
+        carried_rewards: BigUint,
+        reward_per_share: &BigUint,
+    ) {
+        if new_farm_token.amount == 0u64 || (position_lock.is_none() && carried_rewards == 0u64) {
+            return;
+        }
+
+        let (unlock_epoch, lock_multiplier) = match position_lock {
+            Some(lock) => (lock.unlock_epoch, lock.lock_multiplier),
+            None => (0, 0),
+        };
+        let lock_boost = self.get_lock_boost(&new_farm_token.amount, lock_multiplier);
+        self.add_lock_boost(unlock_epoch, &lock_boost);
+
+        self.position_lock(new_farm_token.token_nonce)
+            .set(PositionLock {
+                unlock_epoch,
+                lock_multiplier,
+                reward_per_share: reward_per_share.clone(),
+                carried_rewards,
+                remaining_amount: new_farm_token.amount.clone(),
+            });
+    }
+
+    /// The reward weight a locked position has on top of its amount.
+    fn get_lock_boost(&self, amount: &BigUint, lock_multiplier: u64) -> BigUint {
+        if lock_multiplier > MAX_PERCENT {
+            amount * (lock_multiplier - MAX_PERCENT) / MAX_PERCENT
+        } else {
+            BigUint::zero()
+        }
+    }
+
+    fn add_lock_boost(&self, unlock_epoch: Epoch, lock_boost: &BigUint) {
+        if !self.is_lock_boost_active(unlock_epoch, lock_boost) {
+            return;
+        }
+
+        self.expiring_lock_boost(unlock_epoch)
+            .update(|amount| *amount += lock_boost);
+        self.total_lock_boost()
+            .update(|amount| *amount += lock_boost);
+    }
+
+    /// The boosts of the expired locks were already removed from the total.
+    fn remove_lock_boost(&self, unlock_epoch: Epoch, lock_boost: &BigUint) {
+        if !self.is_lock_boost_active(unlock_epoch, lock_boost) {
+            return;
+        }
+
+        self.expiring_lock_boost(unlock_epoch)
+            .update(|amount| *amount -= lock_boost);
+        self.total_lock_boost()
+            .update(|amount| *amount -= lock_boost);
+    }
+
+    fn is_lock_boost_active(&self, unlock_epoch: Epoch, lock_boost: &BigUint) -> bool {
+        lock_boost > &0u64 && unlock_epoch > self.last_lock_update_epoch().get()
+    }
+
+    /// Removes the boosts of the locks which expired since the last update. Has to be called
+    /// after the reward per share is increased, so that the rewards generated since the
+    /// previous update, which includes the blocks before the unlock epoch, are still
+    /// shared with the boosts applied.
+    fn update_lock_boosts(&self, reward_per_share: &BigUint) {
+        let current_epoch = self.blockchain().get_block_epoch();
+        let last_update_mapper = self.last_lock_update_epoch();
+        let last_update_epoch = last_update_mapper.get();
+        if current_epoch <= last_update_epoch {
+            return;
+        }
+
+        last_update_mapper.set(current_epoch);
+
+        let total_lock_boost_mapper = self.total_lock_boost();
+        let mut total_lock_boost = total_lock_boost_mapper.get();
+        if total_lock_boost == 0u64 {
+            return;
+        }
+
+        for epoch in last_update_epoch + 1..=current_epoch {
+            let expiring_mapper = self.expiring_lock_boost(epoch);
+            if expiring_mapper.is_empty() {
+                continue;
+            }
+
+            total_lock_boost -= expiring_mapper.take();
+            self.unlock_reward_per_share(epoch).set(reward_per_share);
+        }
+
+        total_lock_boost_mapper.set(total_lock_boost);
+    }
+
+    /// The supply used to compute the reward per share.
+    fn get_weighted_farm_supply(&self, farm_token_supply: &BigUint) -> BigUint {
+        farm_token_supply + &self.total_lock_boost().get()
+    }
+
+    /// The rewards a locked position receives on top of its base farm rewards. The boost of
+    /// an expired lock only applies up to the reward per share saved when it expired.
+    fn calculate_lock_boost_rewards(
+        &self,
+        lock_boost: &BigUint,
+        position_lock: &PositionLock<Self::Api>,
+        reward_per_share: &BigUint,
+        division_safety_constant: &BigUint,
+    ) -> BigUint {
+        if lock_boost == &0u64 {
+            return BigUint::zero();
+        }
+
+        let boost_end_reward_per_share =
+            if position_lock.unlock_epoch <= self.last_lock_update_epoch().get() {
+                self.unlock_reward_per_share(position_lock.unlock_epoch)
+                    .get()
+            } else {
+                reward_per_share.clone()
+            };
+        if boost_end_reward_per_share <= position_lock.reward_per_share {
+            return BigUint::zero();
+        }
+
+        lock_boost * &(boost_end_reward_per_share - &position_lock.reward_per_share)
+            / division_safety_constant
+    }
+
+    #[storage_mapper("lockOptions")]
+    fn lock_options(&self) -> MapMapper<Epoch, u64>;
+
+    #[view(getEarlyUnlockPenaltyPercent)]
+    #[storage_mapper("earlyUnlockPenaltyPercent")]
+    fn early_unlock_penalty_percent(&self) -> SingleValueMapper<u64>;
+
+    #[view(getPositionLock)]
+    #[storage_mapper("positionLock")]
+    fn position_lock(&self, farm_token_nonce: Nonce) -> SingleValueMapper<PositionLock<Self::Api>>;
+
+    #[view(getTotalLockBoost)]
+    #[storage_mapper("totalLockBoost")]
+    fn total_lock_boost(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("expiringLockBoost")]
+    fn expiring_lock_boost(&self, unlock_epoch: Epoch) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("unlockRewardPerShare")]
+    fn unlock_reward_per_share(&self, unlock_epoch: Epoch) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("lastLockUpdateEpoch")]
+    fn last_lock_update_epoch(&self) -> SingleValueMapper<Epoch>;
+}
@@ -998,3 +998,144 @@ fn farm_multiple_claim_weeks_with_collect_undistributed_rewards_test() {
         .b_mock
         .check_esdt_balance(&farm_setup.owner, MEX_TOKEN_ID, &rust_biguint!(0));
 }
+
+#[test]
+fn farm_locked_positions_test() {
+    DebugApi::dummy();
+    let mut farm_setup = MultiUserFarmSetup::new(
+        farm::contract_obj,
+        energy_factory::contract_obj,
+        energy_update::contract_obj,
+        permissions_hub::contract_obj,
+    );
+
+    // locking for 10 epochs doubles the rewards weight
+    farm_setup.set_lock_options(vec![(10, 20_000), (5, 15_000)]);
+
+    // first user enters without lock
+    let farm_token_amount = 100_000;
+    let first_user = farm_setup.first_user.clone();
+    farm_setup.enter_farm(&first_user, farm_token_amount);
+
+    // second user enters with the same amount, locked until epoch 10
+    // the lock is kept by the farm, the attributes are the same as for unlocked positions
+    let second_user = farm_setup.second_user.clone();
+    farm_setup.enter_farm_locked(&second_user, farm_token_amount, 10);
+    farm_setup
+        .b_mock
+        .check_nft_balance::<FarmTokenAttributes<DebugApi>>(
+            &second_user,
+            FARM_TOKEN_ID,
+            2,
+            &rust_biguint!(farm_token_amount),
+            Some(&FarmTokenAttributes {
+                reward_per_share: managed_biguint!(0),
+                entering_epoch: 0,
+                compounded_reward: managed_biguint!(0),
+                current_farm_amount: managed_biguint!(farm_token_amount),
+                original_owner: managed_address!(&second_user),
+            }),
+        );
+    farm_setup.check_no_position_lock(1);
+    farm_setup.check_position_lock(2, 10, 20_000, 0);
+    farm_setup.check_total_lock_boost(farm_token_amount);
+
+    // 10 blocks - 10_000 rewards, shared by a weighted supply of 300_000
+    farm_setup.b_mock.set_block_nonce(10);
+    let first_rewards = farm_setup.claim_rewards(&first_user, 1, farm_token_amount);
+    assert_eq!(first_rewards, 3_333);
+
+    farm_setup.exit_farm_expect_error(
+        &second_user,
+        2,
+        farm_token_amount,
+        "Farm position is locked",
+    );
+
+    // the next 10_000 rewards were generated before the unlock epoch,
+    // so the lock boost still applies to them
+    farm_setup.b_mock.set_block_epoch(10);
+    farm_setup.b_mock.set_block_nonce(20);
+    let second_rewards = farm_setup.claim_rewards(&second_user, 2, farm_token_amount);
+    assert_eq!(second_rewards, 6_666 + 6_666);
+    farm_setup.check_total_lock_boost(0);
+    farm_setup.check_no_position_lock(2);
+    farm_setup.check_no_position_lock(4);
+
+    let first_rewards = farm_setup.claim_rewards(&first_user, 3, farm_token_amount);
+    assert_eq!(first_rewards, 3_333);
+
+    let locked_amount = 50_000;
+    farm_setup.enter_farm_locked(&second_user, locked_amount, 5);
+    farm_setup.check_position_lock(6, 15, 15_000, 0);
+    farm_setup.check_total_lock_boost(25_000);
+
+    // 10_000 rewards, shared by a weighted supply of 275_000
+    farm_setup.b_mock.set_block_nonce(30);
+
+    // an expired lock counts as no lock when merging
+    // the lock boost rewards of the expired position are carried over to the merged one
+    farm_setup.b_mock.set_block_epoch(15);
+    farm_setup.merge_farm_tokens(
+        &second_user,
+        vec![
+            NonceAmountPair {
+                nonce: 4,
+                amount: farm_token_amount,
+            },
+            NonceAmountPair {
+                nonce: 6,
+                amount: locked_amount,
+            },
+        ],
+    );
+    farm_setup.check_no_position_lock(6);
+    farm_setup.check_position_lock(7, 0, 0, 909);
+    farm_setup.check_total_lock_boost(0);
+
+    // base rewards of 3_636 + 1_818 and the carried lock boost rewards
+    let second_rewards =
+        farm_setup.claim_rewards(&second_user, 7, farm_token_amount + locked_amount);
+    assert_eq!(second_rewards, 3_636 + 1_818 + 909);
+    farm_setup.check_no_position_lock(7);
+
+    // early exit is possible only after the owner sets a penalty
+    farm_setup.enter_farm_locked(&second_user, locked_amount, 10);
+    farm_setup.exit_farm_expect_error(&second_user, 9, locked_amount, "Farm position is locked");
+    farm_setup.set_early_unlock_penalty_percent(2_000);
+    farm_setup.exit_farm(&second_user, 9, locked_amount);
+    farm_setup.check_no_position_lock(9);
+    farm_setup.check_total_lock_boost(0);
+    farm_setup.b_mock.check_esdt_balance(
+        &second_user,
+        FARMING_TOKEN_ID,
+        &rust_biguint!(FARMING_TOKEN_BALANCE - farm_token_amount - locked_amount - 10_000),
+    );
+
+    // positions with different locks are merged until the latest unlock epoch,
+    // with the multiplier averaged by amount, the unlocked position counting as 100%
+    farm_setup.enter_farm_locked(&second_user, locked_amount, 10);
+    farm_setup.enter_farm_locked(&second_user, locked_amount, 5);
+    farm_setup.check_total_lock_boost(75_000);
+    farm_setup.merge_farm_tokens(
+        &second_user,
+        vec![
+            NonceAmountPair {
+                nonce: 8,
+                amount: farm_token_amount + locked_amount,
+            },
+            NonceAmountPair {
+                nonce: 10,
+                amount: locked_amount,
+            },
+            NonceAmountPair {
+                nonce: 11,
+                amount: locked_amount,
+            },
+        ],
+    );
+    farm_setup.check_no_position_lock(10);
+    farm_setup.check_no_position_lock(11);
+    farm_setup.check_position_lock(12, 25, 13_000, 0);
+    farm_setup.check_total_lock_boost(75_000);
+}
@@ -6,6 +6,7 @@ use config::ConfigModule;
 use energy_factory::energy::EnergyModule;
 use energy_factory::unlocked_token_transfer::UnlockedTokenTransferModule;
 use farm::external_interaction::ExternalInteractionsModule;
+use farm::position_lock::PositionLockModule;
 use farm_boosted_yields::undistributed_rewards::UndistributedRewardsModule;
 use multiversx_sc::codec::multi_types::OptionalValue;
 use multiversx_sc::{
@@ -503,7 +504,7 @@ where
                 farm_token_nonce,
                 &rust_biguint!(farm_token_amount),
                 |sc| {
-                    let (out_farm_token, out_reward_token) =
+                    let (out_farm_token, out_reward_token, _) =
                         sc.claim_rewards_endpoint(OptionalValue::None).into_tuple();
                     assert_eq!(
                         out_farm_token.token_identifier,
@@ -549,7 +550,7 @@ where
         let mut result = 0;
         self.b_mock
             .execute_esdt_multi_transfer(user, &self.farm_wrapper, &payments, |sc| {
-                let (out_farm_token, out_reward_token) =
+                let (out_farm_token, out_reward_token, _) =
                     sc.claim_rewards_endpoint(OptionalValue::None).into_tuple();
                 assert_eq!(
                     out_farm_token.token_identifier,
@@ -626,7 +627,7 @@ where
                 farm_token_nonce,
                 &rust_biguint!(farm_token_amount),
                 |sc| {
-                    let (out_farm_token, out_reward_token) = sc
+                    let (out_farm_token, out_reward_token, _) = sc
                         .claim_rewards_endpoint(OptionalValue::Some(managed_address!(user)))
                         .into_tuple();
                     assert_eq!(
@@ -1029,4 +1030,119 @@ where
             }),
         );
     }
+
+    pub fn set_lock_options(&mut self, options: Vec<(Epoch, u64)>) {
+        self.b_mock
+            .execute_tx(&self.owner, &self.farm_wrapper, &rust_biguint!(0), |sc| {
+                let mut args = MultiValueEncoded::new();
+                for option in options {
+                    args.push(option.into());
+                }
+
+                sc.set_lock_options(args);
+            })
+            .assert_ok();
+    }
+
+    pub fn set_early_unlock_penalty_percent(&mut self, percent: u64) {
+        self.b_mock
+            .execute_tx(&self.owner, &self.farm_wrapper, &rust_biguint!(0), |sc| {
+                sc.set_early_unlock_penalty_percent(percent);
+            })
+            .assert_ok();
+    }
+
+    pub fn enter_farm_locked(
+        &mut self,
+        user: &Address,
+        farming_token_amount: u64,
+        lock_epochs: Epoch,
+    ) {
+        self.last_farm_token_nonce += 1;
+
+        let expected_farm_token_nonce = self.last_farm_token_nonce;
+        self.b_mock
+            .execute_esdt_transfer(
+                user,
+                &self.farm_wrapper,
+                FARMING_TOKEN_ID,
+                0,
+                &rust_biguint!(farming_token_amount),
+                |sc| {
+                    let (out_farm_token, _reward_token) = sc
+                        .enter_farm_locked_endpoint(lock_epochs, OptionalValue::None)
+                        .into_tuple();
+                    assert_eq!(
+                        out_farm_token.token_identifier,
+                        managed_token_id!(FARM_TOKEN_ID)
+                    );
+                    assert_eq!(out_farm_token.token_nonce, expected_farm_token_nonce);
+                    assert_eq!(
+                        out_farm_token.amount,
+                        managed_biguint!(farming_token_amount)
+                    );
+                },
+            )
+            .assert_ok();
+    }
+
+    pub fn check_total_lock_boost(&mut self, expected_amount: u64) {
+        self.b_mock
+            .execute_query(&self.farm_wrapper, |sc| {
+                assert_eq!(
+                    sc.total_lock_boost().get(),
+                    managed_biguint!(expected_amount)
+                );
+            })
+            .assert_ok();
+    }
+
+    pub fn check_position_lock(
+        &mut self,
+        farm_token_nonce: u64,
+        expected_unlock_epoch: Epoch,
+        expected_lock_multiplier: u64,
+        expected_carried_rewards: u64,
+    ) {
+        self.b_mock
+            .execute_query(&self.farm_wrapper, |sc| {
+                let position_lock = sc.position_lock(farm_token_nonce).get();
+                assert_eq!(position_lock.unlock_epoch, expected_unlock_epoch);
+                assert_eq!(position_lock.lock_multiplier, expected_lock_multiplier);
+                assert_eq!(
+                    position_lock.carried_rewards,
+                    managed_biguint!(expected_carried_rewards)
+                );
+            })
+            .assert_ok();
+    }
+
+    pub fn check_no_position_lock(&mut self, farm_token_nonce: u64) {
+        self.b_mock
+            .execute_query(&self.farm_wrapper, |sc| {
+                assert!(sc.position_lock(farm_token_nonce).is_empty());
+            })
+            .assert_ok();
+    }
+
+    pub fn exit_farm_expect_error(
+        &mut self,
+        user: &Address,
+        farm_token_nonce: u64,
+        exit_farm_amount: u64,
+        expected_message: &str,
+    ) {
+        self.b_mock
+            .execute_esdt_transfer(
+                user,
+                &self.farm_wrapper,
+                FARM_TOKEN_ID,
+                farm_token_nonce,
+                &rust_biguint!(exit_farm_amount),
+                |sc| {
+                    let _ = sc.exit_farm_endpoint(OptionalValue::None);
+                },
+            )
+            .assert_user_error(expected_message);
+    }
 }
@@ -28,6 +28,7 @@ pub const WEGLD_TOKEN_ID: &[u8] = b"WEGLD-abcdef";
 pub const MEX_TOKEN_ID: &[u8] = b"MEX-abcdef"; // reward token ID
 pub const LP_TOKEN_ID: &[u8] = b"LPTOK-abcdef"; // farming token ID
 pub const FARM_TOKEN_ID: &[u8] = b"FARM-abcdef";
+pub const PARTNER_TOKEN_ID: &[u8] = b"PARTNER-abcdef";
 pub const DIVISION_SAFETY_CONSTANT: u64 = 1_000_000_000_000;
 pub const MIN_FARMING_EPOCHS: u64 = 2;
 pub const PENALTY_PERCENT: u64 = 10;
@@ -319,7 +320,7 @@ where
                 |sc| {
                     let multi_result = sc.exit_farm_endpoint(OptionalValue::None);
 
-                    let (first_result, second_result) = multi_result.into_tuple();
+                    let (first_result, second_result, _) = multi_result.into_tuple();
 
                     assert_eq!(
                         first_result.token_identifier,
@@ -371,7 +372,7 @@ where
                 |sc| {
                     let multi_result = sc.claim_rewards_endpoint(OptionalValue::None);
 
-                    let (first_result, second_result) = multi_result.into_tuple();
+                    let (first_result, second_result, _) = multi_result.into_tuple();
 
                     assert_eq!(
                         first_result.token_identifier,
@@ -3,12 +3,20 @@
 mod farm_setup;
 
 use config::ConfigModule;
+use farm::{additional_rewards::AdditionalRewardsModule, Farm};
 use farm_setup::single_user_farm_setup::*;
-use multiversx_sc::types::EsdtLocalRole;
+use multiversx_sc::{
+    codec::multi_types::OptionalValue,
+    types::{EsdtLocalRole, MultiValueEncoded},
+};
 use multiversx_sc_scenario::{
     managed_address, managed_biguint, managed_token_id, rust_biguint,
     whitebox_legacy::TxTokenTransfer, DebugApi,
 };
+use rewards::{
+    emission_schedule::{EmissionCurve, EmissionSegment},
+    RewardsModule,
+};
 use sc_whitelist_module::SCWhitelistModule;
 
 #[test]
@@ -106,6 +114,392 @@ fn test_claim_rewards() {
     farm_setup.check_farm_token_supply(farm_in_amount);
 }
 
+#[test]
+fn test_migrate_to_per_second_rewards() {
+    let mut farm_setup = SingleUserFarmSetup::new(farm::contract_obj, pair::contract_obj);
+
+    let farm_in_amount = 100_000_000;
+    let expected_farm_token_nonce = 1;
+    farm_setup.enter_farm(farm_in_amount, &[], expected_farm_token_nonce, 0, 0, 0);
+
+    // 1_000 partner tokens per block, between blocks 5 and 15
+    let owner_address = farm_setup.owner_address.clone();
+    farm_setup.blockchain_wrapper.set_esdt_balance(
+        &owner_address,
+        PARTNER_TOKEN_ID,
+        &rust_biguint!(10_000),
+    );
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.add_additional_reward_token(managed_token_id!(PARTNER_TOKEN_ID));
+            },
+        )
+        .assert_ok();
+    farm_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &owner_address,
+            &farm_setup.farm_wrapper,
+            PARTNER_TOKEN_ID,
+            0,
+            &rust_biguint!(10_000),
+            |sc| {
+                sc.deposit_additional_rewards(5, 15);
+            },
+        )
+        .assert_ok();
+
+    // the rewards of the first 10 blocks are generated per block
+    farm_setup.set_block_nonce(10);
+    farm_setup.blockchain_wrapper.set_block_timestamp(60);
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.migrate_to_per_second_rewards_endpoint(6);
+
+                assert_eq!(
+                    sc.per_second_reward_amount().get(),
+                    managed_biguint!(PER_BLOCK_REWARD_AMOUNT / 6)
+                );
+                assert_eq!(sc.last_reward_timestamp().get(), 60);
+
+                // the remaining 5 blocks of the additional rewards are now 30 seconds
+                let info = sc
+                    .additional_reward_info(&managed_token_id!(PARTNER_TOKEN_ID))
+                    .get();
+                assert_eq!(info.reward_rate, managed_biguint!(1_000 / 6));
+                assert_eq!(info.start, 60);
+                assert_eq!(info.end, 90);
+                assert_eq!(info.last_update, 60);
+            },
+        )
+        .assert_ok();
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_per_block_rewards_endpoint(managed_biguint!(PER_BLOCK_REWARD_AMOUNT));
+            },
+        )
+        .assert_user_error("Per block rewards are no longer used");
+
+    // block nonces are no longer used
+    farm_setup.set_block_epoch(5);
+    farm_setup.set_block_nonce(100);
+    farm_setup.blockchain_wrapper.set_block_timestamp(120);
+
+    let expected_mex_out = 10 * PER_BLOCK_REWARD_AMOUNT + 60 * (PER_BLOCK_REWARD_AMOUNT / 6);
+    let expected_reward_per_share = expected_mex_out * DIVISION_SAFETY_CONSTANT / farm_in_amount;
+    farm_setup.claim_rewards(
+        farm_in_amount,
+        expected_farm_token_nonce,
+        expected_mex_out,
+        &rust_biguint!(expected_mex_out),
+        &rust_biguint!(USER_TOTAL_LP_TOKENS - farm_in_amount),
+        expected_farm_token_nonce + 1,
+        expected_reward_per_share,
+    );
+    farm_setup.blockchain_wrapper.check_esdt_balance(
+        &farm_setup.user_address,
+        PARTNER_TOKEN_ID,
+        &rust_biguint!(5 * 1_000 + 30 * (1_000 / 6)),
+    );
+}
+
+#[test]
+fn test_emission_schedule() {
+    let mut farm_setup = SingleUserFarmSetup::new(farm::contract_obj, pair::contract_obj);
+
+    let farm_in_amount = 100_000_000;
+    let expected_farm_token_nonce = 1;
+    farm_setup.enter_farm(farm_in_amount, &[], expected_farm_token_nonce, 0, 0, 0);
+
+    let owner_address = farm_setup.owner_address.clone();
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut segments = MultiValueEncoded::new();
+                segments.push(EmissionSegment {
+                    start: 10,
+                    rate: managed_biguint!(1_000),
+                    curve: EmissionCurve::Constant,
+                });
+                segments.push(EmissionSegment {
+                    start: 20,
+                    rate: managed_biguint!(4_000),
+                    curve: EmissionCurve::Halving { period: 5 },
+                });
+                segments.push(EmissionSegment {
+                    start: 30,
+                    rate: managed_biguint!(1_000),
+                    curve: EmissionCurve::LinearDecay {
+                        decay: managed_biguint!(100),
+                    },
+                });
+                sc.set_emission_schedule_endpoint(segments);
+            },
+        )
+        .assert_ok();
+
+    // blocks 0-10 use the per block amount, then 10 * 1_000, 5 * 4_000 + 5 * 2_000,
+    // and 1_000 + 900 + ... + 100, after which the emission stops
+    let expected_mex_out = 10 * PER_BLOCK_REWARD_AMOUNT + 10_000 + 30_000 + 5_500;
+    farm_setup
+        .blockchain_wrapper
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            assert_eq!(
+                sc.get_projected_emissions(100),
+                managed_biguint!(expected_mex_out)
+            );
+        })
+        .assert_ok();
+
+    farm_setup.set_block_nonce(25);
+    farm_setup
+        .blockchain_wrapper
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            let upcoming_starts: Vec<u64> = sc
+                .get_upcoming_emission_schedule()
+                .into_iter()
+                .map(|segment| segment.start)
+                .collect();
+            assert_eq!(upcoming_starts, vec![20, 30]);
+        })
+        .assert_ok();
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut segments = MultiValueEncoded::new();
+                segments.push(EmissionSegment {
+                    start: 20,
+                    rate: managed_biguint!(1_000),
+                    curve: EmissionCurve::Constant,
+                });
+                sc.set_emission_schedule_endpoint(segments);
+            },
+        )
+        .assert_user_error("Invalid emission segment");
+
+    farm_setup.set_block_epoch(5);
+    farm_setup.set_block_nonce(40);
+
+    let expected_reward_per_share = expected_mex_out * DIVISION_SAFETY_CONSTANT / farm_in_amount;
+    farm_setup.claim_rewards(
+        farm_in_amount,
+        expected_farm_token_nonce,
+        expected_mex_out,
+        &rust_biguint!(expected_mex_out),
+        &rust_biguint!(USER_TOTAL_LP_TOKENS - farm_in_amount),
+        expected_farm_token_nonce + 1,
+        expected_reward_per_share,
+    );
+}
+
+#[test]
+fn test_additional_rewards() {
+    let mut farm_setup = SingleUserFarmSetup::new(farm::contract_obj, pair::contract_obj);
+
+    let farm_in_amount = 100_000_000;
+    let expected_farm_token_nonce = 1;
+    farm_setup.enter_farm(farm_in_amount, &[], expected_farm_token_nonce, 0, 0, 0);
+
+    // 1_000 partner tokens per block, between blocks 10 and 20
+    let owner_address = farm_setup.owner_address.clone();
+    farm_setup.blockchain_wrapper.set_esdt_balance(
+        &owner_address,
+        PARTNER_TOKEN_ID,
+        &rust_biguint!(10_000),
+    );
+    farm_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &owner_address,
+            &farm_setup.farm_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.add_additional_reward_token(managed_token_id!(PARTNER_TOKEN_ID));
+            },
+        )
+        .assert_ok();
+    farm_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &owner_address,
+            &farm_setup.farm_wrapper,
+            PARTNER_TOKEN_ID,
+            0,
+            &rust_biguint!(10_000),
+            |sc| {
+                sc.deposit_additional_rewards(10, 20);
+            },
+        )
+        .assert_ok();
+
+    farm_setup.set_block_epoch(5);
+    farm_setup.set_block_nonce(15);
+
+    let expected_mex_out = 15 * PER_BLOCK_REWARD_AMOUNT;
+    farm_setup.claim_rewards(
+        farm_in_amount,
+        expected_farm_token_nonce,
+        expected_mex_out,
+        &rust_biguint!(expected_mex_out),
+        &rust_biguint!(USER_TOTAL_LP_TOKENS - farm_in_amount),
+        expected_farm_token_nonce + 1,
+        750_000_000,
+    );
+    farm_setup.blockchain_wrapper.check_esdt_balance(
+        &farm_setup.user_address,
+        PARTNER_TOKEN_ID,
+        &rust_biguint!(5_000),
+    );
+
+    // the new position only gets the rewards produced after it entered
+    let second_user = farm_setup
+        .blockchain_wrapper
+        .create_user_account(&rust_biguint!(0));
+    farm_setup.blockchain_wrapper.set_esdt_balance(
+        &second_user,
+        LP_TOKEN_ID,
+        &rust_biguint!(farm_in_amount),
+    );
+    farm_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &second_user,
+            &farm_setup.farm_wrapper,
+            LP_TOKEN_ID,
+            0,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let _ = sc.enter_farm_endpoint(OptionalValue::None);
+            },
+        )
+        .assert_ok();
+
+    farm_setup.set_block_nonce(30);
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            let rewards = sc
+                .calculate_additional_rewards_for_given_position(
+                    3,
+                    managed_biguint!(farm_in_amount),
+                )
+                .to_vec();
+            assert_eq!(rewards.len(), 1);
+            assert_eq!(
+                rewards.get(0).token_identifier,
+                managed_token_id!(PARTNER_TOKEN_ID)
+            );
+            assert_eq!(rewards.get(0).amount, managed_biguint!(2_500));
+        })
+        .assert_ok();
+
+    let user_address = farm_setup.user_address.clone();
+    farm_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &user_address,
+            &farm_setup.farm_wrapper,
+            FARM_TOKEN_ID,
+            expected_farm_token_nonce + 1,
+            &rust_biguint!(farm_in_amount),
+            |sc| {
+                let (_, _, additional_rewards) =
+                    sc.exit_farm_endpoint(OptionalValue::None).into_tuple();
+                let additional_rewards = additional_rewards.to_vec();
+                assert_eq!(additional_rewards.len(), 1);
+                assert_eq!(additional_rewards.get(0).amount, managed_biguint!(2_500));
+            },
+        )
+        .assert_ok();
+    farm_setup.blockchain_wrapper.check_esdt_balance(
+        &user_address,
+        PARTNER_TOKEN_ID,
+        &rust_biguint!(7_500),
+    );
+
+    // merging carries the additional rewards over to the new position
+    farm_setup.blockchain_wrapper.set_esdt_balance(
+        &second_user,
+        LP_TOKEN_ID,
+        &rust_biguint!(farm_in_amount),
+    );
+    let payments = [
+        TxTokenTransfer {
+            token_identifier: LP_TOKEN_ID.to_vec(),
+            nonce: 0,
+            value: rust_biguint!(farm_in_amount),
+        },
+        TxTokenTransfer {
+            token_identifier: FARM_TOKEN_ID.to_vec(),
+            nonce: 3,
+            value: rust_biguint!(farm_in_amount),
+        },
+    ];
+    farm_setup
+        .blockchain_wrapper
+        .execute_esdt_multi_transfer(&second_user, &farm_setup.farm_wrapper, &payments, |sc| {
+            let _ = sc.enter_farm_endpoint(OptionalValue::None);
+        })
+        .assert_ok();
+    farm_setup.blockchain_wrapper.check_esdt_balance(
+        &second_user,
+        PARTNER_TOKEN_ID,
+        &rust_biguint!(0),
+    );
+
+    farm_setup
+        .blockchain_wrapper
+        .execute_query(&farm_setup.farm_wrapper, |sc| {
+            let rewards = sc
+                .calculate_additional_rewards_for_given_position(
+                    4,
+                    managed_biguint!(2 * farm_in_amount),
+                )
+                .to_vec();
+            assert_eq!(rewards.get(0).amount, managed_biguint!(2_500));
+
+            // the checkpoints of the burned farm tokens are cleared
+            let partner_token_id = managed_token_id!(PARTNER_TOKEN_ID);
+            for nonce in [2, 3] {
+                assert!(sc
+                    .additional_reward_checkpoint(nonce, &partner_token_id)
+                    .is_empty());
+                assert!(sc.additional_reward_checkpoint_supply(nonce).is_empty());
+            }
+            assert!(!sc
+                .additional_reward_checkpoint(4, &partner_token_id)
+                .is_empty());
+        })
+        .assert_ok();
+}
+
 fn steps_enter_farm_twice<FarmObjBuilder, PairObjBuilder>(
     farm_builder: FarmObjBuilder,
     pair_builder: PairObjBuilder,
@@ -334,6 +728,27 @@ fn test_farm_through_simple_lock() {
         }),
     );
 
+    // 1_000 partner tokens per block, until the claim
+    let owner_addr = farm_setup.owner_address.clone();
+    b_mock.set_esdt_balance(&owner_addr, PARTNER_TOKEN_ID, &rust_biguint!(10_000));
+    b_mock
+        .execute_tx(&owner_addr, &farm_setup.farm_wrapper, &rust_zero, |sc| {
+            sc.add_additional_reward_token(managed_token_id!(PARTNER_TOKEN_ID));
+        })
+        .assert_ok();
+    b_mock
+        .execute_esdt_transfer(
+            &owner_addr,
+            &farm_setup.farm_wrapper,
+            PARTNER_TOKEN_ID,
+            0,
+            &rust_biguint!(10_000),
+            |sc| {
+                sc.deposit_additional_rewards(0, 10);
+            },
+        )
+        .assert_ok();
+
     // user claim farm rewards
     b_mock.set_block_nonce(10);
     b_mock.set_block_epoch(5);
@@ -386,6 +801,8 @@ fn test_farm_through_simple_lock() {
         MEX_TOKEN_ID,
         &rust_biguint!(10 * PER_BLOCK_REWARD_AMOUNT),
     );
+    // the additional rewards are forwarded by the proxy
+    b_mock.check_esdt_balance(&user_addr, PARTNER_TOKEN_ID, &rust_biguint!(10_000));
 
     // user exit farm
     b_mock.set_block_nonce(25);
@@ -6,9 +6,9 @@
 
 // Init:                                 1
 // Upgrade:                              1
-// Endpoints:                           65
+// Endpoints:                           84
 // Async Callback:                       1
-// Total number of exported functions:  68
+// Total number of exported functions:  87
 
 #![no_std]
 
@@ -21,6 +21,7 @@ multiversx_sc_wasm_adapter::endpoints! {
         init => init
         upgrade => upgrade
         enterFarm => enter_farm_endpoint
+        enterFarmLocked => enter_farm_locked_endpoint
         claimRewards => claim_rewards_endpoint
         compoundRewards => compound_rewards_endpoint
         exitFarm => exit_farm_endpoint
@@ -29,13 +30,20 @@ multiversx_sc_wasm_adapter::endpoints! {
         startProduceRewards => start_produce_rewards_endpoint
         endProduceRewards => end_produce_rewards_endpoint
         setPerBlockRewardAmount => set_per_block_rewards_endpoint
+        migrateToPerSecondRewards => migrate_to_per_second_rewards_endpoint
+        setPerSecondRewardAmount => set_per_second_rewards_endpoint
+        setEmissionSchedule => set_emission_schedule_endpoint
         setBoostedYieldsRewardsPercentage => set_boosted_yields_rewards_percentage
         calculateRewardsForGivenPosition => calculate_rewards_for_given_position
+        getUpcomingEmissionSchedule => get_upcoming_emission_schedule
+        getProjectedEmissions => get_projected_emissions
         getRewardPerShare => reward_per_share
         getRewardReserve => reward_reserve
         getFarmingTokenId => farming_token_id
         getRewardTokenId => reward_token_id
         getPerBlockRewardAmount => per_block_reward_amount
+        getPerSecondRewardAmount => per_second_reward_amount
+        getLastRewardTimestamp => last_reward_timestamp
         getLastRewardBlockNonce => last_reward_block_nonce
         getDivisionSafetyConstant => division_safety_constant
         getUserTotalFarmPosition => user_total_farm_position
@@ -57,6 +65,11 @@ multiversx_sc_wasm_adapter::endpoints! {
         addSCAddressToWhitelist => add_sc_address_to_whitelist
         removeSCAddressFromWhitelist => remove_sc_address_from_whitelist
         isSCAddressWhitelisted => is_sc_address_whitelisted
+        addAdditionalRewardToken => add_additional_reward_token
+        depositAdditionalRewards => deposit_additional_rewards
+        getAdditionalRewardsInfo => get_additional_rewards_info
+        calculateAdditionalRewardsForGivenPosition => calculate_additional_rewards_for_given_position
+        getAdditionalRewardTokens => additional_reward_tokens
         set_penalty_percent => set_penalty_percent
         set_minimum_farming_epochs => set_minimum_farming_epochs
         set_burn_gas_limit => set_burn_gas_limit
@@ -64,6 +77,12 @@ multiversx_sc_wasm_adapter::endpoints! {
         getMinimumFarmingEpoch => minimum_farming_epochs
         getBurnGasLimit => burn_gas_limit
         getPairContractManagedAddress => pair_contract_address
+        setLockOptions => set_lock_options
+        setEarlyUnlockPenaltyPercent => set_early_unlock_penalty_percent
+        getLockOptions => get_lock_options
+        getEarlyUnlockPenaltyPercent => early_unlock_penalty_percent
+        getPositionLock => position_lock
+        getTotalLockBoost => total_lock_boost
         enterFarmOnBehalf => enter_farm_on_behalf
         claimRewardsOnBehalf => claim_rewards_on_behalf
         getBoostedYieldsRewardsPercentage => boosted_yields_rewards_percentage
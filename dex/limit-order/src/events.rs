@@ -0,0 +1,130 @@
+use crate::orders::{Order, OrderId};
+
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+#[derive(TypeAbi, TopEncode)]
+pub struct CreateOrderEvent<M: ManagedTypeApi> {
+    order: Order<M>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct FillOrderEvent<M: ManagedTypeApi> {
+    keeper: ManagedAddress<M>,
+    amount_in: BigUint<M>,
+    amount_out: BigUint<M>,
+    keeper_reward: BigUint<M>,
+    price: BigUint<M>,
+    remaining_amount: BigUint<M>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct CancelOrderEvent<M: ManagedTypeApi> {
+    caller: ManagedAddress<M>,
+    refunded_amount: BigUint<M>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[multiversx_sc::module]
+pub trait EventsModule {
+    fn emit_create_order_event(&self, order: Order<Self::Api>) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.create_order_event(
+            order.creator.clone(),
+            order.id,
+            order.pair_address.clone(),
+            epoch,
+            CreateOrderEvent {
+                order,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_fill_order_event(
+        &self,
+        order: &Order<Self::Api>,
+        amount_in: BigUint,
+        amount_out: BigUint,
+        keeper_reward: BigUint,
+        price: BigUint,
+    ) {
+        let keeper = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.fill_order_event(
+            order.creator.clone(),
+            order.id,
+            order.pair_address.clone(),
+            epoch,
+            FillOrderEvent {
+                keeper,
+                amount_in,
+                amount_out,
+                keeper_reward,
+                price,
+                remaining_amount: order.remaining_amount.clone(),
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_cancel_order_event(&self, order: &Order<Self::Api>) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.cancel_order_event(
+            order.creator.clone(),
+            order.id,
+            order.pair_address.clone(),
+            epoch,
+            CancelOrderEvent {
+                caller,
+                refunded_amount: order.remaining_amount.clone(),
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    #[event("createOrder")]
+    fn create_order_event(
+        &self,
+        #[indexed] creator: ManagedAddress,
+        #[indexed] order_id: OrderId,
+        #[indexed] pair_address: ManagedAddress,
+        #[indexed] epoch: u64,
+        create_order_event: CreateOrderEvent<Self::Api>,
+    );
+
+    #[event("fillOrder")]
+    fn fill_order_event(
+        &self,
+        #[indexed] creator: ManagedAddress,
+        #[indexed] order_id: OrderId,
+        #[indexed] pair_address: ManagedAddress,
+        #[indexed] epoch: u64,
+        fill_order_event: FillOrderEvent<Self::Api>,
+    );
+
+    #[event("cancelOrder")]
+    fn cancel_order_event(
+        &self,
+        #[indexed] creator: ManagedAddress,
+        #[indexed] order_id: OrderId,
+        #[indexed] pair_address: ManagedAddress,
+        #[indexed] epoch: u64,
+        cancel_order_event: CancelOrderEvent<Self::Api>,
+    );
+}
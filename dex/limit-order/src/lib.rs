@@ -0,0 +1,300 @@
+#![no_std]
+
+multiversx_sc::imports!();
+
+pub mod events;
+pub mod orders;
+
+use orders::{Order, OrderId, PRICE_PRECISION};
+use pair::{
+    pair_actions::swap::ProxyTrait as _, read_pair_storage, safe_price_view::ProxyTrait as _,
+};
+use pausable::State;
+
+pub const MAX_PERCENTAGE: u64 = 10_000;
+pub const MAX_KEEPER_REWARD_PERCENT: u64 = 500;
+
+/// Escrows the input tokens of limit orders, which anyone can fill through the pair
+/// once the price of the pair reaches the price of the order.
+#[multiversx_sc::contract]
+pub trait LimitOrder:
+    orders::OrdersModule
+    + events::EventsModule
+    + read_pair_storage::ReadPairStorageModule
+    + multiversx_sc_modules::pause::PauseModule
+{
+    #[init]
+    fn init(&self, keeper_reward_percent: u64) {
+        self.set_keeper_reward_percent(keeper_reward_percent);
+    }
+
+    #[upgrade]
+    fn upgrade(&self) {}
+
+    /// The keepers receive this part of the swap output, in basis points, for filling orders.
+    #[only_owner]
+    #[endpoint(setKeeperRewardPercent)]
+    fn set_keeper_reward_percent(&self, keeper_reward_percent: u64) {
+        require!(
+            keeper_reward_percent <= MAX_KEEPER_REWARD_PERCENT,
+            "Keeper reward percent too high"
+        );
+
+        self.keeper_reward_percent().set(keeper_reward_percent);
+    }
+
+    #[only_owner]
+    #[endpoint(addPairs)]
+    fn add_pairs(&self, pair_addresses: MultiValueEncoded<ManagedAddress>) {
+        let mut pairs = self.pairs();
+        for pair_address in pair_addresses {
+            require!(
+                self.blockchain().is_smart_contract(&pair_address),
+                "Invalid pair address"
+            );
+
+            let _ = pairs.insert(pair_address);
+        }
+    }
+
+    /// Existing orders on the removed pairs can still be cancelled, but not filled.
+    #[only_owner]
+    #[endpoint(removePairs)]
+    fn remove_pairs(&self, pair_addresses: MultiValueEncoded<ManagedAddress>) {
+        let mut pairs = self.pairs();
+        for pair_address in pair_addresses {
+            let _ = pairs.swap_remove(&pair_address);
+        }
+    }
+
+    /// The contract used to query the safe price of the pairs, for the orders
+    /// that use it as trigger.
+    #[only_owner]
+    #[endpoint(setSafePriceViewAddress)]
+    fn set_safe_price_view_address(&self, safe_price_view_address: ManagedAddress) {
+        require!(
+            self.blockchain()
+                .is_smart_contract(&safe_price_view_address),
+            "Invalid safe price view address"
+        );
+
+        self.safe_price_view_address().set(safe_price_view_address);
+    }
+
+    /// Escrows the payment until the order is filled, cancelled or expired.
+    ///
+    /// The order can be filled once the spot price of the pair, or its safe price if
+    /// `use_safe_price` is set, is at least `min_price`, i.e. the amount of `output_token_id`
+    /// for one unit of the payment, scaled by 10^18. Regardless of the trigger, each fill
+    /// has to give the creator at least `min_price` for the swapped amount, after the
+    /// keeper reward.
+    #[payable("*")]
+    #[endpoint(createOrder)]
+    fn create_order(
+        &self,
+        pair_address: ManagedAddress,
+        output_token_id: TokenIdentifier,
+        min_price: BigUint,
+        expiry_timestamp: u64,
+        use_safe_price: bool,
+    ) -> OrderId {
+        self.require_not_paused();
+        require!(self.pairs().contains(&pair_address), "Unknown pair");
+
+        let payment = self.call_value().single_esdt();
+        require!(payment.token_nonce == 0, "Invalid payment token");
+        require!(payment.amount > 0u64, "Invalid payment amount");
+        require!(min_price > 0u64, "Invalid price");
+        require!(
+            expiry_timestamp > self.blockchain().get_block_timestamp(),
+            "Invalid expiry"
+        );
+        require!(
+            !use_safe_price || !self.safe_price_view_address().is_empty(),
+            "Safe price view address not set"
+        );
+
+        let first_token_id = self.get_first_token_id_mapper(pair_address.clone()).get();
+        let second_token_id = self.get_second_token_id_mapper(pair_address.clone()).get();
+        let is_first_to_second =
+            payment.token_identifier == first_token_id && output_token_id == second_token_id;
+        let is_second_to_first =
+            payment.token_identifier == second_token_id && output_token_id == first_token_id;
+        require!(
+            is_first_to_second || is_second_to_first,
+            "Tokens do not match the pair"
+        );
+
+        let order_id = self.last_order_id().update(|id| {
+            *id += 1;
+            *id
+        });
+        let order = Order {
+            id: order_id,
+            creator: self.blockchain().get_caller(),
+            pair_address,
+            input_token_id: payment.token_identifier,
+            initial_amount: payment.amount.clone(),
+            remaining_amount: payment.amount,
+            output_token_id,
+            min_price,
+            expiry_timestamp,
+            use_safe_price,
+        };
+        self.insert_order(&order);
+        self.emit_create_order_event(order);
+
+        order_id
+    }
+
+    /// Swaps at most `amount_in` of the remaining input of the order through the pair.
+    /// The output is sent to the creator of the order, except for the keeper reward,
+    /// which is sent to the caller.
+    #[endpoint(fillOrder)]
+    fn fill_order(&self, order_id: OrderId, amount_in: BigUint) -> EsdtTokenPayment {
+        self.require_not_paused();
+        require!(amount_in > 0u64, "Invalid amount");
+
+        let mut order = self.get_order(order_id);
+        require!(
+            !order.is_expired(self.blockchain().get_block_timestamp()),
+            "Order expired"
+        );
+        require!(self.pairs().contains(&order.pair_address), "Unknown pair");
+        require!(
+            self.get_pair_state_mapper(order.pair_address.clone()).get() == State::Active,
+            "Pair not active"
+        );
+
+        let price = self.get_trigger_price(&order);
+        require!(price >= order.min_price, "Price not reached");
+
+        let fill_amount = core::cmp::min(amount_in, order.remaining_amount.clone());
+        let min_amount_out = order.get_min_amount_out(&fill_amount);
+        let output_payment: EsdtTokenPayment = self
+            .pair_proxy(order.pair_address.clone())
+            .swap_tokens_fixed_input(
+                order.output_token_id.clone(),
+                min_amount_out.clone(),
+                OptionalValue::<ManagedAddress>::None,
+            )
+            .with_esdt_transfer((order.input_token_id.clone(), 0, fill_amount.clone()))
+            .execute_on_dest_context();
+        require!(
+            output_payment.token_identifier == order.output_token_id,
+            "Invalid swap output token"
+        );
+
+        let keeper_reward =
+            &output_payment.amount * self.keeper_reward_percent().get() / MAX_PERCENTAGE;
+        let creator_amount = &output_payment.amount - &keeper_reward;
+        require!(
+            creator_amount >= min_amount_out,
+            "Price below the order limit"
+        );
+
+        order.remaining_amount -= &fill_amount;
+        if order.remaining_amount == 0u64 {
+            self.remove_order(&order);
+        } else {
+            self.orders(order_id).set(&order);
+        }
+
+        let keeper = self.blockchain().get_caller();
+        self.send()
+            .direct_esdt(&order.creator, &order.output_token_id, 0, &creator_amount);
+        let keeper_payment = EsdtTokenPayment::new(order.output_token_id.clone(), 0, keeper_reward);
+        if keeper_payment.amount > 0u64 {
+            self.send().direct_esdt(
+                &keeper,
+                &keeper_payment.token_identifier,
+                0,
+                &keeper_payment.amount,
+            );
+        }
+
+        self.emit_fill_order_event(
+            &order,
+            fill_amount,
+            creator_amount,
+            keeper_payment.amount.clone(),
+            price,
+        );
+
+        keeper_payment
+    }
+
+    /// The creator can cancel the order at any time. Once expired, anyone can cancel it.
+    /// In both cases, the remaining input is sent back to the creator.
+    #[endpoint(cancelOrder)]
+    fn cancel_order(&self, order_id: OrderId) -> EsdtTokenPayment {
+        let order = self.get_order(order_id);
+        let caller = self.blockchain().get_caller();
+        require!(
+            caller == order.creator || order.is_expired(self.blockchain().get_block_timestamp()),
+            "Only the creator can cancel the order before it expires"
+        );
+
+        self.remove_order(&order);
+        self.send().direct_esdt(
+            &order.creator,
+            &order.input_token_id,
+            0,
+            &order.remaining_amount,
+        );
+        self.emit_cancel_order_event(&order);
+
+        EsdtTokenPayment::new(order.input_token_id, 0, order.remaining_amount)
+    }
+
+    /// The price of the pair used as trigger by the order, scaled by 10^18.
+    #[view(getOrderTriggerPrice)]
+    fn get_order_trigger_price(&self, order_id: OrderId) -> BigUint {
+        let order = self.get_order(order_id);
+        self.get_trigger_price(&order)
+    }
+
+    fn get_trigger_price(&self, order: &Order<Self::Api>) -> BigUint {
+        if order.use_safe_price {
+            let safe_price_view_address = self.safe_price_view_address().get();
+            let input_payment = EsdtTokenPayment::new(
+                order.input_token_id.clone(),
+                0,
+                BigUint::from(PRICE_PRECISION),
+            );
+            let safe_price: EsdtTokenPayment = self
+                .pair_proxy(safe_price_view_address)
+                .get_safe_price_by_default_offset(order.pair_address.clone(), input_payment)
+                .execute_on_dest_context();
+
+            return safe_price.amount;
+        }
+
+        let reserve_in = self
+            .get_pair_reserve_mapper(order.pair_address.clone(), &order.input_token_id)
+            .get();
+        let reserve_out = self
+            .get_pair_reserve_mapper(order.pair_address.clone(), &order.output_token_id)
+            .get();
+        if reserve_in == 0u64 {
+            return BigUint::zero();
+        }
+
+        reserve_out * PRICE_PRECISION / reserve_in
+    }
+
+    #[proxy]
+    fn pair_proxy(&self, to: ManagedAddress) -> pair::Proxy<Self::Api>;
+
+    #[view(getPairs)]
+    #[storage_mapper("pairs")]
+    fn pairs(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[view(getKeeperRewardPercent)]
+    #[storage_mapper("keeperRewardPercent")]
+    fn keeper_reward_percent(&self) -> SingleValueMapper<u64>;
+
+    #[view(getSafePriceViewAddress)]
+    #[storage_mapper("safePriceViewAddress")]
+    fn safe_price_view_address(&self) -> SingleValueMapper<ManagedAddress>;
+}
@@ -0,0 +1,123 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+pub type OrderId = u64;
+
+pub const PRICE_PRECISION: u64 = 1_000_000_000_000_000_000;
+pub const MAX_ORDERS_PAGE_SIZE: usize = 100;
+
+/// The price is the minimum amount of the output token received for one unit of the
+/// input token, scaled by PRICE_PRECISION.
+#[derive(
+    ManagedVecItem,
+    TypeAbi,
+    TopEncode,
+    TopDecode,
+    NestedEncode,
+    NestedDecode,
+    Clone,
+    PartialEq,
+    Debug,
+)]
+pub struct Order<M: ManagedTypeApi> {
+    pub id: OrderId,
+    pub creator: ManagedAddress<M>,
+    pub pair_address: ManagedAddress<M>,
+    pub input_token_id: TokenIdentifier<M>,
+    pub initial_amount: BigUint<M>,
+    pub remaining_amount: BigUint<M>,
+    pub output_token_id: TokenIdentifier<M>,
+    pub min_price: BigUint<M>,
+    pub expiry_timestamp: u64,
+    pub use_safe_price: bool,
+}
+
+impl<M: ManagedTypeApi> Order<M> {
+    #[inline]
+    pub fn is_expired(&self, current_timestamp: u64) -> bool {
+        current_timestamp > self.expiry_timestamp
+    }
+
+    /// The output the creator should receive at least for `amount_in`, rounded up.
+    pub fn get_min_amount_out(&self, amount_in: &BigUint<M>) -> BigUint<M> {
+        let price_precision = BigUint::from(PRICE_PRECISION);
+        (amount_in * &self.min_price + &price_precision - 1u64) / price_precision
+    }
+}
+
+#[multiversx_sc::module]
+pub trait OrdersModule {
+    #[view(getOrder)]
+    fn get_order(&self, order_id: OrderId) -> Order<Self::Api> {
+        let order_mapper = self.orders(order_id);
+        require!(!order_mapper.is_empty(), "Order not found");
+
+        order_mapper.get()
+    }
+
+    /// Returns at most `count` active orders, starting from index `start`. The order of
+    /// the entries may change when orders are filled or cancelled.
+    #[view(getOrders)]
+    fn get_orders(&self, start: usize, count: usize) -> MultiValueEncoded<Order<Self::Api>> {
+        self.get_orders_page(&self.active_orders(), start, count)
+    }
+
+    #[view(getUserOrders)]
+    fn get_user_orders(
+        &self,
+        user: ManagedAddress,
+        start: usize,
+        count: usize,
+    ) -> MultiValueEncoded<Order<Self::Api>> {
+        self.get_orders_page(&self.user_orders(&user), start, count)
+    }
+
+    #[view(getOrdersCount)]
+    fn get_orders_count(&self) -> usize {
+        self.active_orders().len()
+    }
+
+    fn get_orders_page(
+        &self,
+        order_ids: &UnorderedSetMapper<OrderId>,
+        start: usize,
+        count: usize,
+    ) -> MultiValueEncoded<Order<Self::Api>> {
+        require!(count <= MAX_ORDERS_PAGE_SIZE, "Page size too big");
+
+        let mut orders = MultiValueEncoded::new();
+        let end = core::cmp::min(start.saturating_add(count), order_ids.len());
+        for index in start..end {
+            // the mapper indexes start from 1
+            let order_id = order_ids.get_by_index(index + 1);
+            orders.push(self.orders(order_id).get());
+        }
+
+        orders
+    }
+
+    fn insert_order(&self, order: &Order<Self::Api>) {
+        self.orders(order.id).set(order);
+        self.active_orders().insert(order.id);
+        self.user_orders(&order.creator).insert(order.id);
+    }
+
+    fn remove_order(&self, order: &Order<Self::Api>) {
+        self.orders(order.id).clear();
+        self.active_orders().swap_remove(&order.id);
+        self.user_orders(&order.creator).swap_remove(&order.id);
+    }
+
+    #[view(getLastOrderId)]
+    #[storage_mapper("lastOrderId")]
+    fn last_order_id(&self) -> SingleValueMapper<OrderId>;
+
+    #[storage_mapper("orders")]
+    fn orders(&self, order_id: OrderId) -> SingleValueMapper<Order<Self::Api>>;
+
+    #[storage_mapper("activeOrders")]
+    fn active_orders(&self) -> UnorderedSetMapper<OrderId>;
+
+    #[storage_mapper("userOrders")]
+    fn user_orders(&self, user: &ManagedAddress) -> UnorderedSetMapper<OrderId>;
+}
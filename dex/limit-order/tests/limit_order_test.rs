@@ -0,0 +1,505 @@
+#![allow(deprecated)]
+
+use limit_order::{orders::OrdersModule, LimitOrder};
+use multiversx_sc::codec::multi_types::OptionalValue;
+use multiversx_sc::types::{Address, EsdtLocalRole, ManagedAddress, MultiValueEncoded};
+use multiversx_sc_scenario::{
+    managed_address, managed_biguint, managed_token_id, rust_biguint,
+    whitebox_legacy::{BlockchainStateWrapper, ContractObjWrapper, TxTokenTransfer},
+    DebugApi,
+};
+use pair::{
+    config::ConfigModule,
+    pair_actions::{add_liq::AddLiquidityModule, swap::SwapModule},
+    Pair,
+};
+use pausable::{PausableModule, State};
+
+const LIMIT_ORDER_WASM_PATH: &str = "limit-order/output/limit-order.wasm";
+const PAIR_WASM_PATH: &str = "pair/output/pair.wasm";
+
+const WEGLD_TOKEN_ID: &[u8] = b"WEGLD-abcdef";
+const MEX_TOKEN_ID: &[u8] = b"MEX-abcdef";
+const LP_TOKEN_ID: &[u8] = b"LPTOK-abcdef";
+
+const INITIAL_RESERVE: u64 = 1_000_000_000;
+const USER_BALANCE: u64 = 100_000;
+const KEEPER_REWARD_PERCENT: u64 = 100;
+
+// 1.02 MEX for each WEGLD
+const MIN_PRICE: u64 = 1_020_000_000_000_000_000;
+const HIGH_MIN_PRICE: u64 = 1_200_000_000_000_000_000;
+
+struct LimitOrderSetup<LimitOrderObjBuilder, PairObjBuilder>
+where
+    LimitOrderObjBuilder: 'static + Copy + Fn() -> limit_order::ContractObj<DebugApi>,
+    PairObjBuilder: 'static + Copy + Fn() -> pair::ContractObj<DebugApi>,
+{
+    b_mock: BlockchainStateWrapper,
+    owner_address: Address,
+    user_address: Address,
+    keeper_address: Address,
+    limit_order_wrapper:
+        ContractObjWrapper<limit_order::ContractObj<DebugApi>, LimitOrderObjBuilder>,
+    pair_wrapper: ContractObjWrapper<pair::ContractObj<DebugApi>, PairObjBuilder>,
+}
+
+impl<LimitOrderObjBuilder, PairObjBuilder> LimitOrderSetup<LimitOrderObjBuilder, PairObjBuilder>
+where
+    LimitOrderObjBuilder: 'static + Copy + Fn() -> limit_order::ContractObj<DebugApi>,
+    PairObjBuilder: 'static + Copy + Fn() -> pair::ContractObj<DebugApi>,
+{
+    fn new(limit_order_builder: LimitOrderObjBuilder, pair_builder: PairObjBuilder) -> Self {
+        let rust_zero = rust_biguint!(0);
+        let mut b_mock = BlockchainStateWrapper::new();
+        let owner_address = b_mock.create_user_account(&rust_zero);
+        let user_address = b_mock.create_user_account(&rust_zero);
+        let keeper_address = b_mock.create_user_account(&rust_zero);
+
+        let pair_wrapper = b_mock.create_sc_account(
+            &rust_zero,
+            Some(&owner_address),
+            pair_builder,
+            PAIR_WASM_PATH,
+        );
+        b_mock
+            .execute_tx(&owner_address, &pair_wrapper, &rust_zero, |sc| {
+                sc.init(
+                    managed_token_id!(WEGLD_TOKEN_ID),
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_address!(&owner_address),
+                    managed_address!(&owner_address),
+                    300,
+                    50,
+                    ManagedAddress::zero(),
+                    MultiValueEncoded::new(),
+                );
+                sc.lp_token_identifier()
+                    .set(&managed_token_id!(LP_TOKEN_ID));
+                sc.state().set(State::Active);
+            })
+            .assert_ok();
+        b_mock.set_esdt_local_roles(
+            pair_wrapper.address_ref(),
+            LP_TOKEN_ID,
+            &[EsdtLocalRole::Mint, EsdtLocalRole::Burn],
+        );
+
+        b_mock.set_esdt_balance(
+            &owner_address,
+            WEGLD_TOKEN_ID,
+            &rust_biguint!(INITIAL_RESERVE),
+        );
+        b_mock.set_esdt_balance(
+            &owner_address,
+            MEX_TOKEN_ID,
+            &rust_biguint!(INITIAL_RESERVE),
+        );
+        let payments = vec![
+            TxTokenTransfer {
+                token_identifier: WEGLD_TOKEN_ID.to_vec(),
+                nonce: 0,
+                value: rust_biguint!(INITIAL_RESERVE),
+            },
+            TxTokenTransfer {
+                token_identifier: MEX_TOKEN_ID.to_vec(),
+                nonce: 0,
+                value: rust_biguint!(INITIAL_RESERVE),
+            },
+        ];
+        b_mock
+            .execute_esdt_multi_transfer(&owner_address, &pair_wrapper, &payments, |sc| {
+                let _ = sc.add_liquidity(managed_biguint!(1), managed_biguint!(1));
+            })
+            .assert_ok();
+
+        let limit_order_wrapper = b_mock.create_sc_account(
+            &rust_zero,
+            Some(&owner_address),
+            limit_order_builder,
+            LIMIT_ORDER_WASM_PATH,
+        );
+        b_mock
+            .execute_tx(&owner_address, &limit_order_wrapper, &rust_zero, |sc| {
+                sc.init(KEEPER_REWARD_PERCENT);
+
+                let mut pairs = MultiValueEncoded::new();
+                pairs.push(managed_address!(pair_wrapper.address_ref()));
+                sc.add_pairs(pairs);
+            })
+            .assert_ok();
+
+        b_mock.set_esdt_balance(&user_address, WEGLD_TOKEN_ID, &rust_biguint!(USER_BALANCE));
+
+        LimitOrderSetup {
+            b_mock,
+            owner_address,
+            user_address,
+            keeper_address,
+            limit_order_wrapper,
+            pair_wrapper,
+        }
+    }
+
+    fn create_order(
+        &mut self,
+        amount: u64,
+        min_price: u64,
+        expiry: u64,
+        use_safe_price: bool,
+    ) -> u64 {
+        let pair_address = self.pair_wrapper.address_ref().clone();
+        let mut order_id = 0;
+        self.b_mock
+            .execute_esdt_transfer(
+                &self.user_address,
+                &self.limit_order_wrapper,
+                WEGLD_TOKEN_ID,
+                0,
+                &rust_biguint!(amount),
+                |sc| {
+                    order_id = sc.create_order(
+                        managed_address!(&pair_address),
+                        managed_token_id!(MEX_TOKEN_ID),
+                        managed_biguint!(min_price),
+                        expiry,
+                        use_safe_price,
+                    );
+                },
+            )
+            .assert_ok();
+
+        order_id
+    }
+
+    // sells MEX for WEGLD, which raises the WEGLD price
+    fn move_price(&mut self, mex_amount: u64) {
+        let trader_address = self.b_mock.create_user_account(&rust_biguint!(0));
+        self.b_mock
+            .set_esdt_balance(&trader_address, MEX_TOKEN_ID, &rust_biguint!(mex_amount));
+        self.b_mock
+            .execute_esdt_transfer(
+                &trader_address,
+                &self.pair_wrapper,
+                MEX_TOKEN_ID,
+                0,
+                &rust_biguint!(mex_amount),
+                |sc| {
+                    let _ = sc.swap_tokens_fixed_input(
+                        managed_token_id!(WEGLD_TOKEN_ID),
+                        managed_biguint!(1),
+                        OptionalValue::None,
+                    );
+                },
+            )
+            .assert_ok();
+    }
+}
+
+#[test]
+fn limit_order_fill_and_cancel_test() {
+    let mut setup = LimitOrderSetup::new(limit_order::contract_obj, pair::contract_obj);
+    let user_address = setup.user_address.clone();
+    let keeper_address = setup.keeper_address.clone();
+
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &user_address,
+            &setup.limit_order_wrapper,
+            WEGLD_TOKEN_ID,
+            0,
+            &rust_biguint!(10_000),
+            |sc| {
+                let _ = sc.create_order(
+                    managed_address!(&keeper_address),
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_biguint!(MIN_PRICE),
+                    100,
+                    false,
+                );
+            },
+        )
+        .assert_user_error("Unknown pair");
+
+    let order_id = setup.create_order(10_000, MIN_PRICE, 100, false);
+    assert_eq!(order_id, 1);
+    setup.b_mock.check_esdt_balance(
+        &user_address,
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(USER_BALANCE - 10_000),
+    );
+
+    // the spot price is 1
+    setup
+        .b_mock
+        .execute_tx(
+            &keeper_address,
+            &setup.limit_order_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.fill_order(order_id, managed_biguint!(4_000));
+            },
+        )
+        .assert_user_error("Price not reached");
+
+    setup.move_price(50_000_000);
+
+    setup
+        .b_mock
+        .execute_tx(
+            &keeper_address,
+            &setup.limit_order_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let keeper_reward = sc.fill_order(order_id, managed_biguint!(4_000));
+                assert_eq!(keeper_reward.amount, managed_biguint!(43));
+            },
+        )
+        .assert_ok();
+    setup
+        .b_mock
+        .check_esdt_balance(&user_address, MEX_TOKEN_ID, &rust_biguint!(4_353));
+    setup
+        .b_mock
+        .check_esdt_balance(&keeper_address, MEX_TOKEN_ID, &rust_biguint!(43));
+
+    setup
+        .b_mock
+        .execute_query(&setup.limit_order_wrapper, |sc| {
+            assert_eq!(sc.get_orders_count(), 1);
+
+            let orders = sc
+                .get_user_orders(managed_address!(&user_address), 0, 10)
+                .to_vec();
+            assert_eq!(orders.len(), 1);
+            let order = orders.get(0);
+            assert_eq!(order.id, order_id);
+            assert_eq!(order.initial_amount, managed_biguint!(10_000));
+            assert_eq!(order.remaining_amount, managed_biguint!(6_000));
+
+            assert_eq!(sc.get_orders(1, 10).to_vec().len(), 0);
+        })
+        .assert_ok();
+
+    // only the creator can cancel the order before it expires
+    setup
+        .b_mock
+        .execute_tx(
+            &keeper_address,
+            &setup.limit_order_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.cancel_order(order_id);
+            },
+        )
+        .assert_user_error("Only the creator can cancel the order before it expires");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &user_address,
+            &setup.limit_order_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let refund = sc.cancel_order(order_id);
+                assert_eq!(refund.amount, managed_biguint!(6_000));
+            },
+        )
+        .assert_ok();
+    setup.b_mock.check_esdt_balance(
+        &user_address,
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(USER_BALANCE - 4_000),
+    );
+
+    setup
+        .b_mock
+        .execute_query(&setup.limit_order_wrapper, |sc| {
+            assert_eq!(sc.get_orders_count(), 0);
+        })
+        .assert_ok();
+}
+
+#[test]
+fn limit_order_full_fill_and_expiry_test() {
+    let mut setup = LimitOrderSetup::new(limit_order::contract_obj, pair::contract_obj);
+    let user_address = setup.user_address.clone();
+    let keeper_address = setup.keeper_address.clone();
+
+    setup.move_price(50_000_000);
+    let first_order_id = setup.create_order(1_000, MIN_PRICE, 100, false);
+    let second_order_id = setup.create_order(1_000, MIN_PRICE, 100, false);
+
+    // the amount is capped to the remaining input
+    setup
+        .b_mock
+        .execute_tx(
+            &keeper_address,
+            &setup.limit_order_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.fill_order(first_order_id, managed_biguint!(5_000));
+            },
+        )
+        .assert_ok();
+    setup.b_mock.check_esdt_balance(
+        &user_address,
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(USER_BALANCE - 2_000),
+    );
+
+    setup.b_mock.set_block_timestamp(101);
+    setup
+        .b_mock
+        .execute_tx(
+            &keeper_address,
+            &setup.limit_order_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.fill_order(second_order_id, managed_biguint!(1_000));
+            },
+        )
+        .assert_user_error("Order expired");
+
+    // anyone can cancel an expired order, the input goes back to the creator
+    setup
+        .b_mock
+        .execute_tx(
+            &keeper_address,
+            &setup.limit_order_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.cancel_order(second_order_id);
+            },
+        )
+        .assert_ok();
+    setup.b_mock.check_esdt_balance(
+        &user_address,
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(USER_BALANCE - 1_000),
+    );
+
+    setup
+        .b_mock
+        .execute_query(&setup.limit_order_wrapper, |sc| {
+            assert_eq!(sc.get_orders_count(), 0);
+            assert_eq!(sc.last_order_id().get(), 2);
+        })
+        .assert_ok();
+}
+
+#[test]
+fn limit_order_price_limit_test() {
+    let mut setup = LimitOrderSetup::new(limit_order::contract_obj, pair::contract_obj);
+    let keeper_address = setup.keeper_address.clone();
+
+    setup.b_mock.set_esdt_balance(
+        &setup.user_address.clone(),
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(INITIAL_RESERVE),
+    );
+    setup.b_mock.set_block_round(5);
+    setup.move_price(50_000_000);
+
+    // the price is reached, but swapping the whole order would move it below the limit
+    let order_id = setup.create_order(100_000_000, MIN_PRICE, 100, false);
+    setup
+        .b_mock
+        .execute_tx(
+            &keeper_address,
+            &setup.limit_order_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.fill_order(order_id, managed_biguint!(100_000_000));
+            },
+        )
+        .assert_user_error("Slippage exceeded");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &keeper_address,
+            &setup.limit_order_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.fill_order(order_id, managed_biguint!(1_000_000));
+            },
+        )
+        .assert_ok();
+
+    // safe price orders need the view contract
+    let owner_address = setup.owner_address.clone();
+    setup
+        .b_mock
+        .execute_esdt_transfer(
+            &setup.user_address.clone(),
+            &setup.limit_order_wrapper,
+            WEGLD_TOKEN_ID,
+            0,
+            &rust_biguint!(1_000),
+            |sc| {
+                let _ = sc.create_order(
+                    managed_address!(setup.pair_wrapper.address_ref()),
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_biguint!(MIN_PRICE),
+                    100,
+                    true,
+                );
+            },
+        )
+        .assert_user_error("Safe price view address not set");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &owner_address,
+            &setup.limit_order_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_keeper_reward_percent(1_000);
+            },
+        )
+        .assert_user_error("Keeper reward percent too high");
+
+    setup
+        .b_mock
+        .execute_tx(
+            &owner_address,
+            &setup.limit_order_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_safe_price_view_address(managed_address!(setup.pair_wrapper.address_ref()));
+            },
+        )
+        .assert_ok();
+
+    // the safe price follows the price spikes slowly
+    setup.b_mock.set_block_round(100);
+    setup.move_price(100_000_000);
+
+    let safe_price_order_id = setup.create_order(1_000, HIGH_MIN_PRICE, 100, true);
+    let spot_price_order_id = setup.create_order(1_000, HIGH_MIN_PRICE, 100, false);
+    setup
+        .b_mock
+        .execute_tx(
+            &keeper_address,
+            &setup.limit_order_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.fill_order(safe_price_order_id, managed_biguint!(1_000));
+            },
+        )
+        .assert_user_error("Price not reached");
+    setup
+        .b_mock
+        .execute_tx(
+            &keeper_address,
+            &setup.limit_order_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.fill_order(spot_price_order_id, managed_biguint!(1_000));
+            },
+        )
+        .assert_ok();
+}
@@ -0,0 +1,45 @@
+// Code generated by the multiversx-sc build system. DO NOT EDIT.
+
+////////////////////////////////////////////////////
+////////////////// AUTO-GENERATED //////////////////
+////////////////////////////////////////////////////
+
+// Init:                                 1
+// Upgrade:                              1
+// Endpoints:                           19
+// Async Callback (empty):               1
+// Total number of exported functions:  22
+
+#![no_std]
+
+multiversx_sc_wasm_adapter::allocator!();
+multiversx_sc_wasm_adapter::panic_handler!();
+
+multiversx_sc_wasm_adapter::endpoints! {
+    limit_order
+    (
+        init => init
+        upgrade => upgrade
+        setKeeperRewardPercent => set_keeper_reward_percent
+        addPairs => add_pairs
+        removePairs => remove_pairs
+        setSafePriceViewAddress => set_safe_price_view_address
+        createOrder => create_order
+        fillOrder => fill_order
+        cancelOrder => cancel_order
+        getOrderTriggerPrice => get_order_trigger_price
+        getPairs => pairs
+        getKeeperRewardPercent => keeper_reward_percent
+        getSafePriceViewAddress => safe_price_view_address
+        getOrder => get_order
+        getOrders => get_orders
+        getUserOrders => get_user_orders
+        getOrdersCount => get_orders_count
+        getLastOrderId => last_order_id
+        pause => pause_endpoint
+        unpause => unpause_endpoint
+        isPaused => paused_status
+    )
+}
+
+multiversx_sc_wasm_adapter::async_callback_empty! {}
@@ -2,12 +2,15 @@ multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
 use crate::config::MAX_PERCENTAGE;
+use crate::contexts::base::SwapTokensOrder;
 
 use super::config;
+use super::stable_swap;
 
 #[multiversx_sc::module]
 pub trait AmmModule:
     config::ConfigModule
+    + stable_swap::StableSwapModule
     + token_send::TokenSendModule
     + permissions_module::PermissionsModule
     + pausable::PausableModule
@@ -17,6 +20,15 @@ pub trait AmmModule:
         first_token_amount: &BigUint,
         second_token_amount: &BigUint,
     ) -> BigUint {
+        if self.is_stable_swap() {
+            let amplification = self.get_current_amplification();
+            return self.get_stable_invariant(
+                first_token_amount,
+                second_token_amount,
+                amplification,
+            );
+        }
+
         first_token_amount * second_token_amount
     }
 
@@ -34,7 +46,17 @@ pub trait AmmModule:
         amount_in: &BigUint,
         reserve_in: &BigUint,
         reserve_out: &BigUint,
+        swap_tokens_order: SwapTokensOrder,
     ) -> BigUint {
+        if self.is_stable_swap() {
+            return self.get_stable_amount_out(
+                amount_in,
+                reserve_in,
+                reserve_out,
+                swap_tokens_order,
+            );
+        }
+
         let numerator = amount_in * reserve_out;
         let denominator = reserve_in + amount_in;
 
@@ -46,8 +68,36 @@ pub trait AmmModule:
         amount_in: &BigUint,
         reserve_in: &BigUint,
         reserve_out: &BigUint,
+        swap_tokens_order: SwapTokensOrder,
     ) -> BigUint {
-        let amount_in_with_fee = amount_in * (MAX_PERCENTAGE - self.total_fee_percent().get());
+        self.get_amount_out_with_fee_percent(
+            amount_in,
+            reserve_in,
+            reserve_out,
+            swap_tokens_order,
+            self.get_total_fee_percent(),
+        )
+    }
+
+    fn get_amount_out_with_fee_percent(
+        &self,
+        amount_in: &BigUint,
+        reserve_in: &BigUint,
+        reserve_out: &BigUint,
+        swap_tokens_order: SwapTokensOrder,
+        total_fee_percent: u64,
+    ) -> BigUint {
+        let amount_in_with_fee = amount_in * (MAX_PERCENTAGE - total_fee_percent);
+        if self.is_stable_swap() {
+            let amount_in_after_fee = amount_in_with_fee / MAX_PERCENTAGE;
+            return self.get_stable_amount_out(
+                &amount_in_after_fee,
+                reserve_in,
+                reserve_out,
+                swap_tokens_order,
+            );
+        }
+
         let numerator = &amount_in_with_fee * reserve_out;
         let denominator = (reserve_in * MAX_PERCENTAGE) + amount_in_with_fee;
 
@@ -59,15 +109,45 @@ pub trait AmmModule:
         amount_out: &BigUint,
         reserve_in: &BigUint,
         reserve_out: &BigUint,
+        swap_tokens_order: SwapTokensOrder,
     ) -> BigUint {
+        self.get_amount_in_with_fee_percent(
+            amount_out,
+            reserve_in,
+            reserve_out,
+            swap_tokens_order,
+            self.get_total_fee_percent(),
+        )
+    }
+
+    fn get_amount_in_with_fee_percent(
+        &self,
+        amount_out: &BigUint,
+        reserve_in: &BigUint,
+        reserve_out: &BigUint,
+        swap_tokens_order: SwapTokensOrder,
+        total_fee_percent: u64,
+    ) -> BigUint {
+        if self.is_stable_swap() {
+            let amount_in_after_fee =
+                self.get_stable_amount_in(amount_out, reserve_in, reserve_out, swap_tokens_order);
+            let numerator = amount_in_after_fee * MAX_PERCENTAGE;
+            let denominator = MAX_PERCENTAGE - total_fee_percent;
+
+            return (numerator / denominator) + 1u64;
+        }
+
         let numerator = reserve_in * amount_out * MAX_PERCENTAGE;
-        let denominator =
-            (reserve_out - amount_out) * (MAX_PERCENTAGE - self.total_fee_percent().get());
+        let denominator = (reserve_out - amount_out) * (MAX_PERCENTAGE - total_fee_percent);
 
         (numerator / denominator) + 1u64
     }
 
     fn get_special_fee_from_input(&self, amount_in: &BigUint) -> BigUint {
-        amount_in * self.special_fee_percent().get() / MAX_PERCENTAGE
+        self.get_fee_from_input(amount_in, self.special_fee_percent().get())
+    }
+
+    fn get_fee_from_input(&self, amount_in: &BigUint, fee_percent: u64) -> BigUint {
+        amount_in * fee_percent / MAX_PERCENTAGE
     }
 }
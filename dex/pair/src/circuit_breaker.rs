@@ -0,0 +1,153 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use pausable::State;
+
+use crate::{
+    amm,
+    config::{self, MAX_PERCENTAGE},
+    contexts::base::StorageCache,
+    errors::*,
+    events, read_pair_storage,
+    safe_price::{self, Round},
+    safe_price_view, stable_swap,
+};
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Debug)]
+pub struct CircuitBreakerConfig {
+    pub max_price_deviation_percent: u64,
+    pub round_offset: Round,
+    pub pause_swaps_on_trip: bool,
+}
+
+#[multiversx_sc::module]
+pub trait CircuitBreakerModule:
+    safe_price_view::SafePriceViewModule
+    + safe_price::SafePriceModule
+    + events::EventsModule
+    + config::ConfigModule
+    + token_send::TokenSendModule
+    + amm::AmmModule
+    + stable_swap::StableSwapModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+    + read_pair_storage::ReadPairStorageModule
+{
+    /// Rejects the actions that move the spot price further away from the safe price over
+    /// the last `round_offset` rounds, and leave it more than `max_price_deviation_percent` away.
+    /// Removing liquidity keeps the reserves ratio, so it is never rejected.
+    ///
+    /// If `pause_swaps_on_trip` is set, a swap that trips the breaker is not executed, its payment
+    /// is returned, and the pair is switched to the ActiveNoSwaps state.
+    /// Liquidity actions and flash swaps are always rejected.
+    #[endpoint(setCircuitBreakerConfig)]
+    fn set_circuit_breaker_config(
+        &self,
+        max_price_deviation_percent: u64,
+        round_offset: Round,
+        pause_swaps_on_trip: bool,
+    ) {
+        self.require_caller_has_owner_or_admin_permissions();
+        require!(
+            max_price_deviation_percent > 0 && max_price_deviation_percent < MAX_PERCENTAGE,
+            ERROR_BAD_PERCENTS
+        );
+        require!(round_offset > 0, ERROR_INVALID_ARGS);
+
+        self.circuit_breaker_config().set(CircuitBreakerConfig {
+            max_price_deviation_percent,
+            round_offset,
+            pause_swaps_on_trip,
+        });
+    }
+
+    #[endpoint(disableCircuitBreaker)]
+    fn disable_circuit_breaker(&self) {
+        self.require_caller_has_owner_or_admin_permissions();
+        self.circuit_breaker_config().clear();
+    }
+
+    /// Has to be called with the reserves from before the action,
+    /// and the storage cache holding the reserves resulting from it.
+    fn check_circuit_breaker(
+        &self,
+        first_reserve_before: &BigUint,
+        second_reserve_before: &BigUint,
+        storage_cache: &StorageCache<Self>,
+    ) {
+        let opt_trip = self.get_circuit_breaker_trip(
+            first_reserve_before,
+            second_reserve_before,
+            storage_cache,
+        );
+        require!(opt_trip.is_none(), ERROR_CIRCUIT_BREAKER_TRIPPED);
+    }
+
+    /// Same as check_circuit_breaker, but if swaps are paused on trip, returns the price
+    /// deviation instead of failing. The swap then has to be cancelled, and swaps paused
+    /// through pause_swaps_after_trip.
+    fn check_swap_circuit_breaker(
+        &self,
+        first_reserve_before: &BigUint,
+        second_reserve_before: &BigUint,
+        storage_cache: &StorageCache<Self>,
+    ) -> Option<u64> {
+        let (price_deviation_percent, config) = self.get_circuit_breaker_trip(
+            first_reserve_before,
+            second_reserve_before,
+            storage_cache,
+        )?;
+        require!(config.pause_swaps_on_trip, ERROR_CIRCUIT_BREAKER_TRIPPED);
+
+        Some(price_deviation_percent)
+    }
+
+    fn pause_swaps_after_trip(
+        &self,
+        storage_cache: &StorageCache<Self>,
+        price_deviation_percent: u64,
+    ) {
+        self.state().set(State::PartialActive);
+        self.emit_circuit_breaker_event(storage_cache, price_deviation_percent);
+    }
+
+    /// The breaker trips if the deviation after the action is above the max one,
+    /// and higher than the deviation before it.
+    fn get_circuit_breaker_trip(
+        &self,
+        first_reserve_before: &BigUint,
+        second_reserve_before: &BigUint,
+        storage_cache: &StorageCache<Self>,
+    ) -> Option<(u64, CircuitBreakerConfig)> {
+        let config_mapper = self.circuit_breaker_config();
+        if config_mapper.is_empty() {
+            return None;
+        }
+
+        let config = config_mapper.get();
+        let price_deviation_percent = self
+            .get_price_divergence_percent(
+                config.round_offset,
+                &storage_cache.first_token_reserve,
+                &storage_cache.second_token_reserve,
+            )
+            .filter(|deviation| *deviation > config.max_price_deviation_percent)?;
+
+        let price_deviation_percent_before = self
+            .get_price_divergence_percent(
+                config.round_offset,
+                first_reserve_before,
+                second_reserve_before,
+            )
+            .unwrap_or_default();
+        if price_deviation_percent <= price_deviation_percent_before {
+            return None;
+        }
+
+        Some((price_deviation_percent, config))
+    }
+
+    #[view(getCircuitBreakerConfig)]
+    #[storage_mapper("circuitBreakerConfig")]
+    fn circuit_breaker_config(&self) -> SingleValueMapper<CircuitBreakerConfig>;
+}
@@ -33,15 +33,33 @@ pub trait ConfigModule:
         self.special_fee_percent().set(special_fee_percent);
     }
 
+    /// The total fee percent charged by swaps. In dynamic fee mode,
+    /// this is the fee computed by the last swap.
+    fn get_total_fee_percent(&self) -> u64 {
+        let dynamic_fee_percent_mapper = self.dynamic_fee_percent();
+        if dynamic_fee_percent_mapper.is_empty() {
+            self.total_fee_percent().get()
+        } else {
+            dynamic_fee_percent_mapper.get()
+        }
+    }
+
     #[view(getLpTokenIdentifier)]
     fn get_lp_token_identifier(&self) -> TokenIdentifier {
         self.lp_token_identifier().get()
     }
 
+    /// Set while a flash swap callback is executing
+    #[storage_mapper("flashSwapLock")]
+    fn flash_swap_lock(&self) -> SingleValueMapper<bool>;
+
     #[view(getTotalFeePercent)]
     #[storage_mapper("total_fee_percent")]
     fn total_fee_percent(&self) -> SingleValueMapper<u64>;
 
+    #[storage_mapper("dynamicFeePercent")]
+    fn dynamic_fee_percent(&self) -> SingleValueMapper<u64>;
+
     #[view(getSpecialFee)]
     #[storage_mapper("special_fee_percent")]
     fn special_fee_percent(&self) -> SingleValueMapper<u64>;
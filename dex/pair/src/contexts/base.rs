@@ -28,6 +28,12 @@ where
     C: crate::config::ConfigModule,
 {
     pub fn new(sc_ref: &'a C) -> Self {
+        if sc_ref.flash_swap_lock().get() {
+            multiversx_sc::contract_base::ErrorHelper::<C::Api>::signal_error_with_message(
+                crate::errors::ERROR_FLASH_SWAP_IN_PROGRESS,
+            );
+        }
+
         let first_token_id = sc_ref.first_token_id().get();
         let second_token_id = sc_ref.second_token_id().get();
         let first_token_reserve = sc_ref.pair_reserve(&first_token_id).get();
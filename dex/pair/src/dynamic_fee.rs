@@ -0,0 +1,122 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::{
+    amm,
+    config::{self, MAX_FEE_PERCENTAGE},
+    errors::*,
+    read_pair_storage,
+    safe_price::{self, Round},
+    safe_price_view, stable_swap,
+};
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Debug)]
+pub struct DynamicFeeConfig {
+    pub min_fee_percent: u64,
+    pub max_fee_percent: u64,
+    pub round_offset: Round,
+}
+
+#[multiversx_sc::module]
+pub trait DynamicFeeModule:
+    safe_price_view::SafePriceViewModule
+    + safe_price::SafePriceModule
+    + config::ConfigModule
+    + token_send::TokenSendModule
+    + amm::AmmModule
+    + stable_swap::StableSwapModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+    + read_pair_storage::ReadPairStorageModule
+{
+    /// Enables the dynamic fee mode. Before each swap, the total fee is set to the
+    /// divergence between the spot price and the safe price over the last
+    /// `round_offset` rounds, clamped between `min_fee_percent` and `max_fee_percent`.
+    /// The special fee percent stays the one set through setFeePercents.
+    #[endpoint(setDynamicFeeConfig)]
+    fn set_dynamic_fee_config(
+        &self,
+        min_fee_percent: u64,
+        max_fee_percent: u64,
+        round_offset: Round,
+    ) {
+        self.require_caller_has_owner_or_admin_permissions();
+        require!(
+            min_fee_percent > 0
+                && min_fee_percent >= self.special_fee_percent().get()
+                && min_fee_percent <= max_fee_percent
+                && max_fee_percent <= MAX_FEE_PERCENTAGE,
+            ERROR_BAD_PERCENTS
+        );
+        require!(round_offset > 0, ERROR_INVALID_ARGS);
+
+        self.dynamic_fee_config().set(DynamicFeeConfig {
+            min_fee_percent,
+            max_fee_percent,
+            round_offset,
+        });
+        self.dynamic_fee_percent().set(min_fee_percent);
+    }
+
+    /// Switches back to the static total fee percent.
+    #[endpoint(disableDynamicFee)]
+    fn disable_dynamic_fee(&self) {
+        self.require_caller_has_owner_or_admin_permissions();
+        self.dynamic_fee_config().clear();
+        self.dynamic_fee_percent().clear();
+    }
+
+    fn update_dynamic_fee(&self, first_token_reserve: &BigUint, second_token_reserve: &BigUint) {
+        let config_mapper = self.dynamic_fee_config();
+        if config_mapper.is_empty() {
+            return;
+        }
+
+        let fee_percent = self.compute_dynamic_fee_percent(
+            &config_mapper.get(),
+            first_token_reserve,
+            second_token_reserve,
+        );
+        self.dynamic_fee_percent().set(fee_percent);
+    }
+
+    /// The total fee percent a swap would be charged in the current round.
+    #[view(getEffectiveFeePercent)]
+    fn get_effective_fee_percent(&self) -> u64 {
+        let config_mapper = self.dynamic_fee_config();
+        if config_mapper.is_empty() {
+            return self.total_fee_percent().get();
+        }
+
+        let first_token_reserve = self.pair_reserve(&self.first_token_id().get()).get();
+        let second_token_reserve = self.pair_reserve(&self.second_token_id().get()).get();
+        self.compute_dynamic_fee_percent(
+            &config_mapper.get(),
+            &first_token_reserve,
+            &second_token_reserve,
+        )
+    }
+
+    fn compute_dynamic_fee_percent(
+        &self,
+        config: &DynamicFeeConfig,
+        first_token_reserve: &BigUint,
+        second_token_reserve: &BigUint,
+    ) -> u64 {
+        let fee_percent = match self.get_price_divergence_percent(
+            config.round_offset,
+            first_token_reserve,
+            second_token_reserve,
+        ) {
+            Some(divergence) => divergence.clamp(config.min_fee_percent, config.max_fee_percent),
+            None => config.min_fee_percent,
+        };
+
+        // the special fee might have been increased after the config was set
+        core::cmp::max(fee_percent, self.special_fee_percent().get())
+    }
+
+    #[view(getDynamicFeeConfig)]
+    #[storage_mapper("dynamicFeeConfig")]
+    fn dynamic_fee_config(&self) -> SingleValueMapper<DynamicFeeConfig>;
+}
@@ -0,0 +1,72 @@
+multiversx_sc::imports!();
+
+use multiversx_sc::storage::StorageKey;
+
+use crate::errors::*;
+
+pub static WRAPPED_EGLD_TOKEN_ID_STORAGE_KEY: &[u8] = b"wrappedEgldTokenId";
+
+pub mod egld_wrapper_proxy {
+    multiversx_sc::imports!();
+
+    #[multiversx_sc::proxy]
+    pub trait EgldWrapperProxy {
+        #[payable("EGLD")]
+        #[endpoint(wrapEgld)]
+        fn wrap_egld(&self) -> EsdtTokenPayment;
+
+        #[payable("*")]
+        #[endpoint(unwrapEgld)]
+        fn unwrap_egld(&self);
+    }
+}
+
+#[multiversx_sc::module]
+pub trait EgldWrapperModule {
+    /// Wraps the EGLD through the configured wrapper contract and returns the wrapped EGLD.
+    fn wrap_egld_payment(&self, amount: BigUint) -> EsdtTokenPayment {
+        let wrapper_address = self.get_egld_wrapper_address();
+        self.egld_wrapper_proxy_obj(wrapper_address)
+            .wrap_egld()
+            .with_egld_transfer(amount)
+            .execute_on_dest_context()
+    }
+
+    /// Unwraps the given wrapped EGLD, which is received back as EGLD by the current contract.
+    fn unwrap_egld_payment(&self, payment: EsdtTokenPayment) {
+        let wrapper_address = self.get_egld_wrapper_address();
+        let _: IgnoreValue = self
+            .egld_wrapper_proxy_obj(wrapper_address)
+            .unwrap_egld()
+            .with_esdt_transfer(payment)
+            .execute_on_dest_context();
+    }
+
+    fn get_egld_wrapper_address(&self) -> ManagedAddress {
+        let wrapper_address_mapper = self.egld_wrapper_address();
+        require!(
+            !wrapper_address_mapper.is_empty(),
+            ERROR_EGLD_WRAPPER_NOT_SET
+        );
+
+        wrapper_address_mapper.get()
+    }
+
+    fn get_wrapped_egld_token_id(&self, wrapper_address: ManagedAddress) -> TokenIdentifier {
+        SingleValueMapper::<_, _, ManagedAddress>::new_from_address(
+            wrapper_address,
+            StorageKey::new(WRAPPED_EGLD_TOKEN_ID_STORAGE_KEY),
+        )
+        .get()
+    }
+
+    #[proxy]
+    fn egld_wrapper_proxy_obj(
+        &self,
+        sc_address: ManagedAddress,
+    ) -> egld_wrapper_proxy::Proxy<Self::Api>;
+
+    #[view(getEgldWrapperAddress)]
+    #[storage_mapper("egldWrapperAddress")]
+    fn egld_wrapper_address(&self) -> SingleValueMapper<ManagedAddress>;
+}
@@ -36,6 +36,7 @@ pub static ERROR_PAIR_NOT_TRUSTED: &[u8] = b"Pair not trusted";
 pub static ERROR_ALREADY_FEE_DEST: &[u8] = b"Already a fee destination";
 pub static ERROR_NOT_FEE_DEST: &[u8] = b"Not a fee destination";
 pub static ERROR_BAD_TOKEN_FEE_DEST: &[u8] = b"Destination fee token differs";
+pub static ERROR_BAD_FEE_DESTINATION_WEIGHTS: &[u8] = b"Invalid fee destination weights";
 
 pub static ERROR_ZERO_AMOUNT: &[u8] = b"Zero amount";
 pub static ERROR_UNKNOWN_TOKEN: &[u8] = b"Unknown token";
@@ -52,3 +53,24 @@ pub static ERROR_SAFE_PRICE_CURRENT_INDEX: &[u8] =
     b"The current safe price index is greater than the maximum number of observations";
 pub static ERROR_SAFE_PRICE_OBSERVATION_DOES_NOT_EXIST: &[u8] =
     b"The price observation does not exist";
+
+pub static ERROR_ALREADY_STABLE_SWAP: &[u8] = b"Pool already uses the stable swap invariant";
+pub static ERROR_NOT_STABLE_SWAP: &[u8] = b"Pool does not use the stable swap invariant";
+pub static ERROR_POOL_NOT_EMPTY: &[u8] = b"Pool type can only be changed before adding liquidity";
+pub static ERROR_BAD_AMPLIFICATION: &[u8] = b"Invalid amplification coefficient";
+pub static ERROR_BAD_RATE_MULTIPLIER: &[u8] = b"Invalid rate multiplier";
+pub static ERROR_RAMP_TOO_SHORT: &[u8] = b"Amplification ramp too short";
+pub static ERROR_AMP_CHANGE_TOO_BIG: &[u8] = b"Amplification change too big";
+pub static ERROR_STABLE_SWAP_NO_CONVERGENCE: &[u8] = b"Stable swap invariant did not converge";
+pub static ERROR_FLASH_SWAP_IN_PROGRESS: &[u8] = b"Flash swap in progress";
+pub static ERROR_FLASH_SWAP_CALLER_NOT_SC: &[u8] =
+    b"Flash swaps can only be initiated by smart contracts";
+pub static ERROR_SWAP_OUTPUT_LOCKED: &[u8] = b"Not available while the swap output is locked";
+pub static ERROR_CIRCUIT_BREAKER_TRIPPED: &[u8] = b"Price deviates too much from the safe price";
+pub static ERROR_NOTHING_TO_SKIM: &[u8] = b"Nothing to skim";
+pub static ERROR_EGLD_WRAPPER_NOT_SET: &[u8] = b"EGLD wrapper address not set";
+pub static ERROR_NOT_WRAPPED_EGLD_PAIR: &[u8] = b"Wrapped EGLD is not a token of the pair";
+pub static ERROR_BAD_FEE_DISCOUNT_TIERS: &[u8] = b"Invalid fee discount tiers";
+pub static ERROR_NOT_TRUSTED_SWAP_PROXY: &[u8] = b"Caller cannot swap on behalf of others";
+pub static ERROR_TOO_MANY_QUOTES: &[u8] = b"Too many amounts to quote";
+pub static ERROR_BAD_PRICE_IMPACT: &[u8] = b"Invalid price impact";
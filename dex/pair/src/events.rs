@@ -19,6 +19,9 @@ pub struct SwapEvent<M: ManagedTypeApi> {
     block: u64,
     epoch: u64,
     timestamp: u64,
+    total_fee_percent: u64,
+    original_caller: ManagedAddress<M>,
+    fee_discount_percent: u64,
 }
 
 #[derive(TypeAbi, TopEncode)]
@@ -68,6 +71,63 @@ pub struct RemoveLiquidityEvent<M: ManagedTypeApi> {
     timestamp: u64,
 }
 
+#[derive(TypeAbi, TopEncode)]
+pub struct FlashSwapEvent<M: ManagedTypeApi> {
+    caller: ManagedAddress<M>,
+    first_token_id: TokenIdentifier<M>,
+    first_token_amount_out: BigUint<M>,
+    first_token_amount_in: BigUint<M>,
+    first_token_fee_amount: BigUint<M>,
+    second_token_id: TokenIdentifier<M>,
+    second_token_amount_out: BigUint<M>,
+    second_token_amount_in: BigUint<M>,
+    second_token_fee_amount: BigUint<M>,
+    first_token_reserves: BigUint<M>,
+    second_token_reserves: BigUint<M>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct SkimEvent<M: ManagedTypeApi> {
+    caller: ManagedAddress<M>,
+    destination: ManagedAddress<M>,
+    first_token_id: TokenIdentifier<M>,
+    first_token_amount: BigUint<M>,
+    second_token_id: TokenIdentifier<M>,
+    second_token_amount: BigUint<M>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct SyncEvent<M: ManagedTypeApi> {
+    caller: ManagedAddress<M>,
+    first_token_id: TokenIdentifier<M>,
+    first_token_reserves: BigUint<M>,
+    second_token_id: TokenIdentifier<M>,
+    second_token_reserves: BigUint<M>,
+    lp_supply: BigUint<M>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct CircuitBreakerEvent<M: ManagedTypeApi> {
+    caller: ManagedAddress<M>,
+    first_token_id: TokenIdentifier<M>,
+    second_token_id: TokenIdentifier<M>,
+    price_deviation_percent: u64,
+    first_token_reserves: BigUint<M>,
+    second_token_reserves: BigUint<M>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
 #[multiversx_sc::module]
 pub trait EventsModule:
     crate::config::ConfigModule
@@ -99,6 +159,9 @@ pub trait EventsModule:
                 block: self.blockchain().get_block_nonce(),
                 epoch,
                 timestamp: self.blockchain().get_block_timestamp(),
+                total_fee_percent: context.total_fee_percent,
+                original_caller: context.original_caller.unwrap_or_else(|| caller.clone()),
+                fee_discount_percent: context.fee_discount_percent,
             },
         )
     }
@@ -188,6 +251,117 @@ pub trait EventsModule:
         )
     }
 
+    fn emit_flash_swap_event(
+        &self,
+        storage_cache: &StorageCache<Self>,
+        caller: &ManagedAddress,
+        amounts_out: [BigUint; 2],
+        amounts_in: [BigUint; 2],
+        fee_amounts: [BigUint; 2],
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        let [first_token_amount_out, second_token_amount_out] = amounts_out;
+        let [first_token_amount_in, second_token_amount_in] = amounts_in;
+        let [first_token_fee_amount, second_token_fee_amount] = fee_amounts;
+        self.flash_swap_event(
+            &storage_cache.first_token_id,
+            &storage_cache.second_token_id,
+            caller,
+            epoch,
+            &FlashSwapEvent {
+                caller: caller.clone(),
+                first_token_id: storage_cache.first_token_id.clone(),
+                first_token_amount_out,
+                first_token_amount_in,
+                first_token_fee_amount,
+                second_token_id: storage_cache.second_token_id.clone(),
+                second_token_amount_out,
+                second_token_amount_in,
+                second_token_fee_amount,
+                first_token_reserves: storage_cache.first_token_reserve.clone(),
+                second_token_reserves: storage_cache.second_token_reserve.clone(),
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_skim_event(
+        &self,
+        storage_cache: &StorageCache<Self>,
+        destination: &ManagedAddress,
+        payments: ManagedVec<EsdtTokenPayment<Self::Api>>,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        let caller = self.blockchain().get_caller();
+        self.skim_event(
+            &storage_cache.first_token_id,
+            &storage_cache.second_token_id,
+            &caller,
+            epoch,
+            &SkimEvent {
+                caller: caller.clone(),
+                destination: destination.clone(),
+                first_token_id: storage_cache.first_token_id.clone(),
+                first_token_amount: payments.get(0).amount,
+                second_token_id: storage_cache.second_token_id.clone(),
+                second_token_amount: payments.get(1).amount,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_sync_event(&self, storage_cache: &StorageCache<Self>) {
+        let epoch = self.blockchain().get_block_epoch();
+        let caller = self.blockchain().get_caller();
+        self.sync_event(
+            &storage_cache.first_token_id,
+            &storage_cache.second_token_id,
+            &caller,
+            epoch,
+            &SyncEvent {
+                caller: caller.clone(),
+                first_token_id: storage_cache.first_token_id.clone(),
+                first_token_reserves: storage_cache.first_token_reserve.clone(),
+                second_token_id: storage_cache.second_token_id.clone(),
+                second_token_reserves: storage_cache.second_token_reserve.clone(),
+                lp_supply: storage_cache.lp_token_supply.clone(),
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_circuit_breaker_event(
+        &self,
+        storage_cache: &StorageCache<Self>,
+        price_deviation_percent: u64,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        let caller = self.blockchain().get_caller();
+        self.circuit_breaker_event(
+            &storage_cache.first_token_id,
+            &storage_cache.second_token_id,
+            &caller,
+            epoch,
+            &CircuitBreakerEvent {
+                caller: caller.clone(),
+                first_token_id: storage_cache.first_token_id.clone(),
+                second_token_id: storage_cache.second_token_id.clone(),
+                price_deviation_percent,
+                first_token_reserves: storage_cache.first_token_reserve.clone(),
+                second_token_reserves: storage_cache.second_token_reserve.clone(),
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
     #[event("swap")]
     fn swap_event(
         &self,
@@ -226,4 +400,44 @@ pub trait EventsModule:
         #[indexed] epoch: u64,
         remove_liquidity_event: &RemoveLiquidityEvent<Self::Api>,
     );
+
+    #[event("flash_swap")]
+    fn flash_swap_event(
+        &self,
+        #[indexed] first_token: &TokenIdentifier,
+        #[indexed] second_token: &TokenIdentifier,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] epoch: u64,
+        flash_swap_event: &FlashSwapEvent<Self::Api>,
+    );
+
+    #[event("skim")]
+    fn skim_event(
+        &self,
+        #[indexed] first_token: &TokenIdentifier,
+        #[indexed] second_token: &TokenIdentifier,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] epoch: u64,
+        skim_event: &SkimEvent<Self::Api>,
+    );
+
+    #[event("sync")]
+    fn sync_event(
+        &self,
+        #[indexed] first_token: &TokenIdentifier,
+        #[indexed] second_token: &TokenIdentifier,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] epoch: u64,
+        sync_event: &SyncEvent<Self::Api>,
+    );
+
+    #[event("circuit_breaker")]
+    fn circuit_breaker_event(
+        &self,
+        #[indexed] first_token: &TokenIdentifier,
+        #[indexed] second_token: &TokenIdentifier,
+        #[indexed] caller: &ManagedAddress,
+        #[indexed] epoch: u64,
+        circuit_breaker_event: &CircuitBreakerEvent<Self::Api>,
+    );
 }
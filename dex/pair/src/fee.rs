@@ -5,6 +5,7 @@ use super::amm;
 use super::config;
 use super::errors::*;
 use super::liquidity_pool;
+use super::stable_swap;
 use crate::config::MAX_PERCENTAGE;
 use crate::contexts::base::StorageCache;
 use crate::contexts::base::SwapTokensOrder;
@@ -12,6 +13,8 @@ use crate::contexts::base::SwapTokensOrder;
 use common_structs::TokenPair;
 use fees_collector::fees_accumulation::ProxyTrait as _;
 
+pub const MAX_FEE_DESTINATION_WEIGHT: u64 = 10_000;
+
 mod self_proxy {
     multiversx_sc::imports!();
 
@@ -28,13 +31,16 @@ pub trait FeeModule:
     config::ConfigModule
     + liquidity_pool::LiquidityPoolModule
     + amm::AmmModule
+    + stable_swap::StableSwapModule
     + token_send::TokenSendModule
     + permissions_module::PermissionsModule
     + pausable::PausableModule
 {
     #[view(getFeeState)]
     fn is_fee_enabled(&self) -> bool {
-        !self.destination_map().is_empty() || !self.fees_collector_address().is_empty()
+        !self.destination_map().is_empty()
+            || !self.fees_collector_address().is_empty()
+            || !self.fee_destination_weights().is_empty()
     }
 
     #[endpoint(whitelist)]
@@ -117,11 +123,93 @@ pub trait FeeModule:
             "Invalid fees percentage"
         );
 
-        self.fees_collector_address().set(&fees_collector_address);
+        let fees_collector_mapper = self.fees_collector_address();
+        if !fees_collector_mapper.is_empty() {
+            let old_fees_collector_address = fees_collector_mapper.get();
+            let mut weights_mapper = self.fee_destination_weights();
+            if let Some(weight) = weights_mapper.remove(&old_fees_collector_address) {
+                weights_mapper.insert(fees_collector_address.clone(), weight);
+            }
+        }
+
+        fees_collector_mapper.set(&fees_collector_address);
         self.fees_collector_cut_percentage()
             .set(fees_collector_cut_percentage);
     }
 
+    /// Treasury destinations receive the special fees as they are, without any swap or burn.
+    /// They only receive fees once they are given a weight through `setFeeDestinationWeights`.
+    #[endpoint(addTreasuryFeeDestination)]
+    fn add_treasury_fee_destination(&self, treasury_address: ManagedAddress) {
+        self.require_caller_has_owner_permissions();
+        require!(
+            !self.destination_map().contains_key(&treasury_address),
+            ERROR_ALREADY_FEE_DEST
+        );
+
+        let is_new = self.treasury_fee_destinations().insert(treasury_address);
+        require!(is_new, ERROR_ALREADY_FEE_DEST);
+    }
+
+    #[endpoint(removeTreasuryFeeDestination)]
+    fn remove_treasury_fee_destination(&self, treasury_address: ManagedAddress) {
+        self.require_caller_has_owner_permissions();
+        let is_removed = self
+            .treasury_fee_destinations()
+            .swap_remove(&treasury_address);
+        require!(is_removed, ERROR_NOT_FEE_DEST);
+
+        self.fee_destination_weights().remove(&treasury_address);
+    }
+
+    /// Replaces the weights, in basis points, by which the special fees are split between
+    /// the fees collector, the `setFeeOn` destinations and the treasury destinations.
+    /// The weights must add up to 10_000. Once set, they take precedence over
+    /// `fees_collector_cut_percentage` and the equal split between destinations.
+    /// Calling the endpoint without arguments restores the previous behaviour.
+    ///
+    /// When a destination is removed, the fees are split by the weights of the remaining ones.
+    #[endpoint(setFeeDestinationWeights)]
+    fn set_fee_destination_weights(
+        &self,
+        weights: MultiValueEncoded<MultiValue2<ManagedAddress, u64>>,
+    ) {
+        self.require_caller_has_owner_permissions();
+
+        let mut weights_mapper = self.fee_destination_weights();
+        weights_mapper.clear();
+
+        let mut total_weight = 0u64;
+        for weight_entry in weights {
+            let (destination, weight) = weight_entry.into_tuple();
+            require!(
+                self.is_known_fee_destination(&destination),
+                ERROR_NOT_FEE_DEST
+            );
+            require!(weight > 0, ERROR_BAD_FEE_DESTINATION_WEIGHTS);
+
+            let is_new = weights_mapper.insert(destination, weight).is_none();
+            require!(is_new, ERROR_BAD_FEE_DESTINATION_WEIGHTS);
+
+            total_weight += weight;
+        }
+
+        require!(
+            total_weight == 0 || total_weight == MAX_FEE_DESTINATION_WEIGHT,
+            ERROR_BAD_FEE_DESTINATION_WEIGHTS
+        );
+    }
+
+    fn is_known_fee_destination(&self, address: &ManagedAddress) -> bool {
+        let fees_collector_mapper = self.fees_collector_address();
+        let is_fees_collector =
+            !fees_collector_mapper.is_empty() && &fees_collector_mapper.get() == address;
+
+        is_fees_collector
+            || self.destination_map().contains_key(address)
+            || self.treasury_fee_destinations().contains(address)
+    }
+
     fn send_fee(
         &self,
         storage_cache: &mut StorageCache<Self>,
@@ -133,6 +221,11 @@ pub trait FeeModule:
             return;
         }
 
+        if !self.fee_destination_weights().is_empty() {
+            self.send_weighted_fee(storage_cache, swap_tokens_order, fee_token, fee_amount);
+            return;
+        }
+
         let fees_collector_configured = !self.fees_collector_address().is_empty();
         let remaining_fee = if fees_collector_configured {
             let fees_collector_cut_percentage = self.fees_collector_cut_percentage().get();
@@ -170,6 +263,62 @@ pub trait FeeModule:
         }
     }
 
+    fn send_weighted_fee(
+        &self,
+        storage_cache: &mut StorageCache<Self>,
+        swap_tokens_order: SwapTokensOrder,
+        fee_token: &TokenIdentifier,
+        fee_amount: &BigUint,
+    ) {
+        let weights_mapper = self.fee_destination_weights();
+        let total_weight: u64 = weights_mapper.values().sum();
+        let fees_collector_mapper = self.fees_collector_address();
+        let last_index = weights_mapper.len() - 1;
+        let mut fees_sent = BigUint::zero();
+
+        for (index, (fee_address, weight)) in weights_mapper.iter().enumerate() {
+            // the rounding remainder goes to the last destination, so no fee is left in the pair
+            let fee_part = if index == last_index {
+                fee_amount - &fees_sent
+            } else {
+                fee_amount * weight / total_weight
+            };
+            if fee_part == 0 {
+                continue;
+            }
+
+            fees_sent += &fee_part;
+            self.add_fee_destination_accumulated_fees(&fee_address, fee_token, &fee_part);
+
+            if !fees_collector_mapper.is_empty() && fees_collector_mapper.get() == fee_address {
+                self.send_fees_collector_cut(fee_token.clone(), fee_part);
+            } else if self.treasury_fee_destinations().contains(&fee_address) {
+                self.send()
+                    .direct_esdt(&fee_address, fee_token, 0, &fee_part);
+            } else {
+                let fee_token_requested = self.destination_map().get(&fee_address).unwrap();
+                self.send_fee_slice(
+                    storage_cache,
+                    swap_tokens_order,
+                    fee_token,
+                    &fee_part,
+                    &fee_address,
+                    &fee_token_requested,
+                );
+            }
+        }
+    }
+
+    fn add_fee_destination_accumulated_fees(
+        &self,
+        fee_address: &ManagedAddress,
+        fee_token: &TokenIdentifier,
+        amount: &BigUint,
+    ) {
+        self.fee_destination_accumulated_fees(fee_address, fee_token)
+            .update(|accumulated_fees| *accumulated_fees += amount);
+    }
+
     fn send_fees_collector_cut(&self, token: TokenIdentifier, cut_amount: BigUint) {
         let fees_collector_address = self.fees_collector_address().get();
         let _: IgnoreValue = self
@@ -361,12 +510,17 @@ pub trait FeeModule:
 
         if enabled {
             require!(!is_dest, ERROR_ALREADY_FEE_DEST);
+            require!(
+                !self.treasury_fee_destinations().contains(&fee_to_address),
+                ERROR_ALREADY_FEE_DEST
+            );
             self.destination_map().insert(fee_to_address, fee_token);
         } else {
             require!(is_dest, ERROR_NOT_FEE_DEST);
             let dest_fee_token = self.destination_map().get(&fee_to_address).unwrap();
             require!(fee_token == dest_fee_token, ERROR_BAD_TOKEN_FEE_DEST);
             self.destination_map().remove(&fee_to_address);
+            self.fee_destination_weights().remove(&fee_to_address);
         }
     }
 
@@ -397,6 +551,38 @@ pub trait FeeModule:
         result
     }
 
+    #[view(getFeeDestinationWeights)]
+    fn get_fee_destination_weights(&self) -> MultiValueEncoded<MultiValue2<ManagedAddress, u64>> {
+        let mut result = MultiValueEncoded::new();
+        for (fee_address, weight) in self.fee_destination_weights().iter() {
+            result.push((fee_address, weight).into());
+        }
+        result
+    }
+
+    /// The special fees sent so far to the given destination through the weighted split,
+    /// in the tokens of the pair, before any swap made on behalf of the destination.
+    #[view(getFeeDestinationAccumulatedFees)]
+    fn get_fee_destination_accumulated_fees(
+        &self,
+        fee_address: ManagedAddress,
+    ) -> MultiValue2<EsdtTokenPayment, EsdtTokenPayment> {
+        let first_token_id = self.first_token_id().get();
+        let second_token_id = self.second_token_id().get();
+        let first_token_amount = self
+            .fee_destination_accumulated_fees(&fee_address, &first_token_id)
+            .get();
+        let second_token_amount = self
+            .fee_destination_accumulated_fees(&fee_address, &second_token_id)
+            .get();
+
+        (
+            EsdtTokenPayment::new(first_token_id, 0, first_token_amount),
+            EsdtTokenPayment::new(second_token_id, 0, second_token_amount),
+        )
+            .into()
+    }
+
     #[proxy]
     fn pair_proxy(&self) -> self_proxy::Proxy<Self::Api>;
 
@@ -411,6 +597,20 @@ pub trait FeeModule:
     #[storage_mapper("feesCollectorCutPercentage")]
     fn fees_collector_cut_percentage(&self) -> SingleValueMapper<u64>;
 
+    #[view(getTreasuryFeeDestinations)]
+    #[storage_mapper("treasuryFeeDestinations")]
+    fn treasury_fee_destinations(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[storage_mapper("feeDestinationWeights")]
+    fn fee_destination_weights(&self) -> MapMapper<ManagedAddress, u64>;
+
+    #[storage_mapper("feeDestinationAccumulatedFees")]
+    fn fee_destination_accumulated_fees(
+        &self,
+        fee_address: &ManagedAddress,
+        token_id: &TokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
+
     #[storage_mapper("fee_destination")]
     fn destination_map(&self) -> MapMapper<ManagedAddress, TokenIdentifier>;
 
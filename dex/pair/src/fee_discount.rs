@@ -0,0 +1,158 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::{
+    config::{self, MAX_PERCENTAGE},
+    errors::*,
+};
+
+pub const MAX_FEE_DISCOUNT_TIERS: usize = 10;
+
+#[derive(
+    ManagedVecItem,
+    TopEncode,
+    TopDecode,
+    NestedEncode,
+    NestedDecode,
+    TypeAbi,
+    Clone,
+    PartialEq,
+    Debug,
+)]
+pub struct FeeDiscountTier<M: ManagedTypeApi> {
+    pub min_energy: BigUint<M>,
+    pub discount_percent: u64,
+}
+
+#[multiversx_sc::module]
+pub trait FeeDiscountModule:
+    config::ConfigModule
+    + token_send::TokenSendModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+    + energy_query::EnergyQueryModule
+{
+    /// Sets the energy thresholds, in increasing order, and the swap fee discount of each,
+    /// out of MAX_PERCENTAGE. Traders get the discount of the highest threshold their
+    /// energy reaches. Passing no tiers disables the discounts.
+    #[endpoint(setFeeDiscountTiers)]
+    fn set_fee_discount_tiers(&self, tiers: MultiValueEncoded<MultiValue2<BigUint, u64>>) {
+        self.require_caller_has_owner_or_admin_permissions();
+        require!(
+            tiers.len() <= MAX_FEE_DISCOUNT_TIERS,
+            ERROR_BAD_FEE_DISCOUNT_TIERS
+        );
+
+        let mut fee_discount_tiers = ManagedVec::<Self::Api, FeeDiscountTier<Self::Api>>::new();
+        for tier in tiers {
+            let (min_energy, discount_percent) = tier.into_tuple();
+            require!(
+                min_energy > 0u64 && discount_percent > 0 && discount_percent <= MAX_PERCENTAGE,
+                ERROR_BAD_FEE_DISCOUNT_TIERS
+            );
+
+            let tiers_len = fee_discount_tiers.len();
+            if tiers_len > 0 {
+                let prev_tier = fee_discount_tiers.get(tiers_len - 1);
+                require!(
+                    min_energy > prev_tier.min_energy
+                        && discount_percent > prev_tier.discount_percent,
+                    ERROR_BAD_FEE_DISCOUNT_TIERS
+                );
+            }
+
+            fee_discount_tiers.push(FeeDiscountTier {
+                min_energy,
+                discount_percent,
+            });
+        }
+
+        self.fee_discount_tiers().set(fee_discount_tiers);
+    }
+
+    /// Allows the given contract to pass the original caller to the swap endpoints,
+    /// so that the discount of the user is applied. The router is always allowed.
+    #[endpoint(addTrustedSwapProxy)]
+    fn add_trusted_swap_proxy(&self, address: ManagedAddress) {
+        self.require_caller_has_owner_permissions();
+        require!(
+            self.trusted_swap_proxies().insert(address),
+            ERROR_ALREADY_WHITELISTED
+        );
+    }
+
+    #[endpoint(removeTrustedSwapProxy)]
+    fn remove_trusted_swap_proxy(&self, address: ManagedAddress) {
+        self.require_caller_has_owner_permissions();
+        require!(
+            self.trusted_swap_proxies().swap_remove(&address),
+            ERROR_NOT_WHITELISTED
+        );
+    }
+
+    /// The discount, out of MAX_PERCENTAGE, applied to the swap fees of the given address.
+    #[view(getFeeDiscountPercent)]
+    fn get_fee_discount_percent(&self, address: ManagedAddress) -> u64 {
+        let tiers_mapper = self.fee_discount_tiers();
+        if tiers_mapper.is_empty() {
+            return 0;
+        }
+
+        let energy = self.get_energy_amount(&address);
+        let mut discount_percent = 0;
+        for tier in tiers_mapper.get().iter() {
+            if energy < tier.min_energy {
+                break;
+            }
+
+            discount_percent = tier.discount_percent;
+        }
+
+        discount_percent
+    }
+
+    /// The total and special fee percents charged to the given address by swaps.
+    #[view(getFeePercentsForAddress)]
+    fn get_fee_percents_for_address(&self, address: ManagedAddress) -> MultiValue2<u64, u64> {
+        let discount_percent = self.get_fee_discount_percent(address);
+        let total_fee_percent =
+            self.apply_fee_discount(self.get_total_fee_percent(), discount_percent);
+        let special_fee_percent =
+            self.apply_fee_discount(self.special_fee_percent().get(), discount_percent);
+
+        (total_fee_percent, special_fee_percent).into()
+    }
+
+    /// The original caller can only be given by the router and the trusted proxies.
+    fn get_swap_original_caller(
+        &self,
+        opt_original_caller: OptionalValue<ManagedAddress>,
+    ) -> ManagedAddress {
+        let caller = self.blockchain().get_caller();
+        match opt_original_caller {
+            OptionalValue::Some(original_caller) => {
+                require!(
+                    caller == self.router_address().get()
+                        || self.trusted_swap_proxies().contains(&caller),
+                    ERROR_NOT_TRUSTED_SWAP_PROXY
+                );
+
+                original_caller
+            }
+            OptionalValue::None => caller,
+        }
+    }
+
+    #[inline]
+    fn apply_fee_discount(&self, fee_percent: u64, discount_percent: u64) -> u64 {
+        fee_percent * (MAX_PERCENTAGE - discount_percent) / MAX_PERCENTAGE
+    }
+
+    #[view(getFeeDiscountTiers)]
+    #[storage_mapper("feeDiscountTiers")]
+    fn fee_discount_tiers(&self) -> SingleValueMapper<ManagedVec<FeeDiscountTier<Self::Api>>>;
+
+    #[view(getTrustedSwapProxies)]
+    #[storage_mapper("trustedSwapProxies")]
+    fn trusted_swap_proxies(&self) -> UnorderedSetMapper<ManagedAddress>;
+}
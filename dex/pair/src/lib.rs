@@ -4,17 +4,22 @@ multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
 mod amm;
+pub mod circuit_breaker;
 pub mod config;
 mod contexts;
+pub mod dynamic_fee;
+pub mod egld_wrapper;
 pub mod errors;
 mod events;
 pub mod fee;
+pub mod fee_discount;
 mod liquidity_pool;
 pub mod locking_wrapper;
 pub mod pair_actions;
 pub mod read_pair_storage;
 pub mod safe_price;
 pub mod safe_price_view;
+pub mod stable_swap;
 
 use crate::errors::*;
 
@@ -29,14 +34,19 @@ use permissions_module::Permissions;
 #[multiversx_sc::contract]
 pub trait Pair<ContractReader>:
     amm::AmmModule
+    + stable_swap::StableSwapModule
     + fee::FeeModule
+    + fee_discount::FeeDiscountModule
     + liquidity_pool::LiquidityPoolModule
     + config::ConfigModule
     + token_send::TokenSendModule
     + events::EventsModule
     + read_pair_storage::ReadPairStorageModule
+    + egld_wrapper::EgldWrapperModule
     + safe_price::SafePriceModule
     + safe_price_view::SafePriceViewModule
+    + dynamic_fee::DynamicFeeModule
+    + circuit_breaker::CircuitBreakerModule
     + contexts::output_builder::OutputBuilderModule
     + locking_wrapper::LockingWrapperModule
     + permissions_module::PermissionsModule
@@ -45,9 +55,14 @@ pub trait Pair<ContractReader>:
     + pair_actions::add_liq::AddLiquidityModule
     + pair_actions::remove_liq::RemoveLiquidityModule
     + pair_actions::swap::SwapModule
+    + pair_actions::flash_swap::FlashSwapModule
+    + pair_actions::single_token_liq::SingleTokenLiquidityModule
+    + pair_actions::skim_sync::SkimSyncModule
+    + pair_actions::egld_liq::EgldLiquidityModule
     + pair_actions::views::ViewsModule
     + pair_actions::common_methods::CommonMethodsModule
     + utils::UtilsModule
+    + energy_query::EnergyQueryModule
 {
     #[init]
     fn init(
@@ -8,12 +8,14 @@ use crate::errors::*;
 
 use super::amm;
 use super::config;
+use super::stable_swap;
 
 const MINIMUM_LIQUIDITY: u64 = 1_000;
 
 #[multiversx_sc::module]
 pub trait LiquidityPoolModule:
     amm::AmmModule
+    + stable_swap::StableSwapModule
     + config::ConfigModule
     + token_send::TokenSendModule
     + permissions_module::PermissionsModule
@@ -202,7 +204,8 @@ pub trait LiquidityPoolModule:
         let reserve_out = storage_cache.get_reserve_out(swap_tokens_order);
         require!(*reserve_in != 0, ERROR_ZERO_AMOUNT);
 
-        let amount_out = self.get_amount_out_no_fee(amount_in, reserve_in, reserve_out);
+        let amount_out =
+            self.get_amount_out_no_fee(amount_in, reserve_in, reserve_out, swap_tokens_order);
         require!(
             *reserve_out > amount_out && amount_out != 0,
             ERROR_ZERO_AMOUNT
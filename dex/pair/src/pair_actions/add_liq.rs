@@ -12,10 +12,14 @@ multiversx_sc::imports!();
 pub trait AddLiquidityModule:
     crate::liquidity_pool::LiquidityPoolModule
     + crate::amm::AmmModule
+    + crate::stable_swap::StableSwapModule
     + crate::contexts::output_builder::OutputBuilderModule
     + crate::locking_wrapper::LockingWrapperModule
     + crate::events::EventsModule
     + crate::safe_price::SafePriceModule
+    + crate::safe_price_view::SafePriceViewModule
+    + crate::circuit_breaker::CircuitBreakerModule
+    + crate::read_pair_storage::ReadPairStorageModule
     + crate::config::ConfigModule
     + token_send::TokenSendModule
     + permissions_module::PermissionsModule
@@ -68,6 +72,8 @@ pub trait AddLiquidityModule:
             &storage_cache.lp_token_supply,
         );
 
+        let first_reserve_before = storage_cache.first_token_reserve.clone();
+        let second_reserve_before = storage_cache.second_token_reserve.clone();
         let initial_k = self.calculate_k_constant(
             &storage_cache.first_token_reserve,
             &storage_cache.second_token_reserve,
@@ -101,6 +107,12 @@ pub trait AddLiquidityModule:
         );
         require!(initial_k <= new_k, ERROR_K_INVARIANT_FAILED);
 
+        self.check_circuit_breaker(
+            &first_reserve_before,
+            &second_reserve_before,
+            &storage_cache,
+        );
+
         self.send()
             .esdt_local_mint(&storage_cache.lp_token_id, 0, &add_liq_context.liq_added);
 
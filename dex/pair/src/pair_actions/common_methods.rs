@@ -13,4 +13,10 @@ pub trait CommonMethodsModule {
     fn can_swap(&self, state: State) -> bool {
         state == State::Active
     }
+
+    fn get_own_balance(&self, token_id: &TokenIdentifier) -> BigUint {
+        let own_address = self.blockchain().get_sc_address();
+        self.blockchain()
+            .get_esdt_balance(&own_address, token_id, 0)
+    }
 }
@@ -0,0 +1,113 @@
+use crate::{ERROR_BAD_PAYMENT_TOKENS, ERROR_NOT_WRAPPED_EGLD_PAIR};
+
+use super::common_result_types::{
+    AddLiquiditySingleTokenResultType, RemoveLiquidityEgldResultType,
+};
+
+multiversx_sc::imports!();
+
+#[multiversx_sc::module]
+pub trait EgldLiquidityModule:
+    crate::liquidity_pool::LiquidityPoolModule
+    + crate::amm::AmmModule
+    + crate::stable_swap::StableSwapModule
+    + crate::contexts::output_builder::OutputBuilderModule
+    + crate::locking_wrapper::LockingWrapperModule
+    + crate::events::EventsModule
+    + crate::safe_price::SafePriceModule
+    + crate::safe_price_view::SafePriceViewModule
+    + crate::dynamic_fee::DynamicFeeModule
+    + crate::circuit_breaker::CircuitBreakerModule
+    + crate::read_pair_storage::ReadPairStorageModule
+    + crate::egld_wrapper::EgldWrapperModule
+    + crate::fee::FeeModule
+    + crate::fee_discount::FeeDiscountModule
+    + crate::config::ConfigModule
+    + token_send::TokenSendModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+    + super::common_methods::CommonMethodsModule
+    + super::remove_liq::RemoveLiquidityModule
+    + super::swap::SwapModule
+    + energy_query::EnergyQueryModule
+    + super::single_token_liq::SingleTokenLiquidityModule
+    + utils::UtilsModule
+{
+    /// The wrapped EGLD token of the wrapper contract has to be one of the pair tokens.
+    #[endpoint(setEgldWrapperAddress)]
+    fn set_egld_wrapper_address(&self, wrapper_address: ManagedAddress) {
+        self.require_caller_has_owner_permissions();
+        require!(
+            self.blockchain().is_smart_contract(&wrapper_address),
+            "Invalid SC Address"
+        );
+
+        let wrapped_egld_token_id = self.get_wrapped_egld_token_id(wrapper_address.clone());
+        require!(
+            wrapped_egld_token_id == self.first_token_id().get()
+                || wrapped_egld_token_id == self.second_token_id().get(),
+            ERROR_NOT_WRAPPED_EGLD_PAIR
+        );
+
+        self.egld_wrapper_address().set(&wrapper_address);
+    }
+
+    /// Wraps the paid EGLD, then adds liquidity the same way as addLiquiditySingleToken.
+    /// The leftovers are sent as ESDTs.
+    #[payable("EGLD")]
+    #[endpoint(addLiquidityEgld)]
+    fn add_liquidity_egld(
+        &self,
+        min_lp_out: BigUint,
+    ) -> AddLiquiditySingleTokenResultType<Self::Api> {
+        let egld_amount = self.call_value().egld_value().clone_value();
+        require!(egld_amount > 0, ERROR_BAD_PAYMENT_TOKENS);
+
+        let payment = self.wrap_egld_payment(egld_amount);
+        self.add_liquidity_single_token_from_payment(payment, min_lp_out)
+    }
+
+    /// Same as removeLiquidity, but the wrapped EGLD part is unwrapped and sent as EGLD.
+    #[payable("*")]
+    #[endpoint(removeLiquidityEgld)]
+    fn remove_liquidity_egld(
+        &self,
+        first_token_amount_min: BigUint,
+        second_token_amount_min: BigUint,
+    ) -> RemoveLiquidityEgldResultType<Self::Api> {
+        let caller = self.blockchain().get_caller();
+        let payment = self.call_value().single_esdt();
+        let wrapper_address = self.get_egld_wrapper_address();
+        let wrapped_egld_token_id = self.get_wrapped_egld_token_id(wrapper_address);
+
+        let output_payments =
+            self.remove_liquidity_common(payment, first_token_amount_min, second_token_amount_min);
+
+        let first_payment_out =
+            self.send_unwrapping_egld(&caller, output_payments.get(0), &wrapped_egld_token_id);
+        let second_payment_out =
+            self.send_unwrapping_egld(&caller, output_payments.get(1), &wrapped_egld_token_id);
+
+        (first_payment_out, second_payment_out).into()
+    }
+
+    fn send_unwrapping_egld(
+        &self,
+        to: &ManagedAddress,
+        payment: EsdtTokenPayment,
+        wrapped_egld_token_id: &TokenIdentifier,
+    ) -> EgldOrEsdtTokenPayment {
+        if &payment.token_identifier != wrapped_egld_token_id {
+            self.send_payment_non_zero(to, &payment);
+            return payment.into();
+        }
+
+        let egld_amount = payment.amount.clone();
+        if egld_amount > 0 {
+            self.unwrap_egld_payment(payment);
+            self.send().direct_egld(to, &egld_amount);
+        }
+
+        EgldOrEsdtTokenPayment::new(EgldOrEsdtTokenIdentifier::egld(), 0, egld_amount)
+    }
+}
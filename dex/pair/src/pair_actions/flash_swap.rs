@@ -0,0 +1,180 @@
+use crate::{
+    config::MAX_PERCENTAGE, contexts::base::SwapTokensOrder, StorageCache,
+    ERROR_FLASH_SWAP_CALLER_NOT_SC, ERROR_INVALID_ARGS, ERROR_K_INVARIANT_FAILED,
+    ERROR_NOT_ENOUGH_RESERVE, ERROR_SWAP_NOT_ENABLED, ERROR_UNKNOWN_TOKEN,
+};
+
+multiversx_sc::imports!();
+
+#[multiversx_sc::module]
+pub trait FlashSwapModule:
+    crate::liquidity_pool::LiquidityPoolModule
+    + crate::amm::AmmModule
+    + crate::stable_swap::StableSwapModule
+    + crate::events::EventsModule
+    + crate::safe_price::SafePriceModule
+    + crate::safe_price_view::SafePriceViewModule
+    + crate::dynamic_fee::DynamicFeeModule
+    + crate::circuit_breaker::CircuitBreakerModule
+    + crate::read_pair_storage::ReadPairStorageModule
+    + crate::fee::FeeModule
+    + crate::config::ConfigModule
+    + token_send::TokenSendModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+    + super::common_methods::CommonMethodsModule
+{
+    /// Sends `amount_out` of `token_out` to the caller contract, calling `callback_endpoint`
+    /// with `callback_args`. Before the callback returns, the caller has to send back
+    /// either of the pool tokens, so that the pool invariant, after the swap fee is
+    /// deducted from the returned amounts, is not lower than before the flash swap.
+    ///
+    /// No other action that changes the pool reserves can be done while the callback runs.
+    #[endpoint(flashSwap)]
+    fn flash_swap(
+        &self,
+        token_out: TokenIdentifier,
+        amount_out: BigUint,
+        callback_endpoint: ManagedBuffer,
+        callback_args: MultiValueEncoded<ManagedBuffer>,
+    ) {
+        require!(amount_out > 0, ERROR_INVALID_ARGS);
+
+        let caller = self.blockchain().get_caller();
+        require!(
+            self.blockchain().is_smart_contract(&caller),
+            ERROR_FLASH_SWAP_CALLER_NOT_SC
+        );
+
+        let mut storage_cache = StorageCache::new(self);
+        require!(
+            self.can_swap(storage_cache.contract_state),
+            ERROR_SWAP_NOT_ENABLED
+        );
+
+        let out_is_first_token = if token_out == storage_cache.first_token_id {
+            true
+        } else if token_out == storage_cache.second_token_id {
+            false
+        } else {
+            sc_panic!(ERROR_UNKNOWN_TOKEN);
+        };
+
+        let reserve_out = if out_is_first_token {
+            &storage_cache.first_token_reserve
+        } else {
+            &storage_cache.second_token_reserve
+        };
+        require!(*reserve_out > amount_out, ERROR_NOT_ENOUGH_RESERVE);
+
+        self.update_safe_price(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+            &storage_cache.lp_token_supply,
+        );
+        self.update_dynamic_fee(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+        );
+
+        let first_reserve_before = storage_cache.first_token_reserve.clone();
+        let second_reserve_before = storage_cache.second_token_reserve.clone();
+        let initial_k = self.calculate_k_constant(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+        );
+
+        let first_balance_before = self.get_own_balance(&storage_cache.first_token_id);
+        let second_balance_before = self.get_own_balance(&storage_cache.second_token_id);
+
+        self.flash_swap_lock().set(true);
+        let _: IgnoreValue = self
+            .send()
+            .contract_call::<IgnoreValue>(caller.clone(), callback_endpoint)
+            .with_raw_arguments(callback_args.to_arg_buffer())
+            .with_esdt_transfer((token_out, 0, amount_out.clone()))
+            .execute_on_dest_context();
+        self.flash_swap_lock().clear();
+
+        let (first_amount_out, second_amount_out) = if out_is_first_token {
+            (amount_out, BigUint::zero())
+        } else {
+            (BigUint::zero(), amount_out)
+        };
+        let first_amount_in = self.get_flash_swap_amount_in(
+            &storage_cache.first_token_id,
+            &first_balance_before,
+            &first_amount_out,
+        );
+        let second_amount_in = self.get_flash_swap_amount_in(
+            &storage_cache.second_token_id,
+            &second_balance_before,
+            &second_amount_out,
+        );
+
+        // the whole fee is excluded from the reserves used for the invariant check
+        let total_fee_percent = self.get_total_fee_percent();
+        let first_reserve_after_fee = &storage_cache.first_token_reserve - &first_amount_out
+            + &first_amount_in * (MAX_PERCENTAGE - total_fee_percent) / MAX_PERCENTAGE;
+        let second_reserve_after_fee = &storage_cache.second_token_reserve - &second_amount_out
+            + &second_amount_in * (MAX_PERCENTAGE - total_fee_percent) / MAX_PERCENTAGE;
+        let new_k = self.calculate_k_constant(&first_reserve_after_fee, &second_reserve_after_fee);
+        require!(initial_k <= new_k, ERROR_K_INVARIANT_FAILED);
+
+        let mut first_fee_amount = BigUint::zero();
+        let mut second_fee_amount = BigUint::zero();
+        if self.is_fee_enabled() {
+            first_fee_amount = self.get_special_fee_from_input(&first_amount_in);
+            second_fee_amount = self.get_special_fee_from_input(&second_amount_in);
+        }
+
+        storage_cache.first_token_reserve -= &first_amount_out;
+        storage_cache.first_token_reserve += &first_amount_in - &first_fee_amount;
+        storage_cache.second_token_reserve -= &second_amount_out;
+        storage_cache.second_token_reserve += &second_amount_in - &second_fee_amount;
+
+        self.check_circuit_breaker(
+            &first_reserve_before,
+            &second_reserve_before,
+            &storage_cache,
+        );
+
+        let first_token_id = storage_cache.first_token_id.clone();
+        let second_token_id = storage_cache.second_token_id.clone();
+        self.send_fee(
+            &mut storage_cache,
+            SwapTokensOrder::PoolOrder,
+            &first_token_id,
+            &first_fee_amount,
+        );
+        self.send_fee(
+            &mut storage_cache,
+            SwapTokensOrder::ReverseOrder,
+            &second_token_id,
+            &second_fee_amount,
+        );
+
+        self.emit_flash_swap_event(
+            &storage_cache,
+            &caller,
+            [first_amount_out, second_amount_out],
+            [first_amount_in, second_amount_in],
+            [first_fee_amount, second_fee_amount],
+        );
+    }
+
+    fn get_flash_swap_amount_in(
+        &self,
+        token_id: &TokenIdentifier,
+        balance_before: &BigUint,
+        amount_out: &BigUint,
+    ) -> BigUint {
+        let balance_after = self.get_own_balance(token_id);
+        let expected_balance = balance_before - amount_out;
+        if balance_after > expected_balance {
+            balance_after - expected_balance
+        } else {
+            BigUint::zero()
+        }
+    }
+}
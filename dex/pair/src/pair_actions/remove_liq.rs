@@ -12,10 +12,14 @@ multiversx_sc::imports!();
 pub trait RemoveLiquidityModule:
     crate::liquidity_pool::LiquidityPoolModule
     + crate::amm::AmmModule
+    + crate::stable_swap::StableSwapModule
     + crate::contexts::output_builder::OutputBuilderModule
     + crate::locking_wrapper::LockingWrapperModule
     + crate::events::EventsModule
     + crate::safe_price::SafePriceModule
+    + crate::safe_price_view::SafePriceViewModule
+    + crate::circuit_breaker::CircuitBreakerModule
+    + crate::read_pair_storage::ReadPairStorageModule
     + crate::fee::FeeModule
     + crate::config::ConfigModule
     + token_send::TokenSendModule
@@ -31,14 +35,29 @@ pub trait RemoveLiquidityModule:
         first_token_amount_min: BigUint,
         second_token_amount_min: BigUint,
     ) -> RemoveLiquidityResultType<Self::Api> {
+        let caller = self.blockchain().get_caller();
+        let payment = self.call_value().single_esdt();
+        let output_payments =
+            self.remove_liquidity_common(payment, first_token_amount_min, second_token_amount_min);
+
+        self.send_multiple_tokens_if_not_zero(&caller, &output_payments);
+
+        self.build_remove_liq_results(output_payments)
+    }
+
+    /// Removes the liquidity and returns the pool tokens, without sending them.
+    fn remove_liquidity_common(
+        &self,
+        payment: EsdtTokenPayment,
+        first_token_amount_min: BigUint,
+        second_token_amount_min: BigUint,
+    ) -> ManagedVec<EsdtTokenPayment> {
         require!(
             first_token_amount_min > 0 && second_token_amount_min > 0,
             ERROR_INVALID_ARGS
         );
 
         let mut storage_cache = StorageCache::new(self);
-        let caller = self.blockchain().get_caller();
-        let payment = self.call_value().single_esdt();
 
         require!(
             self.is_state_active(storage_cache.contract_state),
@@ -96,11 +115,9 @@ pub trait RemoveLiquidityModule:
             ERROR_SLIPPAGE_ON_REMOVE
         );
 
-        self.send_multiple_tokens_if_not_zero(&caller, &output_payments);
-
         self.emit_remove_liquidity_event(&storage_cache, remove_liq_context);
 
-        self.build_remove_liq_results(output_payments)
+        output_payments
     }
 
     #[payable("*")]
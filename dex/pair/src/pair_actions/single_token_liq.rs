@@ -0,0 +1,429 @@
+use crate::{
+    config::MAX_PERCENTAGE,
+    contexts::{
+        add_liquidity::AddLiquidityContext, base::SwapTokensOrder,
+        remove_liquidity::RemoveLiquidityContext, swap::SwapContext,
+    },
+    StorageCache, ERROR_BAD_PAYMENT_TOKENS, ERROR_INITIAL_LIQUIDITY_NOT_ADDED, ERROR_INVALID_ARGS,
+    ERROR_K_INVARIANT_FAILED, ERROR_LP_TOKEN_NOT_ISSUED, ERROR_SLIPPAGE_EXCEEDED,
+    ERROR_SWAP_NOT_ENABLED, ERROR_SWAP_OUTPUT_LOCKED, ERROR_UNKNOWN_TOKEN,
+};
+
+use super::common_result_types::{
+    AddLiquiditySingleTokenResultType, RemoveLiquiditySingleTokenResultType,
+};
+
+multiversx_sc::imports!();
+
+pub const MAX_SINGLE_TOKEN_SEARCH_ITERATIONS: usize = 32;
+
+#[multiversx_sc::module]
+pub trait SingleTokenLiquidityModule:
+    crate::liquidity_pool::LiquidityPoolModule
+    + crate::amm::AmmModule
+    + crate::stable_swap::StableSwapModule
+    + crate::contexts::output_builder::OutputBuilderModule
+    + crate::locking_wrapper::LockingWrapperModule
+    + crate::events::EventsModule
+    + crate::safe_price::SafePriceModule
+    + crate::safe_price_view::SafePriceViewModule
+    + crate::dynamic_fee::DynamicFeeModule
+    + crate::circuit_breaker::CircuitBreakerModule
+    + crate::read_pair_storage::ReadPairStorageModule
+    + crate::fee::FeeModule
+    + crate::fee_discount::FeeDiscountModule
+    + crate::config::ConfigModule
+    + token_send::TokenSendModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+    + super::common_methods::CommonMethodsModule
+    + super::swap::SwapModule
+    + energy_query::EnergyQueryModule
+    + utils::UtilsModule
+{
+    /// Swaps part of the paid token for the other pool token, then adds liquidity
+    /// with the result. The swapped part is chosen so that the remaining amount
+    /// and the swap output match the pool ratio after the swap.
+    ///
+    /// Returns the LP tokens, followed by the first and second token leftovers.
+    #[payable("*")]
+    #[endpoint(addLiquiditySingleToken)]
+    fn add_liquidity_single_token(
+        &self,
+        min_lp_out: BigUint,
+    ) -> AddLiquiditySingleTokenResultType<Self::Api> {
+        let payment = self.call_value().single_esdt();
+        self.add_liquidity_single_token_from_payment(payment, min_lp_out)
+    }
+
+    fn add_liquidity_single_token_from_payment(
+        &self,
+        payment: EsdtTokenPayment,
+        min_lp_out: BigUint,
+    ) -> AddLiquiditySingleTokenResultType<Self::Api> {
+        require!(min_lp_out > 0, ERROR_INVALID_ARGS);
+
+        let mut storage_cache = StorageCache::new(self);
+        let caller = self.blockchain().get_caller();
+
+        let (token_out, swap_tokens_order) =
+            if payment.token_identifier == storage_cache.first_token_id {
+                (
+                    storage_cache.second_token_id.clone(),
+                    SwapTokensOrder::PoolOrder,
+                )
+            } else if payment.token_identifier == storage_cache.second_token_id {
+                (
+                    storage_cache.first_token_id.clone(),
+                    SwapTokensOrder::ReverseOrder,
+                )
+            } else {
+                sc_panic!(ERROR_UNKNOWN_TOKEN);
+            };
+
+        require!(
+            self.can_swap(storage_cache.contract_state),
+            ERROR_SWAP_NOT_ENABLED
+        );
+        require!(
+            storage_cache.lp_token_id.is_valid_esdt_identifier(),
+            ERROR_LP_TOKEN_NOT_ISSUED
+        );
+        require!(
+            storage_cache.lp_token_supply != 0,
+            ERROR_INITIAL_LIQUIDITY_NOT_ADDED
+        );
+
+        self.update_safe_price(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+            &storage_cache.lp_token_supply,
+        );
+        self.update_dynamic_fee(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+        );
+
+        let first_reserve_before = storage_cache.first_token_reserve.clone();
+        let second_reserve_before = storage_cache.second_token_reserve.clone();
+        let initial_k = self.calculate_k_constant(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+        );
+
+        let swap_amount = self.get_single_token_swap_amount(
+            &payment.amount,
+            storage_cache.get_reserve_in(swap_tokens_order),
+            storage_cache.get_reserve_out(swap_tokens_order),
+            swap_tokens_order,
+        );
+        let mut swap_context = SwapContext::new(
+            payment.token_identifier.clone(),
+            swap_amount.clone(),
+            token_out.clone(),
+            BigUint::from(1u32),
+            swap_tokens_order,
+        );
+        self.perform_swap_fixed_input(&mut swap_context, &mut storage_cache);
+
+        let k_after_swap = self.calculate_k_constant(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+        );
+        require!(initial_k <= k_after_swap, ERROR_K_INVARIANT_FAILED);
+
+        if swap_context.fee_amount > 0 {
+            self.send_fee(
+                &mut storage_cache,
+                swap_context.swap_tokens_order,
+                &swap_context.input_token_id,
+                &swap_context.fee_amount,
+            );
+        }
+
+        let remaining_payment =
+            EsdtTokenPayment::new(payment.token_identifier, 0, &payment.amount - &swap_amount);
+        let swapped_payment =
+            EsdtTokenPayment::new(token_out, 0, swap_context.final_output_amount.clone());
+
+        self.emit_swap_event(&storage_cache, swap_context);
+
+        let (first_payment, second_payment) = match swap_tokens_order {
+            SwapTokensOrder::PoolOrder => (remaining_payment, swapped_payment),
+            SwapTokensOrder::ReverseOrder => (swapped_payment, remaining_payment),
+        };
+        let mut add_liq_context = AddLiquidityContext::new(
+            first_payment,
+            second_payment,
+            BigUint::from(1u32),
+            BigUint::from(1u32),
+        );
+        self.set_optimal_amounts(&mut add_liq_context, &storage_cache);
+
+        add_liq_context.liq_added = self.pool_add_liquidity(
+            &add_liq_context.first_token_optimal_amount,
+            &add_liq_context.second_token_optimal_amount,
+            &mut storage_cache,
+        );
+        require!(
+            add_liq_context.liq_added >= min_lp_out,
+            ERROR_SLIPPAGE_EXCEEDED
+        );
+
+        let new_k = self.calculate_k_constant(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+        );
+        require!(k_after_swap <= new_k, ERROR_K_INVARIANT_FAILED);
+
+        self.check_circuit_breaker(
+            &first_reserve_before,
+            &second_reserve_before,
+            &storage_cache,
+        );
+
+        self.send()
+            .esdt_local_mint(&storage_cache.lp_token_id, 0, &add_liq_context.liq_added);
+
+        let lp_payment = EsdtTokenPayment::new(
+            storage_cache.lp_token_id.clone(),
+            0,
+            add_liq_context.liq_added.clone(),
+        );
+
+        let mut output_payments =
+            self.build_add_liq_output_payments(&storage_cache, &add_liq_context);
+        output_payments.push(lp_payment.clone());
+
+        self.send_multiple_tokens_if_not_zero(&caller, &output_payments);
+
+        self.emit_add_liquidity_event(&storage_cache, add_liq_context);
+
+        (lp_payment, output_payments.get(0), output_payments.get(1)).into()
+    }
+
+    /// Removes liquidity, then swaps the unwanted token against the reserves left
+    /// after the removal, so the caller only receives `token_out`.
+    ///
+    /// Not available while the swap output is locked, as the swapped part would have
+    /// to be sent as a locked token.
+    #[payable("*")]
+    #[endpoint(removeLiquidityToSingleToken)]
+    fn remove_liquidity_to_single_token(
+        &self,
+        token_out: TokenIdentifier,
+        min_amount_out: BigUint,
+    ) -> RemoveLiquiditySingleTokenResultType<Self::Api> {
+        require!(min_amount_out > 0, ERROR_INVALID_ARGS);
+
+        let mut storage_cache = StorageCache::new(self);
+        let caller = self.blockchain().get_caller();
+        let payment = self.call_value().single_esdt();
+
+        require!(
+            self.can_swap(storage_cache.contract_state),
+            ERROR_SWAP_NOT_ENABLED
+        );
+        require!(
+            storage_cache.lp_token_id.is_valid_esdt_identifier(),
+            ERROR_LP_TOKEN_NOT_ISSUED
+        );
+        require!(
+            payment.token_identifier == storage_cache.lp_token_id && payment.amount > 0,
+            ERROR_BAD_PAYMENT_TOKENS
+        );
+        require!(
+            !self.should_generate_locked_asset(),
+            ERROR_SWAP_OUTPUT_LOCKED
+        );
+
+        let (token_in, swap_tokens_order) = if token_out == storage_cache.first_token_id {
+            (
+                storage_cache.second_token_id.clone(),
+                SwapTokensOrder::ReverseOrder,
+            )
+        } else if token_out == storage_cache.second_token_id {
+            (
+                storage_cache.first_token_id.clone(),
+                SwapTokensOrder::PoolOrder,
+            )
+        } else {
+            sc_panic!(ERROR_UNKNOWN_TOKEN);
+        };
+
+        self.update_safe_price(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+            &storage_cache.lp_token_supply,
+        );
+        self.update_dynamic_fee(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+        );
+
+        let first_reserve_before = storage_cache.first_token_reserve.clone();
+        let second_reserve_before = storage_cache.second_token_reserve.clone();
+        let initial_k = self.calculate_k_constant(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+        );
+
+        let mut remove_liq_context =
+            RemoveLiquidityContext::new(payment.amount, BigUint::from(1u64), BigUint::from(1u64));
+        self.pool_remove_liquidity(&mut remove_liq_context, &mut storage_cache);
+
+        let k_after_removal = self.calculate_k_constant(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+        );
+        require!(k_after_removal <= initial_k, ERROR_K_INVARIANT_FAILED);
+
+        self.burn(
+            &storage_cache.lp_token_id,
+            &remove_liq_context.lp_token_payment_amount,
+        );
+
+        let (kept_amount, swap_amount) = match swap_tokens_order {
+            SwapTokensOrder::PoolOrder => (
+                remove_liq_context.second_token_amount_removed.clone(),
+                remove_liq_context.first_token_amount_removed.clone(),
+            ),
+            SwapTokensOrder::ReverseOrder => (
+                remove_liq_context.first_token_amount_removed.clone(),
+                remove_liq_context.second_token_amount_removed.clone(),
+            ),
+        };
+
+        self.emit_remove_liquidity_event(&storage_cache, remove_liq_context);
+
+        let mut swap_context = SwapContext::new(
+            token_in,
+            swap_amount,
+            token_out.clone(),
+            BigUint::from(1u32),
+            swap_tokens_order,
+        );
+        self.perform_swap_fixed_input(&mut swap_context, &mut storage_cache);
+
+        let new_k = self.calculate_k_constant(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+        );
+        require!(k_after_removal <= new_k, ERROR_K_INVARIANT_FAILED);
+
+        self.check_circuit_breaker(
+            &first_reserve_before,
+            &second_reserve_before,
+            &storage_cache,
+        );
+
+        if swap_context.fee_amount > 0 {
+            self.send_fee(
+                &mut storage_cache,
+                swap_context.swap_tokens_order,
+                &swap_context.input_token_id,
+                &swap_context.fee_amount,
+            );
+        }
+
+        let output_amount = kept_amount + &swap_context.final_output_amount;
+        require!(output_amount >= min_amount_out, ERROR_SLIPPAGE_EXCEEDED);
+
+        let output_payment = EsdtTokenPayment::new(token_out, 0, output_amount);
+        self.send()
+            .direct_non_zero_esdt_payment(&caller, &output_payment);
+
+        self.emit_swap_event(&storage_cache, swap_context);
+
+        output_payment
+    }
+
+    /// The swap amount for which the remaining input, relative to the swap output,
+    /// matches the pool ratio after the swap.
+    ///
+    /// For constant product pools, with `r` the part of the input left after the total fee
+    /// and `q` the part of the input kept in the reserves, it is the positive root of
+    /// `r * q * x^2 + (1 + r) * reserve_in * x - amount_in * reserve_in = 0`.
+    fn get_single_token_swap_amount(
+        &self,
+        amount_in: &BigUint,
+        reserve_in: &BigUint,
+        reserve_out: &BigUint,
+        swap_tokens_order: SwapTokensOrder,
+    ) -> BigUint {
+        if self.is_stable_swap() {
+            return self.search_single_token_swap_amount(
+                amount_in,
+                reserve_in,
+                reserve_out,
+                swap_tokens_order,
+            );
+        }
+
+        let special_fee_percent = if self.is_fee_enabled() {
+            self.special_fee_percent().get()
+        } else {
+            0
+        };
+        let max_percentage = BigUint::from(MAX_PERCENTAGE);
+        let fee_factor = BigUint::from(MAX_PERCENTAGE - self.get_total_fee_percent());
+        let kept_factor = BigUint::from(MAX_PERCENTAGE - special_fee_percent);
+
+        let linear_term = (&max_percentage + &fee_factor) * &max_percentage * reserve_in;
+        let discriminant = &linear_term * &linear_term
+            + &fee_factor
+                * &kept_factor
+                * 4u32
+                * amount_in
+                * reserve_in
+                * &max_percentage
+                * &max_percentage;
+        let swap_amount = (discriminant.sqrt() - linear_term) / (fee_factor * kept_factor * 2u32);
+
+        core::cmp::min(swap_amount, amount_in.clone())
+    }
+
+    /// Binary search for the largest swap amount for which the remaining input,
+    /// relative to the swap output, is not below the pool ratio after the swap.
+    ///
+    /// Used for stable swap pools, where each step solves the invariant. The search
+    /// stops after MAX_SINGLE_TOKEN_SEARCH_ITERATIONS steps, and the remaining
+    /// difference is returned to the caller as leftover.
+    fn search_single_token_swap_amount(
+        &self,
+        amount_in: &BigUint,
+        reserve_in: &BigUint,
+        reserve_out: &BigUint,
+        swap_tokens_order: SwapTokensOrder,
+    ) -> BigUint {
+        let is_fee_enabled = self.is_fee_enabled();
+        let mut low = BigUint::zero();
+        let mut high = amount_in.clone();
+        let mut iterations = 0;
+        while low < high && iterations < MAX_SINGLE_TOKEN_SEARCH_ITERATIONS {
+            iterations += 1;
+
+            let mid = (&low + &high + 1u32) / 2u32;
+            let amount_out = self.get_amount_out(&mid, reserve_in, reserve_out, swap_tokens_order);
+            if &amount_out >= reserve_out {
+                high = mid - 1u32;
+                continue;
+            }
+
+            let mut new_reserve_in = reserve_in + &mid;
+            if is_fee_enabled {
+                new_reserve_in -= self.get_special_fee_from_input(&mid);
+            }
+            let new_reserve_out = reserve_out - &amount_out;
+
+            let remaining_in = amount_in - &mid;
+            if remaining_in * new_reserve_out >= amount_out * new_reserve_in {
+                low = mid;
+            } else {
+                high = mid - 1u32;
+            }
+        }
+
+        low
+    }
+}
@@ -0,0 +1,102 @@
+use crate::{
+    StorageCache, ERROR_INITIAL_LIQUIDITY_NOT_ADDED, ERROR_K_INVARIANT_FAILED,
+    ERROR_NOTHING_TO_SKIM,
+};
+
+multiversx_sc::imports!();
+
+#[multiversx_sc::module]
+pub trait SkimSyncModule:
+    crate::amm::AmmModule
+    + crate::stable_swap::StableSwapModule
+    + crate::events::EventsModule
+    + crate::safe_price::SafePriceModule
+    + crate::config::ConfigModule
+    + token_send::TokenSendModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+    + super::common_methods::CommonMethodsModule
+{
+    /// Sends the pool token balances that exceed the recorded reserves to `to`.
+    /// Only allowed after the initial liquidity was added, as the reserves are not
+    /// backed by LP tokens before that.
+    #[endpoint]
+    fn skim(&self, to: ManagedAddress) {
+        self.require_caller_has_owner_or_admin_permissions();
+
+        let storage_cache = StorageCache::new(self);
+        require!(
+            storage_cache.lp_token_supply != 0,
+            ERROR_INITIAL_LIQUIDITY_NOT_ADDED
+        );
+
+        let first_excess = self.get_excess_balance(
+            &storage_cache.first_token_id,
+            &storage_cache.first_token_reserve,
+        );
+        let second_excess = self.get_excess_balance(
+            &storage_cache.second_token_id,
+            &storage_cache.second_token_reserve,
+        );
+        require!(first_excess > 0 || second_excess > 0, ERROR_NOTHING_TO_SKIM);
+
+        let mut payments = ManagedVec::new();
+        payments.push(EsdtTokenPayment::new(
+            storage_cache.first_token_id.clone(),
+            0,
+            first_excess,
+        ));
+        payments.push(EsdtTokenPayment::new(
+            storage_cache.second_token_id.clone(),
+            0,
+            second_excess,
+        ));
+        self.send_multiple_tokens_if_not_zero(&to, &payments);
+
+        self.emit_skim_event(&storage_cache, &to, payments);
+    }
+
+    /// Sets the reserves to the actual pool token balances. The new reserves
+    /// cannot decrease the pool invariant.
+    #[endpoint]
+    fn sync(&self) {
+        self.require_caller_has_owner_or_admin_permissions();
+
+        let mut storage_cache = StorageCache::new(self);
+        require!(
+            storage_cache.lp_token_supply != 0,
+            ERROR_INITIAL_LIQUIDITY_NOT_ADDED
+        );
+
+        self.update_safe_price(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+            &storage_cache.lp_token_supply,
+        );
+
+        let initial_k = self.calculate_k_constant(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+        );
+
+        storage_cache.first_token_reserve = self.get_own_balance(&storage_cache.first_token_id);
+        storage_cache.second_token_reserve = self.get_own_balance(&storage_cache.second_token_id);
+
+        let new_k = self.calculate_k_constant(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+        );
+        require!(initial_k <= new_k, ERROR_K_INVARIANT_FAILED);
+
+        self.emit_sync_event(&storage_cache);
+    }
+
+    fn get_excess_balance(&self, token_id: &TokenIdentifier, reserve: &BigUint) -> BigUint {
+        let balance = self.get_own_balance(token_id);
+        if &balance > reserve {
+            balance - reserve
+        } else {
+            BigUint::zero()
+        }
+    }
+}
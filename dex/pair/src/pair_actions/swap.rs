@@ -19,17 +19,24 @@ pub enum SwapType {
 pub trait SwapModule:
     crate::liquidity_pool::LiquidityPoolModule
     + crate::amm::AmmModule
+    + crate::stable_swap::StableSwapModule
     + crate::contexts::output_builder::OutputBuilderModule
     + crate::locking_wrapper::LockingWrapperModule
     + crate::events::EventsModule
     + crate::safe_price::SafePriceModule
+    + crate::safe_price_view::SafePriceViewModule
+    + crate::dynamic_fee::DynamicFeeModule
+    + crate::circuit_breaker::CircuitBreakerModule
+    + crate::read_pair_storage::ReadPairStorageModule
     + crate::fee::FeeModule
+    + crate::fee_discount::FeeDiscountModule
     + crate::config::ConfigModule
     + token_send::TokenSendModule
     + permissions_module::PermissionsModule
     + pausable::PausableModule
     + super::common_methods::CommonMethodsModule
     + utils::UtilsModule
+    + energy_query::EnergyQueryModule
 {
     #[payable("*")]
     #[endpoint(swapNoFeeAndForward)]
@@ -96,6 +103,7 @@ pub trait SwapModule:
         &self,
         token_out: TokenIdentifier,
         amount_out_min: BigUint,
+        opt_original_caller: OptionalValue<ManagedAddress>,
     ) -> SwapTokensFixedInputResultType<Self::Api> {
         require!(amount_out_min > 0, ERROR_INVALID_ARGS);
 
@@ -117,7 +125,13 @@ pub trait SwapModule:
             &storage_cache.second_token_reserve,
             &storage_cache.lp_token_supply,
         );
+        self.update_dynamic_fee(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+        );
 
+        let first_reserve_before = storage_cache.first_token_reserve.clone();
+        let second_reserve_before = storage_cache.second_token_reserve.clone();
         let initial_k = self.calculate_k_constant(
             &storage_cache.first_token_reserve,
             &storage_cache.second_token_reserve,
@@ -130,6 +144,7 @@ pub trait SwapModule:
             amount_out_min,
             swap_tokens_order,
         );
+        self.set_swap_fee_discount(&mut swap_context, opt_original_caller);
         self.perform_swap_fixed_input(&mut swap_context, &mut storage_cache);
 
         let new_k = self.calculate_k_constant(
@@ -138,6 +153,16 @@ pub trait SwapModule:
         );
         require!(initial_k <= new_k, ERROR_K_INVARIANT_FAILED);
 
+        if let Some(price_deviation_percent) = self.check_swap_circuit_breaker(
+            &first_reserve_before,
+            &second_reserve_before,
+            &storage_cache,
+        ) {
+            storage_cache.first_token_reserve = first_reserve_before;
+            storage_cache.second_token_reserve = second_reserve_before;
+            return self.cancel_swap(&storage_cache, &swap_context, price_deviation_percent);
+        }
+
         if swap_context.fee_amount > 0 {
             self.send_fee(
                 &mut storage_cache,
@@ -168,6 +193,7 @@ pub trait SwapModule:
         &self,
         token_out: TokenIdentifier,
         amount_out: BigUint,
+        opt_original_caller: OptionalValue<ManagedAddress>,
     ) -> SwapTokensFixedOutputResultType<Self::Api> {
         require!(amount_out > 0, ERROR_INVALID_ARGS);
 
@@ -189,7 +215,13 @@ pub trait SwapModule:
             &storage_cache.second_token_reserve,
             &storage_cache.lp_token_supply,
         );
+        self.update_dynamic_fee(
+            &storage_cache.first_token_reserve,
+            &storage_cache.second_token_reserve,
+        );
 
+        let first_reserve_before = storage_cache.first_token_reserve.clone();
+        let second_reserve_before = storage_cache.second_token_reserve.clone();
         let initial_k = self.calculate_k_constant(
             &storage_cache.first_token_reserve,
             &storage_cache.second_token_reserve,
@@ -202,6 +234,7 @@ pub trait SwapModule:
             amount_out,
             swap_tokens_order,
         );
+        self.set_swap_fee_discount(&mut swap_context, opt_original_caller);
         self.perform_swap_fixed_output(&mut swap_context, &mut storage_cache);
 
         let new_k = self.calculate_k_constant(
@@ -210,6 +243,19 @@ pub trait SwapModule:
         );
         require!(initial_k <= new_k, ERROR_K_INVARIANT_FAILED);
 
+        if let Some(price_deviation_percent) = self.check_swap_circuit_breaker(
+            &first_reserve_before,
+            &second_reserve_before,
+            &storage_cache,
+        ) {
+            storage_cache.first_token_reserve = first_reserve_before;
+            storage_cache.second_token_reserve = second_reserve_before;
+            let payment = self.cancel_swap(&storage_cache, &swap_context, price_deviation_percent);
+            let no_leftover =
+                EsdtTokenPayment::new(swap_context.input_token_id, 0, BigUint::zero());
+            return (payment, no_leftover).into();
+        }
+
         if swap_context.fee_amount > 0 {
             self.send_fee(
                 &mut storage_cache,
@@ -229,6 +275,38 @@ pub trait SwapModule:
         self.build_swap_fixed_output_results(output_payments)
     }
 
+    /// Returns the payment of a swap cancelled by the circuit breaker, and pauses swaps.
+    /// The reserves have to be restored before.
+    fn cancel_swap(
+        &self,
+        storage_cache: &StorageCache<Self>,
+        swap_context: &SwapContext<Self::Api>,
+        price_deviation_percent: u64,
+    ) -> EsdtTokenPayment {
+        self.pause_swaps_after_trip(storage_cache, price_deviation_percent);
+
+        let caller = self.blockchain().get_caller();
+        let payment = EsdtTokenPayment::new(
+            swap_context.input_token_id.clone(),
+            0,
+            swap_context.input_token_amount.clone(),
+        );
+        self.send().direct_non_zero_esdt_payment(&caller, &payment);
+
+        payment
+    }
+
+    /// Swaps coming through the router or a trusted proxy get the discount of the original caller.
+    fn set_swap_fee_discount(
+        &self,
+        context: &mut SwapContext<Self::Api>,
+        opt_original_caller: OptionalValue<ManagedAddress>,
+    ) {
+        let original_caller = self.get_swap_original_caller(opt_original_caller);
+        context.fee_discount_percent = self.get_fee_discount_percent(original_caller.clone());
+        context.original_caller = Some(original_caller);
+    }
+
     fn perform_swap_fixed_input(
         &self,
         context: &mut SwapContext<Self::Api>,
@@ -239,8 +317,15 @@ pub trait SwapModule:
         let reserve_in = storage_cache.get_reserve_in(context.swap_tokens_order);
         let reserve_out = storage_cache.get_reserve_out(context.swap_tokens_order);
 
-        let amount_out_optimal =
-            self.get_amount_out(&context.input_token_amount, reserve_in, reserve_out);
+        context.total_fee_percent =
+            self.apply_fee_discount(self.get_total_fee_percent(), context.fee_discount_percent);
+        let amount_out_optimal = self.get_amount_out_with_fee_percent(
+            &context.input_token_amount,
+            reserve_in,
+            reserve_out,
+            context.swap_tokens_order,
+            context.total_fee_percent,
+        );
         require!(
             amount_out_optimal >= context.output_token_amount,
             ERROR_SLIPPAGE_EXCEEDED
@@ -252,7 +337,11 @@ pub trait SwapModule:
 
         let mut amount_in_after_fee = context.input_token_amount.clone();
         if self.is_fee_enabled() {
-            let fee_amount = self.get_special_fee_from_input(&amount_in_after_fee);
+            let special_fee_percent = self.apply_fee_discount(
+                self.special_fee_percent().get(),
+                context.fee_discount_percent,
+            );
+            let fee_amount = self.get_fee_from_input(&amount_in_after_fee, special_fee_percent);
             amount_in_after_fee -= &fee_amount;
 
             context.fee_amount = fee_amount;
@@ -273,8 +362,15 @@ pub trait SwapModule:
         let reserve_in = storage_cache.get_reserve_in(context.swap_tokens_order);
         let reserve_out = storage_cache.get_reserve_out(context.swap_tokens_order);
 
-        let amount_in_optimal =
-            self.get_amount_in(&context.output_token_amount, reserve_in, reserve_out);
+        context.total_fee_percent =
+            self.apply_fee_discount(self.get_total_fee_percent(), context.fee_discount_percent);
+        let amount_in_optimal = self.get_amount_in_with_fee_percent(
+            &context.output_token_amount,
+            reserve_in,
+            reserve_out,
+            context.swap_tokens_order,
+            context.total_fee_percent,
+        );
         require!(
             amount_in_optimal <= context.input_token_amount,
             ERROR_SLIPPAGE_EXCEEDED
@@ -285,7 +381,12 @@ pub trait SwapModule:
 
         let mut amount_in_optimal_after_fee = amount_in_optimal;
         if self.is_fee_enabled() {
-            let fee_amount = self.get_special_fee_from_input(&amount_in_optimal_after_fee);
+            let special_fee_percent = self.apply_fee_discount(
+                self.special_fee_percent().get(),
+                context.fee_discount_percent,
+            );
+            let fee_amount =
+                self.get_fee_from_input(&amount_in_optimal_after_fee, special_fee_percent);
             amount_in_optimal_after_fee -= &fee_amount;
 
             context.fee_amount = fee_amount;
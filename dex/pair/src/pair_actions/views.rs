@@ -1,21 +1,43 @@
-use crate::{ERROR_NOT_ENOUGH_RESERVE, ERROR_UNKNOWN_TOKEN, ERROR_ZERO_AMOUNT};
+use crate::{
+    config::MAX_PERCENTAGE, contexts::base::SwapTokensOrder, ERROR_BAD_PRICE_IMPACT,
+    ERROR_NOT_ENOUGH_RESERVE, ERROR_TOO_MANY_QUOTES, ERROR_UNKNOWN_TOKEN, ERROR_ZERO_AMOUNT,
+};
 
 multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+pub const MAX_SWAP_QUOTES: usize = 100;
+pub const PRICE_PRECISION: u64 = 1_000_000_000_000_000_000;
+pub const MAX_PRICE_IMPACT_SEARCH_STEPS: usize = 64;
+
+#[derive(
+    ManagedVecItem, TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Debug,
+)]
+pub struct SwapQuote<M: ManagedTypeApi> {
+    pub amount_in: BigUint<M>,
+    pub amount_out: BigUint<M>,
+    pub effective_price: BigUint<M>,
+    pub price_impact: u64,
+    pub fee_amount: BigUint<M>,
+}
 
 #[multiversx_sc::module]
 pub trait ViewsModule:
     crate::liquidity_pool::LiquidityPoolModule
     + crate::amm::AmmModule
+    + crate::stable_swap::StableSwapModule
     + crate::contexts::output_builder::OutputBuilderModule
     + crate::locking_wrapper::LockingWrapperModule
     + crate::events::EventsModule
     + crate::safe_price::SafePriceModule
     + crate::fee::FeeModule
+    + crate::fee_discount::FeeDiscountModule
     + crate::config::ConfigModule
     + token_send::TokenSendModule
     + permissions_module::PermissionsModule
     + pausable::PausableModule
     + super::common_methods::CommonMethodsModule
+    + energy_query::EnergyQueryModule
 {
     #[view(getTokensForGivenPosition)]
     fn get_tokens_for_given_position(
@@ -37,6 +59,44 @@ pub trait ViewsModule:
 
     #[view(getAmountOut)]
     fn get_amount_out_view(&self, token_in: TokenIdentifier, amount_in: BigUint) -> BigUint {
+        self.compute_amount_out(token_in, amount_in, self.get_total_fee_percent())
+    }
+
+    /// Same as getAmountOut, with the fee discount of the given address applied.
+    #[view(getAmountOutForAddress)]
+    fn get_amount_out_for_address_view(
+        &self,
+        address: ManagedAddress,
+        token_in: TokenIdentifier,
+        amount_in: BigUint,
+    ) -> BigUint {
+        let (total_fee_percent, _) = self.get_fee_percents_for_address(address).into_tuple();
+        self.compute_amount_out(token_in, amount_in, total_fee_percent)
+    }
+
+    #[view(getAmountIn)]
+    fn get_amount_in_view(&self, token_wanted: TokenIdentifier, amount_wanted: BigUint) -> BigUint {
+        self.compute_amount_in(token_wanted, amount_wanted, self.get_total_fee_percent())
+    }
+
+    /// Same as getAmountIn, with the fee discount of the given address applied.
+    #[view(getAmountInForAddress)]
+    fn get_amount_in_for_address_view(
+        &self,
+        address: ManagedAddress,
+        token_wanted: TokenIdentifier,
+        amount_wanted: BigUint,
+    ) -> BigUint {
+        let (total_fee_percent, _) = self.get_fee_percents_for_address(address).into_tuple();
+        self.compute_amount_in(token_wanted, amount_wanted, total_fee_percent)
+    }
+
+    fn compute_amount_out(
+        &self,
+        token_in: TokenIdentifier,
+        amount_in: BigUint,
+        total_fee_percent: u64,
+    ) -> BigUint {
         require!(amount_in > 0u64, ERROR_ZERO_AMOUNT);
 
         let first_token_id = self.first_token_id().get();
@@ -46,14 +106,24 @@ pub trait ViewsModule:
 
         if token_in == first_token_id {
             require!(second_token_reserve > 0u64, ERROR_NOT_ENOUGH_RESERVE);
-            let amount_out =
-                self.get_amount_out(&amount_in, &first_token_reserve, &second_token_reserve);
+            let amount_out = self.get_amount_out_with_fee_percent(
+                &amount_in,
+                &first_token_reserve,
+                &second_token_reserve,
+                SwapTokensOrder::PoolOrder,
+                total_fee_percent,
+            );
             require!(second_token_reserve > amount_out, ERROR_NOT_ENOUGH_RESERVE);
             amount_out
         } else if token_in == second_token_id {
             require!(first_token_reserve > 0u64, ERROR_NOT_ENOUGH_RESERVE);
-            let amount_out =
-                self.get_amount_out(&amount_in, &second_token_reserve, &first_token_reserve);
+            let amount_out = self.get_amount_out_with_fee_percent(
+                &amount_in,
+                &second_token_reserve,
+                &first_token_reserve,
+                SwapTokensOrder::ReverseOrder,
+                total_fee_percent,
+            );
             require!(first_token_reserve > amount_out, ERROR_NOT_ENOUGH_RESERVE);
             amount_out
         } else {
@@ -61,8 +131,12 @@ pub trait ViewsModule:
         }
     }
 
-    #[view(getAmountIn)]
-    fn get_amount_in_view(&self, token_wanted: TokenIdentifier, amount_wanted: BigUint) -> BigUint {
+    fn compute_amount_in(
+        &self,
+        token_wanted: TokenIdentifier,
+        amount_wanted: BigUint,
+        total_fee_percent: u64,
+    ) -> BigUint {
         require!(amount_wanted > 0u64, ERROR_ZERO_AMOUNT);
 
         let first_token_id = self.first_token_id().get();
@@ -76,14 +150,189 @@ pub trait ViewsModule:
                 ERROR_NOT_ENOUGH_RESERVE
             );
 
-            self.get_amount_in(&amount_wanted, &second_token_reserve, &first_token_reserve)
+            self.get_amount_in_with_fee_percent(
+                &amount_wanted,
+                &second_token_reserve,
+                &first_token_reserve,
+                SwapTokensOrder::ReverseOrder,
+                total_fee_percent,
+            )
         } else if token_wanted == second_token_id {
             require!(
                 second_token_reserve > amount_wanted,
                 ERROR_NOT_ENOUGH_RESERVE
             );
 
-            self.get_amount_in(&amount_wanted, &first_token_reserve, &second_token_reserve)
+            self.get_amount_in_with_fee_percent(
+                &amount_wanted,
+                &first_token_reserve,
+                &second_token_reserve,
+                SwapTokensOrder::PoolOrder,
+                total_fee_percent,
+            )
+        } else {
+            sc_panic!(ERROR_UNKNOWN_TOKEN);
+        }
+    }
+
+    /// Quotes a swap of each of the given amounts of `token_in`. The effective price is
+    /// the output per unit of input, scaled by PRICE_PRECISION. The price impact, out of
+    /// MAX_PERCENTAGE, compares the output before fees to the output at the reserves ratio,
+    /// i.e. the getEquivalent amount. The fee amount is in `token_in`.
+    #[view(getSwapQuotes)]
+    fn get_swap_quotes(
+        &self,
+        token_in: TokenIdentifier,
+        amounts_in: MultiValueEncoded<BigUint>,
+    ) -> MultiValueEncoded<SwapQuote<Self::Api>> {
+        require!(amounts_in.len() <= MAX_SWAP_QUOTES, ERROR_TOO_MANY_QUOTES);
+
+        let (reserve_in, reserve_out, swap_tokens_order) = self.get_swap_reserves(&token_in);
+        let total_fee_percent = self.get_total_fee_percent();
+        let mut quotes = MultiValueEncoded::new();
+        for amount_in in amounts_in {
+            require!(amount_in > 0u64, ERROR_ZERO_AMOUNT);
+
+            let amount_out = self.get_amount_out_with_fee_percent(
+                &amount_in,
+                &reserve_in,
+                &reserve_out,
+                swap_tokens_order,
+                total_fee_percent,
+            );
+            require!(reserve_out > amount_out, ERROR_NOT_ENOUGH_RESERVE);
+
+            let effective_price = &amount_out * PRICE_PRECISION / &amount_in;
+            let price_impact =
+                self.get_price_impact(&amount_in, &reserve_in, &reserve_out, swap_tokens_order);
+            let fee_amount = self.get_fee_from_input(&amount_in, total_fee_percent);
+            quotes.push(SwapQuote {
+                amount_in,
+                amount_out,
+                effective_price,
+                price_impact,
+                fee_amount,
+            });
+        }
+
+        quotes
+    }
+
+    /// The largest amount of `token_in` that can be swapped with a price impact of at most
+    /// `max_price_impact`, out of MAX_PERCENTAGE, as computed by getSwapQuotes.
+    #[view(getMaxAmountInForPriceImpact)]
+    fn get_max_amount_in_for_price_impact(
+        &self,
+        token_in: TokenIdentifier,
+        max_price_impact: u64,
+    ) -> BigUint {
+        require!(
+            max_price_impact > 0 && max_price_impact < MAX_PERCENTAGE,
+            ERROR_BAD_PRICE_IMPACT
+        );
+
+        let (reserve_in, reserve_out, swap_tokens_order) = self.get_swap_reserves(&token_in);
+        if self.is_stable_swap() {
+            return self.search_max_amount_in(
+                &reserve_in,
+                &reserve_out,
+                swap_tokens_order,
+                max_price_impact,
+            );
+        }
+
+        reserve_in * max_price_impact / (MAX_PERCENTAGE - max_price_impact)
+    }
+
+    /// The stable swap impact has no closed form, but it grows with the amount,
+    /// so the amount is searched for. The result is rounded down.
+    fn search_max_amount_in(
+        &self,
+        reserve_in: &BigUint,
+        reserve_out: &BigUint,
+        swap_tokens_order: SwapTokensOrder,
+        max_price_impact: u64,
+    ) -> BigUint {
+        let mut low = BigUint::zero();
+        let mut high = reserve_in.clone();
+        let mut steps = 0;
+        while self.get_price_impact(&high, reserve_in, reserve_out, swap_tokens_order)
+            <= max_price_impact
+        {
+            steps += 1;
+            if steps == MAX_PRICE_IMPACT_SEARCH_STEPS {
+                return high;
+            }
+
+            low = high.clone();
+            high *= 2u64;
+        }
+
+        for _ in 0..MAX_PRICE_IMPACT_SEARCH_STEPS {
+            let mid = (&low + &high) / 2u64;
+            if mid == low {
+                break;
+            }
+
+            if self.get_price_impact(&mid, reserve_in, reserve_out, swap_tokens_order)
+                <= max_price_impact
+            {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+
+        low
+    }
+
+    /// Swapping amount_in through a constant product pool has an impact of
+    /// amount_in / (reserve_in + amount_in), which is used as is to avoid rounding errors.
+    fn get_price_impact(
+        &self,
+        amount_in: &BigUint,
+        reserve_in: &BigUint,
+        reserve_out: &BigUint,
+        swap_tokens_order: SwapTokensOrder,
+    ) -> u64 {
+        if !self.is_stable_swap() {
+            let price_impact = amount_in * MAX_PERCENTAGE / (reserve_in + amount_in);
+            return price_impact.to_u64().unwrap_or(MAX_PERCENTAGE);
+        }
+
+        let spot_amount_out = self.quote(amount_in, reserve_in, reserve_out);
+        let amount_out =
+            self.get_amount_out_no_fee(amount_in, reserve_in, reserve_out, swap_tokens_order);
+        if spot_amount_out == 0u64 || amount_out >= spot_amount_out {
+            return 0;
+        }
+
+        let price_impact = (&spot_amount_out - &amount_out) * MAX_PERCENTAGE / spot_amount_out;
+        price_impact.to_u64().unwrap_or(MAX_PERCENTAGE)
+    }
+
+    fn get_swap_reserves(&self, token_in: &TokenIdentifier) -> (BigUint, BigUint, SwapTokensOrder) {
+        let first_token_id = self.first_token_id().get();
+        let second_token_id = self.second_token_id().get();
+        let first_token_reserve = self.pair_reserve(&first_token_id).get();
+        let second_token_reserve = self.pair_reserve(&second_token_id).get();
+        require!(
+            first_token_reserve > 0u64 && second_token_reserve > 0u64,
+            ERROR_NOT_ENOUGH_RESERVE
+        );
+
+        if token_in == &first_token_id {
+            (
+                first_token_reserve,
+                second_token_reserve,
+                SwapTokensOrder::PoolOrder,
+            )
+        } else if token_in == &second_token_id {
+            (
+                second_token_reserve,
+                first_token_reserve,
+                SwapTokensOrder::ReverseOrder,
+            )
         } else {
             sc_panic!(ERROR_UNKNOWN_TOKEN);
         }
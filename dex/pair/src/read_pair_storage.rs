@@ -1,5 +1,7 @@
 use multiversx_sc::storage::StorageKey;
 
+use pausable::State;
+
 use crate::safe_price::PriceObservation;
 
 multiversx_sc::imports!();
@@ -10,6 +12,7 @@ pub static SECOND_TOKEN_ID_STORAGE_KEY: &[u8] = b"second_token_id";
 pub static SAFE_PRICE_CURRENT_INDEX_STORAGE_KEY: &[u8] = b"safe_price_current_index";
 pub static PRICE_OBSERVATIONS_STORAGE_KEY: &[u8] = b"price_observations";
 pub static PAIR_RESERVE_BASE_STORAGE_KEY: &[u8] = b"reserve";
+pub static STATE_STORAGE_KEY: &[u8] = b"state";
 
 #[multiversx_sc::module]
 pub trait ReadPairStorageModule {
@@ -73,4 +76,14 @@ pub trait ReadPairStorageModule {
 
         SingleValueMapper::<_, _, ManagedAddress>::new_from_address(pair_address, storage_key)
     }
+
+    fn get_pair_state_mapper(
+        &self,
+        pair_address: ManagedAddress,
+    ) -> SingleValueMapper<State, ManagedAddress> {
+        SingleValueMapper::<_, _, ManagedAddress>::new_from_address(
+            pair_address,
+            StorageKey::new(STATE_STORAGE_KEY),
+        )
+    }
 }
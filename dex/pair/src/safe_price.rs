@@ -3,7 +3,7 @@ multiversx_sc::derive_imports!();
 
 use multiversx_sc::codec::{NestedDecodeInput, TopDecodeInput};
 
-use crate::{amm, config, errors::ERROR_SAFE_PRICE_CURRENT_INDEX};
+use crate::{amm, config, errors::ERROR_SAFE_PRICE_CURRENT_INDEX, stable_swap};
 
 pub type Round = u64;
 
@@ -72,6 +72,7 @@ pub trait SafePriceModule:
     config::ConfigModule
     + token_send::TokenSendModule
     + amm::AmmModule
+    + stable_swap::StableSwapModule
     + permissions_module::PermissionsModule
     + pausable::PausableModule
 {
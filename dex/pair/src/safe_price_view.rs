@@ -4,10 +4,12 @@ use common_errors::{ERROR_BAD_INPUT_TOKEN, ERROR_PARAMETERS};
 use core::cmp::Ordering;
 
 use crate::{
-    amm, config,
+    amm,
+    config::{self, MAX_PERCENTAGE},
     errors::{ERROR_SAFE_PRICE_OBSERVATION_DOES_NOT_EXIST, ERROR_SAFE_PRICE_SAME_ROUNDS},
     read_pair_storage,
     safe_price::{self, PriceObservation, Round, MAX_OBSERVATIONS},
+    stable_swap,
 };
 
 pub const DEFAULT_SAFE_PRICE_ROUNDS_OFFSET: u64 = 10 * 60;
@@ -25,6 +27,7 @@ pub trait SafePriceViewModule:
     + config::ConfigModule
     + token_send::TokenSendModule
     + amm::AmmModule
+    + stable_swap::StableSwapModule
     + permissions_module::PermissionsModule
     + pausable::PausableModule
     + read_pair_storage::ReadPairStorageModule
@@ -517,6 +520,55 @@ pub trait SafePriceViewModule:
         }
     }
 
+    /// Relative difference between the spot price and the safe price, in MAX_PERCENTAGE units.
+    /// Returns None if there are not enough price observations for the given offset.
+    fn get_price_divergence_percent(
+        &self,
+        round_offset: Round,
+        first_token_reserve: &BigUint,
+        second_token_reserve: &BigUint,
+    ) -> Option<u64> {
+        if first_token_reserve == &0 || second_token_reserve == &0 {
+            return None;
+        }
+
+        let current_round = self.blockchain().get_block_round();
+        if round_offset >= current_round {
+            return None;
+        }
+
+        let pair_address = self.blockchain().get_sc_address();
+        let price_observations = self.get_price_observation_mapper(pair_address.clone());
+        if price_observations.is_empty() {
+            return None;
+        }
+
+        let safe_price_current_index = self.safe_price_current_index().get();
+        let oldest_price_observation =
+            self.get_oldest_price_observation(safe_price_current_index, &price_observations);
+        if oldest_price_observation.recording_round > current_round - round_offset {
+            return None;
+        }
+
+        let safe_price = self.get_safe_price_by_round_offset(
+            pair_address,
+            round_offset,
+            EsdtTokenPayment::new(self.first_token_id().get(), 0, first_token_reserve.clone()),
+        );
+        if safe_price.amount == 0 {
+            return None;
+        }
+
+        let price_difference = if second_token_reserve > &safe_price.amount {
+            second_token_reserve - &safe_price.amount
+        } else {
+            &safe_price.amount - second_token_reserve
+        };
+        let divergence = price_difference * MAX_PERCENTAGE / safe_price.amount;
+
+        Some(divergence.to_u64().unwrap_or(u64::MAX))
+    }
+
     fn get_default_offset_rounds(&self, pair_address: &ManagedAddress, end_round: Round) -> u64 {
         let safe_price_current_index = self
             .get_safe_price_current_index_mapper(pair_address.clone())
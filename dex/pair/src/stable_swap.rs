@@ -0,0 +1,342 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::contexts::base::SwapTokensOrder;
+use crate::errors::*;
+
+use super::config;
+
+pub const AMP_PRECISION: u64 = 100;
+pub const MAX_AMP: u64 = 1_000_000;
+pub const MAX_AMP_CHANGE: u64 = 10;
+pub const MIN_RAMP_DURATION: u64 = 86_400;
+pub const NR_POOL_TOKENS: u64 = 2;
+pub const MAX_NEWTON_ITERATIONS: usize = 255;
+
+#[derive(
+    TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Copy, Debug,
+)]
+pub enum PoolType {
+    ConstantProduct,
+    StableSwap,
+}
+
+#[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Default, Debug)]
+pub struct AmplificationRamp {
+    pub initial_amp: u64,
+    pub future_amp: u64,
+    pub initial_timestamp: u64,
+    pub future_timestamp: u64,
+}
+
+#[multiversx_sc::module]
+pub trait StableSwapModule:
+    config::ConfigModule
+    + token_send::TokenSendModule
+    + permissions_module::PermissionsModule
+    + pausable::PausableModule
+{
+    /// Switches the pool to the StableSwap invariant. `amplification` is the A coefficient,
+    /// scaled by AMP_PRECISION (i.e. 100 * A), and has to be at least 1. Only allowed before
+    /// any liquidity was added.
+    ///
+    /// The reserves are multiplied by the rate multipliers before solving the invariant,
+    /// so that tokens with different decimals are compared at the same precision,
+    /// e.g. 10^12 and 1 for a pool of a 6 decimals token and an 18 decimals one.
+    #[endpoint(enableStableSwap)]
+    fn enable_stable_swap(
+        &self,
+        amplification: u64,
+        first_token_rate_multiplier: BigUint,
+        second_token_rate_multiplier: BigUint,
+    ) {
+        self.require_caller_has_owner_permissions();
+        require!(
+            self.pool_type().get() == PoolType::ConstantProduct,
+            ERROR_ALREADY_STABLE_SWAP
+        );
+        require!(self.lp_token_supply().is_empty(), ERROR_POOL_NOT_EMPTY);
+        require!(
+            (AMP_PRECISION..=MAX_AMP * AMP_PRECISION).contains(&amplification),
+            ERROR_BAD_AMPLIFICATION
+        );
+        require!(
+            first_token_rate_multiplier > 0u64 && second_token_rate_multiplier > 0u64,
+            ERROR_BAD_RATE_MULTIPLIER
+        );
+
+        let current_timestamp = self.blockchain().get_block_timestamp();
+        self.amplification_ramp().set(AmplificationRamp {
+            initial_amp: amplification,
+            future_amp: amplification,
+            initial_timestamp: current_timestamp,
+            future_timestamp: current_timestamp,
+        });
+        self.first_token_rate_multiplier()
+            .set(first_token_rate_multiplier);
+        self.second_token_rate_multiplier()
+            .set(second_token_rate_multiplier);
+        self.pool_type().set(PoolType::StableSwap);
+    }
+
+    /// Linearly ramps the amplification coefficient to `future_amp` until `future_timestamp`.
+    /// The coefficient can change by at most MAX_AMP_CHANGE times in a single ramp.
+    #[endpoint(rampAmplification)]
+    fn ramp_amplification(&self, future_amp: u64, future_timestamp: u64) {
+        self.require_caller_has_owner_or_admin_permissions();
+        self.require_stable_swap();
+        require!(
+            (AMP_PRECISION..=MAX_AMP * AMP_PRECISION).contains(&future_amp),
+            ERROR_BAD_AMPLIFICATION
+        );
+
+        let current_timestamp = self.blockchain().get_block_timestamp();
+        require!(
+            future_timestamp >= current_timestamp + MIN_RAMP_DURATION,
+            ERROR_RAMP_TOO_SHORT
+        );
+
+        let current_amp = self.get_current_amplification();
+        if future_amp < current_amp {
+            require!(
+                future_amp * MAX_AMP_CHANGE >= current_amp,
+                ERROR_AMP_CHANGE_TOO_BIG
+            );
+        } else {
+            require!(
+                future_amp <= current_amp * MAX_AMP_CHANGE,
+                ERROR_AMP_CHANGE_TOO_BIG
+            );
+        }
+
+        self.amplification_ramp().set(AmplificationRamp {
+            initial_amp: current_amp,
+            future_amp,
+            initial_timestamp: current_timestamp,
+            future_timestamp,
+        });
+    }
+
+    #[endpoint(stopRampAmplification)]
+    fn stop_ramp_amplification(&self) {
+        self.require_caller_has_owner_or_admin_permissions();
+        self.require_stable_swap();
+
+        let current_amp = self.get_current_amplification();
+        let current_timestamp = self.blockchain().get_block_timestamp();
+        self.amplification_ramp().set(AmplificationRamp {
+            initial_amp: current_amp,
+            future_amp: current_amp,
+            initial_timestamp: current_timestamp,
+            future_timestamp: current_timestamp,
+        });
+    }
+
+    #[view(getAmplification)]
+    fn get_current_amplification(&self) -> u64 {
+        let ramp = self.amplification_ramp().get();
+        let current_timestamp = self.blockchain().get_block_timestamp();
+        if current_timestamp >= ramp.future_timestamp {
+            return ramp.future_amp;
+        }
+
+        let elapsed = current_timestamp - ramp.initial_timestamp;
+        let duration = ramp.future_timestamp - ramp.initial_timestamp;
+        if ramp.future_amp > ramp.initial_amp {
+            ramp.initial_amp + (ramp.future_amp - ramp.initial_amp) * elapsed / duration
+        } else {
+            ramp.initial_amp - (ramp.initial_amp - ramp.future_amp) * elapsed / duration
+        }
+    }
+
+    #[inline]
+    fn is_stable_swap(&self) -> bool {
+        self.pool_type().get() == PoolType::StableSwap
+    }
+
+    fn require_stable_swap(&self) {
+        require!(self.is_stable_swap(), ERROR_NOT_STABLE_SWAP);
+    }
+
+    /// Computes the StableSwap invariant D for the given reserves using Newton's method.
+    fn compute_stable_invariant(
+        &self,
+        first_reserve: &BigUint,
+        second_reserve: &BigUint,
+        amplification: u64,
+    ) -> BigUint {
+        let sum = first_reserve + second_reserve;
+        if sum == 0u64 || first_reserve == &0u64 || second_reserve == &0u64 {
+            return BigUint::zero();
+        }
+
+        let amp_times_n = BigUint::from(amplification * NR_POOL_TOKENS);
+        let amp_precision = BigUint::from(AMP_PRECISION);
+        let mut invariant = sum.clone();
+        for _ in 0..MAX_NEWTON_ITERATIONS {
+            let mut invariant_product = invariant.clone();
+            invariant_product = invariant_product * &invariant / (first_reserve * NR_POOL_TOKENS);
+            invariant_product = invariant_product * &invariant / (second_reserve * NR_POOL_TOKENS);
+
+            let previous_invariant = invariant.clone();
+            let numerator = (&amp_times_n * &sum / &amp_precision
+                + &invariant_product * NR_POOL_TOKENS)
+                * &invariant;
+            let denominator = (&amp_times_n - &amp_precision) * &invariant / &amp_precision
+                + invariant_product * (NR_POOL_TOKENS + 1);
+            invariant = numerator / denominator;
+
+            if self.is_within_one(&invariant, &previous_invariant) {
+                return invariant;
+            }
+        }
+
+        sc_panic!(ERROR_STABLE_SWAP_NO_CONVERGENCE);
+    }
+
+    /// Computes the reserve of one token, given the reserve of the other token and the invariant.
+    fn compute_stable_reserve(
+        &self,
+        other_reserve: &BigUint,
+        invariant: &BigUint,
+        amplification: u64,
+    ) -> BigUint {
+        require!(other_reserve > &0u64, ERROR_ZERO_AMOUNT);
+
+        let amp_times_n = BigUint::from(amplification * NR_POOL_TOKENS);
+        let amp_precision = BigUint::from(AMP_PRECISION);
+
+        let mut c = invariant * invariant / (other_reserve * NR_POOL_TOKENS);
+        c = c * invariant * &amp_precision / (&amp_times_n * NR_POOL_TOKENS);
+        let b = other_reserve + &(invariant * &amp_precision / &amp_times_n);
+
+        let mut reserve = invariant.clone();
+        for _ in 0..MAX_NEWTON_ITERATIONS {
+            let previous_reserve = reserve.clone();
+            let numerator = &reserve * &reserve + &c;
+            let denominator = &reserve * 2u64 + &b - invariant;
+            reserve = numerator / denominator;
+
+            if self.is_within_one(&reserve, &previous_reserve) {
+                return reserve;
+            }
+        }
+
+        sc_panic!(ERROR_STABLE_SWAP_NO_CONVERGENCE);
+    }
+
+    /// The invariant of the reserves, multiplied by their rate multipliers.
+    fn get_stable_invariant(
+        &self,
+        first_token_reserve: &BigUint,
+        second_token_reserve: &BigUint,
+        amplification: u64,
+    ) -> BigUint {
+        self.compute_stable_invariant(
+            &(first_token_reserve * &self.first_token_rate_multiplier().get()),
+            &(second_token_reserve * &self.second_token_rate_multiplier().get()),
+            amplification,
+        )
+    }
+
+    fn get_stable_rate_multipliers(
+        &self,
+        swap_tokens_order: SwapTokensOrder,
+    ) -> (BigUint, BigUint) {
+        let first_token_rate_multiplier = self.first_token_rate_multiplier().get();
+        let second_token_rate_multiplier = self.second_token_rate_multiplier().get();
+        match swap_tokens_order {
+            SwapTokensOrder::PoolOrder => {
+                (first_token_rate_multiplier, second_token_rate_multiplier)
+            }
+            SwapTokensOrder::ReverseOrder => {
+                (second_token_rate_multiplier, first_token_rate_multiplier)
+            }
+        }
+    }
+
+    fn get_stable_amount_out(
+        &self,
+        amount_in: &BigUint,
+        reserve_in: &BigUint,
+        reserve_out: &BigUint,
+        swap_tokens_order: SwapTokensOrder,
+    ) -> BigUint {
+        let (rate_in, rate_out) = self.get_stable_rate_multipliers(swap_tokens_order);
+        let amplification = self.get_current_amplification();
+        let invariant = self.compute_stable_invariant(
+            &(reserve_in * &rate_in),
+            &(reserve_out * &rate_out),
+            amplification,
+        );
+        let new_reserve_in = (reserve_in + amount_in) * &rate_in;
+        let new_reserve_out = self.div_ceil(
+            self.compute_stable_reserve(&new_reserve_in, &invariant, amplification),
+            &rate_out,
+        );
+
+        // one unit is kept in the pool to account for rounding
+        let min_reserve_out = new_reserve_out + 1u64;
+        if reserve_out > &min_reserve_out {
+            reserve_out - &min_reserve_out
+        } else {
+            BigUint::zero()
+        }
+    }
+
+    fn get_stable_amount_in(
+        &self,
+        amount_out: &BigUint,
+        reserve_in: &BigUint,
+        reserve_out: &BigUint,
+        swap_tokens_order: SwapTokensOrder,
+    ) -> BigUint {
+        require!(reserve_out > amount_out, ERROR_NOT_ENOUGH_RESERVE);
+
+        let (rate_in, rate_out) = self.get_stable_rate_multipliers(swap_tokens_order);
+        let amplification = self.get_current_amplification();
+        let invariant = self.compute_stable_invariant(
+            &(reserve_in * &rate_in),
+            &(reserve_out * &rate_out),
+            amplification,
+        );
+        let new_reserve_out = (reserve_out - amount_out) * &rate_out;
+        let new_reserve_in = self.div_ceil(
+            self.compute_stable_reserve(&new_reserve_out, &invariant, amplification),
+            &rate_in,
+        );
+        require!(&new_reserve_in > reserve_in, ERROR_ZERO_AMOUNT);
+
+        new_reserve_in - reserve_in + 1u64
+    }
+
+    /// The reserves computed from the invariant are rounded up, in favor of the pool.
+    fn div_ceil(&self, amount: BigUint, rate_multiplier: &BigUint) -> BigUint {
+        (amount + rate_multiplier - 1u64) / rate_multiplier
+    }
+
+    #[inline]
+    fn is_within_one(&self, first: &BigUint, second: &BigUint) -> bool {
+        if first > second {
+            first - second <= 1u64
+        } else {
+            second - first <= 1u64
+        }
+    }
+
+    #[view(getPoolType)]
+    #[storage_mapper("pool_type")]
+    fn pool_type(&self) -> SingleValueMapper<PoolType>;
+
+    #[view(getAmplificationRamp)]
+    #[storage_mapper("amplification_ramp")]
+    fn amplification_ramp(&self) -> SingleValueMapper<AmplificationRamp>;
+
+    #[view(getFirstTokenRateMultiplier)]
+    #[storage_mapper("first_token_rate_multiplier")]
+    fn first_token_rate_multiplier(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getSecondTokenRateMultiplier)]
+    #[storage_mapper("second_token_rate_multiplier")]
+    fn second_token_rate_multiplier(&self) -> SingleValueMapper<BigUint>;
+}
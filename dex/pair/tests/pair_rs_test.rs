@@ -1,6 +1,9 @@
 #![allow(deprecated)]
 
 mod pair_setup;
+use egld_wrapper_mock::EgldWrapperMock;
+use energy_factory_mock::EnergyFactoryMock;
+use energy_query::EnergyQueryModule;
 use fees_collector::{
     config::ConfigModule, fees_accumulation::FeesAccumulationModule, FeesCollector,
 };
@@ -20,13 +23,22 @@ use multiversx_sc_scenario::{
     whitebox_legacy::TxTokenTransfer, DebugApi,
 };
 use pair::{
-    config::MAX_PERCENTAGE,
+    circuit_breaker::CircuitBreakerModule,
+    config::{ConfigModule as PairConfigModule, MAX_PERCENTAGE},
+    dynamic_fee::DynamicFeeModule,
     fee::FeeModule,
+    fee_discount::FeeDiscountModule,
     locking_wrapper::LockingWrapperModule,
-    pair_actions::swap::SwapModule,
+    pair_actions::{
+        egld_liq::EgldLiquidityModule, remove_liq::RemoveLiquidityModule,
+        skim_sync::SkimSyncModule, swap::SwapModule, views::ViewsModule,
+    },
     safe_price::{PriceObservation, Round, SafePriceModule},
+    stable_swap::StableSwapModule,
 };
+use pair_setup::flash_borrower_mock::*;
 use pair_setup::*;
+use pausable::{PausableModule, State};
 use simple_lock::{
     locked_token::{LockedTokenAttributes, LockedTokenModule},
     proxy_lp::{LpProxyTokenAttributes, ProxyLpModule},
@@ -1203,6 +1215,7 @@ fn test_locked_asset() {
                 let ret = sc.swap_tokens_fixed_input(
                     managed_token_id!(WEGLD_TOKEN_ID),
                     managed_biguint!(10),
+                    OptionalValue::None,
                 );
 
                 assert_eq!(ret.token_identifier, managed_token_id!(LOCKED_TOKEN_ID));
@@ -1650,3 +1663,1334 @@ fn fees_collector_pair_test() {
         })
         .assert_ok();
 }
+
+#[test]
+fn weighted_fee_destinations_test() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    let fees_collector_wrapper = pair_setup.b_mock.create_sc_account(
+        &rust_biguint!(0),
+        None,
+        fees_collector::contract_obj,
+        "fees collector path",
+    );
+    let burn_destination = pair_setup.b_mock.create_user_account(&rust_biguint!(0));
+    let treasury = pair_setup.b_mock.create_user_account(&rust_biguint!(0));
+    pair_setup.b_mock.set_esdt_local_roles(
+        pair_setup.pair_wrapper.address_ref(),
+        WEGLD_TOKEN_ID,
+        &[EsdtLocalRole::Burn],
+    );
+
+    let pair_addr = pair_setup.pair_wrapper.address_ref().clone();
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &fees_collector_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.init(
+                    managed_token_id!(LOCKED_TOKEN_ID),
+                    managed_address!(&pair_addr),
+                );
+                let _ = sc.known_contracts().insert(managed_address!(&pair_addr));
+
+                let mut tokens = MultiValueEncoded::new();
+                tokens.push(managed_token_id!(WEGLD_TOKEN_ID));
+                tokens.push(managed_token_id!(MEX_TOKEN_ID));
+
+                sc.add_known_tokens(tokens);
+            },
+        )
+        .assert_ok();
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.setup_fees_collector(
+                    managed_address!(fees_collector_wrapper.address_ref()),
+                    MAX_PERCENTAGE / 10,
+                );
+                sc.set_fee_on(
+                    true,
+                    managed_address!(&burn_destination),
+                    managed_token_id!(WEGLD_TOKEN_ID),
+                );
+                sc.add_treasury_fee_destination(managed_address!(&treasury));
+            },
+        )
+        .assert_ok();
+
+    // the weights have to add up to 10_000
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut weights = MultiValueEncoded::new();
+                weights.push(
+                    (
+                        managed_address!(fees_collector_wrapper.address_ref()),
+                        5_000u64,
+                    )
+                        .into(),
+                );
+                weights.push((managed_address!(&treasury), 2_000u64).into());
+                sc.set_fee_destination_weights(weights);
+            },
+        )
+        .assert_user_error("Invalid fee destination weights");
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut weights = MultiValueEncoded::new();
+                weights.push((managed_address!(&pair_addr), 10_000u64).into());
+                sc.set_fee_destination_weights(weights);
+            },
+        )
+        .assert_user_error("Not a fee destination");
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let mut weights = MultiValueEncoded::new();
+                weights.push(
+                    (
+                        managed_address!(fees_collector_wrapper.address_ref()),
+                        5_000u64,
+                    )
+                        .into(),
+                );
+                weights.push((managed_address!(&burn_destination), 3_000u64).into());
+                weights.push((managed_address!(&treasury), 2_000u64).into());
+                sc.set_fee_destination_weights(weights);
+            },
+        )
+        .assert_ok();
+
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+
+    // the special fee of 50 tokens is split 50% - 30% - 20%
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 100_000, MEX_TOKEN_ID, 900, 90_669);
+
+    pair_setup.b_mock.check_esdt_balance(
+        fees_collector_wrapper.address_ref(),
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(25),
+    );
+    pair_setup
+        .b_mock
+        .check_esdt_balance(&treasury, WEGLD_TOKEN_ID, &rust_biguint!(10));
+    pair_setup
+        .b_mock
+        .check_esdt_balance(&burn_destination, WEGLD_TOKEN_ID, &rust_biguint!(0));
+
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            let expected_accumulated_fees = [
+                (fees_collector_wrapper.address_ref(), 25u64),
+                (&burn_destination, 15u64),
+                (&treasury, 10u64),
+            ];
+            for (fee_address, expected_amount) in expected_accumulated_fees {
+                let (first_token_fees, second_token_fees) = sc
+                    .get_fee_destination_accumulated_fees(managed_address!(fee_address))
+                    .into_tuple();
+                assert_eq!(first_token_fees.amount, managed_biguint!(expected_amount));
+                assert_eq!(second_token_fees.amount, managed_biguint!(0));
+            }
+        })
+        .assert_ok();
+
+    // the special fee of 15 tokens is split 7 - 4 - 4, the last destination gets the rounding remainder
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 30_000, MEX_TOKEN_ID, 1, 24_077);
+
+    pair_setup.b_mock.check_esdt_balance(
+        fees_collector_wrapper.address_ref(),
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(32),
+    );
+    pair_setup
+        .b_mock
+        .check_esdt_balance(&treasury, WEGLD_TOKEN_ID, &rust_biguint!(14));
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            let (first_token_fees, _) = sc
+                .get_fee_destination_accumulated_fees(managed_address!(&burn_destination))
+                .into_tuple();
+            assert_eq!(first_token_fees.amount, managed_biguint!(19));
+        })
+        .assert_ok();
+
+    // the weights of removed destinations are dropped
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.remove_treasury_fee_destination(managed_address!(&treasury));
+
+                let weights = sc.fee_destination_weights();
+                assert_eq!(weights.len(), 2);
+                assert!(!weights.contains_key(&managed_address!(&treasury)));
+            },
+        )
+        .assert_ok();
+}
+
+#[test]
+fn stable_swap_test() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    pair_setup.enable_stable_swap(10_000, 1, 1);
+
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+
+    // constant product would return only 90_669 for the same input
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 100_000, MEX_TOKEN_ID, 99_000, 99_600);
+    pair_setup.swap_fixed_output(MEX_TOKEN_ID, 100_000, WEGLD_TOKEN_ID, 99_000, 801);
+}
+
+#[test]
+fn stable_swap_rate_multipliers_test() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+
+    // the first token has 3 decimals less than the second one
+    pair_setup.enable_stable_swap(10_000, 1_000, 1);
+
+    pair_setup.add_liquidity(
+        1_001_000,
+        1_000_000,
+        1_001_000_000,
+        1_000_000_000,
+        1_000_000,
+        1_001_000,
+        1_001_000_000,
+    );
+
+    // same prices as for equal decimals, scaled by the rate multipliers
+    pair_setup.swap_fixed_input(
+        WEGLD_TOKEN_ID,
+        100_000,
+        MEX_TOKEN_ID,
+        99_000_000,
+        99_600_815,
+    );
+    pair_setup.swap_fixed_output(MEX_TOKEN_ID, 100_000_000, WEGLD_TOKEN_ID, 99_000, 801_868);
+}
+
+#[test]
+fn stable_swap_min_amplification_test() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    let rust_zero = rust_biguint!(0);
+
+    // A has to be at least 1
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_zero,
+            |sc| {
+                sc.enable_stable_swap(99, managed_biguint!(1), managed_biguint!(1));
+            },
+        )
+        .assert_user_error("Invalid amplification coefficient");
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_zero,
+            |sc| {
+                sc.enable_stable_swap(100, managed_biguint!(1), managed_biguint!(0));
+            },
+        )
+        .assert_user_error("Invalid rate multiplier");
+
+    pair_setup.enable_stable_swap(100, 1, 1);
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 100_000, MEX_TOKEN_ID, 90_000, 94_959);
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_zero,
+            |sc| {
+                sc.ramp_amplification(99, 100_000);
+            },
+        )
+        .assert_user_error("Invalid amplification coefficient");
+}
+
+#[test]
+fn stable_swap_ramp_amplification_test() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    let rust_zero = rust_biguint!(0);
+    pair_setup.enable_stable_swap(10_000, 1, 1);
+
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+
+    // ramp is too short
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_zero,
+            |sc| {
+                sc.ramp_amplification(20_000, 1_000);
+            },
+        )
+        .assert_user_error("Amplification ramp too short");
+
+    // change is too big
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_zero,
+            |sc| {
+                sc.ramp_amplification(200_000, 100_000);
+            },
+        )
+        .assert_user_error("Amplification change too big");
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_zero,
+            |sc| {
+                sc.ramp_amplification(20_000, 100_000);
+            },
+        )
+        .assert_ok();
+
+    pair_setup.b_mock.set_block_timestamp(50_000);
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            assert_eq!(sc.get_current_amplification(), 15_000);
+        })
+        .assert_ok();
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_zero,
+            |sc| {
+                sc.stop_ramp_amplification();
+            },
+        )
+        .assert_ok();
+
+    pair_setup.b_mock.set_block_timestamp(200_000);
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            assert_eq!(sc.get_current_amplification(), 15_000);
+        })
+        .assert_ok();
+
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 100_000, MEX_TOKEN_ID, 99_000, 99_633);
+}
+
+#[test]
+fn flash_swap_test() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    let fees_collector_wrapper = pair_setup.b_mock.create_sc_account(
+        &rust_biguint!(0),
+        None,
+        fees_collector::contract_obj,
+        "fees collector path",
+    );
+    let borrower_wrapper = pair_setup.b_mock.create_sc_account(
+        &rust_biguint!(0),
+        None,
+        FlashBorrowerMock::new,
+        "flash borrower path",
+    );
+    let borrower_addr = borrower_wrapper.address_ref().clone();
+    pair_setup
+        .b_mock
+        .set_esdt_balance(&borrower_addr, MEX_TOKEN_ID, &rust_biguint!(1_000));
+
+    let pair_addr = pair_setup.pair_wrapper.address_ref().clone();
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &fees_collector_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.init(
+                    managed_token_id!(LOCKED_TOKEN_ID),
+                    managed_address!(&pair_addr),
+                );
+                let _ = sc.known_contracts().insert(managed_address!(&pair_addr));
+
+                let mut tokens = MultiValueEncoded::new();
+                tokens.push(managed_token_id!(WEGLD_TOKEN_ID));
+                tokens.push(managed_token_id!(MEX_TOKEN_ID));
+
+                sc.add_known_tokens(tokens);
+            },
+        )
+        .assert_ok();
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.setup_fees_collector(
+                    managed_address!(fees_collector_wrapper.address_ref()),
+                    MAX_PERCENTAGE / 2,
+                );
+            },
+        )
+        .assert_ok();
+
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+
+    // user accounts cannot receive the callback
+    let user_addr = pair_setup.user_address.clone();
+    pair_setup
+        .flash_swap(&user_addr, MEX_TOKEN_ID, 100_000, REPAY_FN_NAME, vec![])
+        .assert_user_error("Flash swaps can only be initiated by smart contracts");
+
+    // repayment does not cover the fee
+    pair_setup
+        .flash_swap(
+            &borrower_addr,
+            MEX_TOKEN_ID,
+            100_000,
+            REPAY_FN_NAME,
+            vec![MEX_TOKEN_ID, &100_300u64.to_be_bytes()],
+        )
+        .assert_user_error("K invariant failed");
+
+    // reentrancy into the same pair
+    pair_setup
+        .flash_swap(
+            &borrower_addr,
+            MEX_TOKEN_ID,
+            100_000,
+            REENTER_FN_NAME,
+            vec![],
+        )
+        .assert_user_error("Flash swap in progress");
+
+    pair_setup
+        .flash_swap(
+            &borrower_addr,
+            MEX_TOKEN_ID,
+            100_000,
+            REPAY_FN_NAME,
+            vec![MEX_TOKEN_ID, &100_301u64.to_be_bytes()],
+        )
+        .assert_ok();
+
+    pair_setup
+        .b_mock
+        .check_esdt_balance(&borrower_addr, MEX_TOKEN_ID, &rust_biguint!(699));
+
+    // special fee is 50, half of it goes to the fees collector
+    pair_setup.b_mock.check_esdt_balance(
+        fees_collector_wrapper.address_ref(),
+        MEX_TOKEN_ID,
+        &rust_biguint!(25),
+    );
+
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            assert_eq!(
+                sc.pair_reserve(&managed_token_id!(MEX_TOKEN_ID)).get(),
+                managed_biguint!(1_001_251)
+            );
+            assert_eq!(
+                sc.pair_reserve(&managed_token_id!(WEGLD_TOKEN_ID)).get(),
+                managed_biguint!(1_001_000)
+            );
+            assert!(!sc.flash_swap_lock().get());
+        })
+        .assert_ok();
+
+    // regular swaps work after the flash swap
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 100_000, MEX_TOKEN_ID, 900, 90_692);
+}
+
+#[test]
+fn add_liquidity_single_token_test() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+
+    pair_setup
+        .add_liquidity_single_token(LP_TOKEN_ID, 100_000, 1)
+        .assert_user_error("Unknown token");
+
+    pair_setup
+        .add_liquidity_single_token(WEGLD_TOKEN_ID, 100_000, 50_000)
+        .assert_user_error("Slippage exceeded");
+
+    pair_setup
+        .add_liquidity_single_token(WEGLD_TOKEN_ID, 100_000, 40_000)
+        .assert_ok();
+
+    let user_addr = pair_setup.user_address.clone();
+    pair_setup
+        .b_mock
+        .check_esdt_balance(&user_addr, LP_TOKEN_ID, &rust_biguint!(1_048_735));
+
+    // only dust is left after the internal swap
+    pair_setup.b_mock.check_esdt_balance(
+        &user_addr,
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_WEGLD_TOKENS - 1_001_000 - 100_000 + 2),
+    );
+    pair_setup.b_mock.check_esdt_balance(
+        &user_addr,
+        MEX_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_MEX_TOKENS - 1_001_000),
+    );
+}
+
+#[test]
+fn add_liquidity_single_token_stable_swap_test() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    pair_setup.enable_stable_swap(10_000, 1, 1);
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+
+    pair_setup
+        .add_liquidity_single_token(WEGLD_TOKEN_ID, 100_000, 45_000)
+        .assert_ok();
+
+    let user_addr = pair_setup.user_address.clone();
+    pair_setup
+        .b_mock
+        .check_esdt_balance(&user_addr, LP_TOKEN_ID, &rust_biguint!(1_049_911));
+
+    // the bounded search leaves only a small leftover
+    pair_setup.b_mock.check_esdt_balance(
+        &user_addr,
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_WEGLD_TOKENS - 1_001_000 - 100_000 + 2),
+    );
+}
+
+#[test]
+fn remove_liquidity_to_single_token_test() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+
+    pair_setup
+        .remove_liquidity_to_single_token(100_000, LP_TOKEN_ID, 1)
+        .assert_user_error("Unknown token");
+
+    pair_setup
+        .remove_liquidity_to_single_token(100_000, MEX_TOKEN_ID, 190_000)
+        .assert_user_error("Slippage exceeded");
+
+    pair_setup
+        .remove_liquidity_to_single_token(100_000, MEX_TOKEN_ID, 185_000)
+        .assert_ok();
+
+    let user_addr = pair_setup.user_address.clone();
+    pair_setup
+        .b_mock
+        .check_esdt_balance(&user_addr, LP_TOKEN_ID, &rust_biguint!(900_000));
+    pair_setup.b_mock.check_esdt_balance(
+        &user_addr,
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_WEGLD_TOKENS - 1_001_000),
+    );
+    pair_setup.b_mock.check_esdt_balance(
+        &user_addr,
+        MEX_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_MEX_TOKENS - 1_001_000 + 189_766),
+    );
+}
+
+#[test]
+fn dynamic_fee_test() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    pair_setup.b_mock.set_block_round(1);
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_dynamic_fee_config(300, 3_000, 10);
+            },
+        )
+        .assert_ok();
+
+    // no observations for the offset yet, so the minimum fee is used
+    pair_setup.b_mock.set_block_round(5);
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 1_000, MEX_TOKEN_ID, 1, 996);
+
+    // the price did not move
+    pair_setup.b_mock.set_block_round(20);
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            assert_eq!(sc.get_effective_fee_percent(), 300);
+        })
+        .assert_ok();
+
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 200_000, MEX_TOKEN_ID, 1, 165_973);
+
+    // the spot price diverges from the safe price after the big swap
+    pair_setup.b_mock.set_block_round(21);
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            assert_eq!(sc.get_effective_fee_percent(), 3_000);
+        })
+        .assert_ok();
+
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 1_000, MEX_TOKEN_ID, 1, 672);
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            assert_eq!(sc.get_total_fee_percent(), 3_000);
+            assert_eq!(sc.total_fee_percent().get(), 300);
+        })
+        .assert_ok();
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.disable_dynamic_fee();
+            },
+        )
+        .assert_ok();
+
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            assert_eq!(sc.get_effective_fee_percent(), 300);
+            assert_eq!(sc.get_total_fee_percent(), 300);
+        })
+        .assert_ok();
+}
+
+#[test]
+fn circuit_breaker_test() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    pair_setup.b_mock.set_block_round(1);
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_circuit_breaker_config(5_000, 10, false);
+            },
+        )
+        .assert_ok();
+
+    pair_setup.b_mock.set_block_round(5);
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 1_000, MEX_TOKEN_ID, 1, 996);
+
+    // moves the price by more than 5%
+    pair_setup.b_mock.set_block_round(20);
+    pair_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &pair_setup.user_address,
+            &pair_setup.pair_wrapper,
+            WEGLD_TOKEN_ID,
+            0,
+            &rust_biguint!(100_000),
+            |sc| {
+                let _ = sc.swap_tokens_fixed_input(
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_biguint!(1),
+                    OptionalValue::None,
+                );
+            },
+        )
+        .assert_user_error("Price deviates too much from the safe price");
+
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 10_000, MEX_TOKEN_ID, 1, 9_852);
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_circuit_breaker_config(5_000, 10, true);
+            },
+        )
+        .assert_ok();
+
+    // the tripping swap is cancelled and returned, and swaps are paused afterwards
+    pair_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &pair_setup.user_address,
+            &pair_setup.pair_wrapper,
+            WEGLD_TOKEN_ID,
+            0,
+            &rust_biguint!(100_000),
+            |sc| {
+                let first_reserve_before =
+                    sc.pair_reserve(&managed_token_id!(WEGLD_TOKEN_ID)).get();
+                let payment = sc.swap_tokens_fixed_input(
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_biguint!(1),
+                    OptionalValue::None,
+                );
+                assert_eq!(payment.token_identifier, managed_token_id!(WEGLD_TOKEN_ID));
+                assert_eq!(payment.amount, managed_biguint!(100_000));
+                assert_eq!(
+                    sc.pair_reserve(&managed_token_id!(WEGLD_TOKEN_ID)).get(),
+                    first_reserve_before
+                );
+            },
+        )
+        .assert_ok();
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            assert!(sc.state().get() == State::PartialActive);
+        })
+        .assert_ok();
+
+    pair_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &pair_setup.user_address,
+            &pair_setup.pair_wrapper,
+            WEGLD_TOKEN_ID,
+            0,
+            &rust_biguint!(1_000),
+            |sc| {
+                let _ = sc.swap_tokens_fixed_input(
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_biguint!(1),
+                    OptionalValue::None,
+                );
+            },
+        )
+        .assert_user_error("Swap is not enabled");
+
+    // the price is moved while the breaker is disabled
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.state().set(State::Active);
+                sc.disable_circuit_breaker();
+            },
+        )
+        .assert_ok();
+    pair_setup.swap_fixed_input(WEGLD_TOKEN_ID, 100_000, MEX_TOKEN_ID, 1, 88_799);
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &pair_setup.owner_address,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_circuit_breaker_config(5_000, 10, false);
+            },
+        )
+        .assert_ok();
+
+    // removing liquidity keeps the reserves ratio, so it is allowed
+    pair_setup.b_mock.set_block_round(21);
+    pair_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &pair_setup.user_address,
+            &pair_setup.pair_wrapper,
+            LP_TOKEN_ID,
+            0,
+            &rust_biguint!(1_000),
+            |sc| {
+                let _ = sc.remove_liquidity(managed_biguint!(1), managed_biguint!(1));
+            },
+        )
+        .assert_ok();
+
+    // moving the price further away is rejected
+    pair_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &pair_setup.user_address,
+            &pair_setup.pair_wrapper,
+            WEGLD_TOKEN_ID,
+            0,
+            &rust_biguint!(1_000),
+            |sc| {
+                let _ = sc.swap_tokens_fixed_input(
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_biguint!(1),
+                    OptionalValue::None,
+                );
+            },
+        )
+        .assert_user_error("Price deviates too much from the safe price");
+
+    // swapping towards the safe price is allowed, even if the deviation stays above the max
+    pair_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &pair_setup.user_address,
+            &pair_setup.pair_wrapper,
+            MEX_TOKEN_ID,
+            0,
+            &rust_biguint!(10_000),
+            |sc| {
+                let _ = sc.swap_tokens_fixed_input(
+                    managed_token_id!(WEGLD_TOKEN_ID),
+                    managed_biguint!(1),
+                    OptionalValue::None,
+                );
+            },
+        )
+        .assert_ok();
+}
+
+#[test]
+fn skim_without_liquidity_test() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    let pair_addr = pair_setup.pair_wrapper.address_ref().clone();
+    let owner_addr = pair_setup.owner_address.clone();
+
+    pair_setup
+        .b_mock
+        .set_esdt_balance(&pair_addr, WEGLD_TOKEN_ID, &rust_biguint!(1_000));
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &owner_addr,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.skim(managed_address!(&owner_addr));
+            },
+        )
+        .assert_user_error("Initial liquidity was not added");
+}
+
+#[test]
+fn skim_and_sync_test() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+
+    let pair_addr = pair_setup.pair_wrapper.address_ref().clone();
+    let user_addr = pair_setup.user_address.clone();
+    let owner_addr = pair_setup.owner_address.clone();
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &user_addr,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.skim(managed_address!(&user_addr));
+            },
+        )
+        .assert_user_error("Permission denied");
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &owner_addr,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.skim(managed_address!(&owner_addr));
+            },
+        )
+        .assert_user_error("Nothing to skim");
+
+    // tokens sent directly to the pair
+    pair_setup
+        .b_mock
+        .set_esdt_balance(&pair_addr, WEGLD_TOKEN_ID, &rust_biguint!(1_001_500));
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &owner_addr,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.skim(managed_address!(&owner_addr));
+            },
+        )
+        .assert_ok();
+
+    pair_setup
+        .b_mock
+        .check_esdt_balance(&owner_addr, WEGLD_TOKEN_ID, &rust_biguint!(500));
+    pair_setup
+        .b_mock
+        .check_esdt_balance(&pair_addr, WEGLD_TOKEN_ID, &rust_biguint!(1_001_000));
+
+    pair_setup
+        .b_mock
+        .set_esdt_balance(&pair_addr, MEX_TOKEN_ID, &rust_biguint!(1_002_000));
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &owner_addr,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.sync();
+            },
+        )
+        .assert_ok();
+
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            assert_eq!(
+                sc.pair_reserve(&managed_token_id!(WEGLD_TOKEN_ID)).get(),
+                managed_biguint!(1_001_000)
+            );
+            assert_eq!(
+                sc.pair_reserve(&managed_token_id!(MEX_TOKEN_ID)).get(),
+                managed_biguint!(1_002_000)
+            );
+            assert_eq!(sc.lp_token_supply().get(), managed_biguint!(1_001_000));
+        })
+        .assert_ok();
+
+    // reserves cannot be synced to lower balances
+    pair_setup
+        .b_mock
+        .set_esdt_balance(&pair_addr, MEX_TOKEN_ID, &rust_biguint!(1_000_000));
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &owner_addr,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.sync();
+            },
+        )
+        .assert_user_error("K invariant failed");
+}
+
+#[test]
+fn add_and_remove_liquidity_egld_test() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+
+    let rust_zero = rust_biguint!(0);
+    let owner_addr = pair_setup.owner_address.clone();
+    let user_addr = pair_setup.user_address.clone();
+
+    let other_wrapper = pair_setup.b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner_addr),
+        egld_wrapper_mock::contract_obj,
+        EGLD_WRAPPER_WASM_PATH,
+    );
+    pair_setup
+        .b_mock
+        .execute_tx(&owner_addr, &other_wrapper, &rust_zero, |sc| {
+            sc.init(managed_token_id!(OTHER_TOKEN_ID));
+        })
+        .assert_ok();
+
+    // the wrapped EGLD supply is already backed by EGLD
+    let egld_wrapper = pair_setup.b_mock.create_sc_account(
+        &rust_biguint!(USER_TOTAL_WEGLD_TOKENS),
+        Some(&owner_addr),
+        egld_wrapper_mock::contract_obj,
+        EGLD_WRAPPER_WASM_PATH,
+    );
+    pair_setup
+        .b_mock
+        .execute_tx(&owner_addr, &egld_wrapper, &rust_zero, |sc| {
+            sc.init(managed_token_id!(WEGLD_TOKEN_ID));
+        })
+        .assert_ok();
+    pair_setup.b_mock.set_esdt_local_roles(
+        egld_wrapper.address_ref(),
+        WEGLD_TOKEN_ID,
+        &[EsdtLocalRole::Mint, EsdtLocalRole::Burn],
+    );
+
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &user_addr,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(100_000),
+            |sc| {
+                let _ = sc.add_liquidity_egld(managed_biguint!(1));
+            },
+        )
+        .assert_user_error("EGLD wrapper address not set");
+
+    pair_setup
+        .b_mock
+        .execute_tx(&owner_addr, &pair_setup.pair_wrapper, &rust_zero, |sc| {
+            sc.set_egld_wrapper_address(managed_address!(other_wrapper.address_ref()));
+        })
+        .assert_user_error("Wrapped EGLD is not a token of the pair");
+
+    pair_setup
+        .b_mock
+        .execute_tx(&owner_addr, &pair_setup.pair_wrapper, &rust_zero, |sc| {
+            sc.set_egld_wrapper_address(managed_address!(egld_wrapper.address_ref()));
+        })
+        .assert_ok();
+
+    // same result as adding liquidity with 100_000 WEGLD
+    pair_setup
+        .b_mock
+        .execute_tx(
+            &user_addr,
+            &pair_setup.pair_wrapper,
+            &rust_biguint!(100_000),
+            |sc| {
+                let _ = sc.add_liquidity_egld(managed_biguint!(40_000));
+            },
+        )
+        .assert_ok();
+
+    pair_setup
+        .b_mock
+        .check_egld_balance(&user_addr, &rust_biguint!(100_000_000 - 100_000));
+    pair_setup
+        .b_mock
+        .check_esdt_balance(&user_addr, LP_TOKEN_ID, &rust_biguint!(1_048_735));
+    pair_setup.b_mock.check_esdt_balance(
+        &user_addr,
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_WEGLD_TOKENS - 1_001_000 + 2),
+    );
+
+    pair_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &user_addr,
+            &pair_setup.pair_wrapper,
+            LP_TOKEN_ID,
+            0,
+            &rust_biguint!(100_000),
+            |sc| {
+                let (first_payment, second_payment) = sc
+                    .remove_liquidity_egld(managed_biguint!(1), managed_biguint!(1))
+                    .into_tuple();
+                assert!(first_payment.token_identifier.is_egld());
+                assert_eq!(first_payment.amount, managed_biguint!(104_883));
+                assert_eq!(
+                    second_payment.token_identifier,
+                    managed_token_id_wrapped!(MEX_TOKEN_ID)
+                );
+                assert_eq!(second_payment.amount, managed_biguint!(95_357));
+            },
+        )
+        .assert_ok();
+
+    pair_setup
+        .b_mock
+        .check_egld_balance(&user_addr, &rust_biguint!(100_000_000 - 100_000 + 104_883));
+    pair_setup.b_mock.check_esdt_balance(
+        &user_addr,
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_WEGLD_TOKENS - 1_001_000 + 2),
+    );
+    pair_setup.b_mock.check_esdt_balance(
+        &user_addr,
+        MEX_TOKEN_ID,
+        &rust_biguint!(USER_TOTAL_MEX_TOKENS - 1_001_000 + 95_357),
+    );
+    pair_setup.b_mock.check_egld_balance(
+        egld_wrapper.address_ref(),
+        &rust_biguint!(USER_TOTAL_WEGLD_TOKENS + 100_000 - 104_883),
+    );
+}
+
+#[test]
+fn fee_discount_test() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+
+    let rust_zero = rust_biguint!(0);
+    let owner_addr = pair_setup.owner_address.clone();
+    let user_addr = pair_setup.user_address.clone();
+
+    let energy_factory_mock = pair_setup.b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner_addr),
+        energy_factory_mock::contract_obj,
+        "energy factory mock",
+    );
+    pair_setup
+        .b_mock
+        .execute_tx(&owner_addr, &energy_factory_mock, &rust_zero, |sc| {
+            sc.init();
+            sc.set_user_energy(
+                managed_address!(&user_addr),
+                managed_biguint!(1_000),
+                managed_biguint!(1),
+            );
+        })
+        .assert_ok();
+
+    pair_setup
+        .b_mock
+        .execute_tx(&owner_addr, &pair_setup.pair_wrapper, &rust_zero, |sc| {
+            // discounts have to increase with the energy
+            let mut tiers = MultiValueEncoded::new();
+            tiers.push((managed_biguint!(500), 50_000u64).into());
+            tiers.push((managed_biguint!(5_000), 20_000u64).into());
+            sc.set_fee_discount_tiers(tiers);
+        })
+        .assert_user_error("Invalid fee discount tiers");
+
+    pair_setup
+        .b_mock
+        .execute_tx(&owner_addr, &pair_setup.pair_wrapper, &rust_zero, |sc| {
+            sc.set_energy_factory_address(managed_address!(energy_factory_mock.address_ref()));
+
+            let mut tiers = MultiValueEncoded::new();
+            tiers.push((managed_biguint!(500), 50_000u64).into());
+            tiers.push((managed_biguint!(5_000), 80_000u64).into());
+            sc.set_fee_discount_tiers(tiers);
+        })
+        .assert_ok();
+
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            assert_eq!(
+                sc.get_fee_discount_percent(managed_address!(&user_addr)),
+                50_000
+            );
+            assert_eq!(
+                sc.get_fee_discount_percent(managed_address!(&owner_addr)),
+                0
+            );
+
+            let (total_fee_percent, special_fee_percent) = sc
+                .get_fee_percents_for_address(managed_address!(&user_addr))
+                .into_tuple();
+            assert_eq!(total_fee_percent, 150);
+            assert_eq!(special_fee_percent, 25);
+
+            assert_eq!(
+                sc.get_amount_out_view(managed_token_id!(WEGLD_TOKEN_ID), managed_biguint!(10_000)),
+                managed_biguint!(9_871)
+            );
+            assert_eq!(
+                sc.get_amount_out_for_address_view(
+                    managed_address!(&user_addr),
+                    managed_token_id!(WEGLD_TOKEN_ID),
+                    managed_biguint!(10_000)
+                ),
+                managed_biguint!(9_886)
+            );
+        })
+        .assert_ok();
+
+    // only the router and the trusted proxies can swap on behalf of other users
+    pair_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &user_addr,
+            &pair_setup.pair_wrapper,
+            WEGLD_TOKEN_ID,
+            0,
+            &rust_biguint!(10_000),
+            |sc| {
+                let _ = sc.swap_tokens_fixed_input(
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_biguint!(1),
+                    OptionalValue::Some(managed_address!(&owner_addr)),
+                );
+            },
+        )
+        .assert_user_error("Caller cannot swap on behalf of others");
+
+    // the owner address is also the router address of the pair
+    pair_setup
+        .b_mock
+        .set_esdt_balance(&owner_addr, WEGLD_TOKEN_ID, &rust_biguint!(10_000));
+    pair_setup
+        .b_mock
+        .execute_esdt_transfer(
+            &owner_addr,
+            &pair_setup.pair_wrapper,
+            WEGLD_TOKEN_ID,
+            0,
+            &rust_biguint!(10_000),
+            |sc| {
+                let ret = sc.swap_tokens_fixed_input(
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_biguint!(1),
+                    OptionalValue::Some(managed_address!(&user_addr)),
+                );
+                assert_eq!(ret.amount, managed_biguint!(9_886));
+            },
+        )
+        .assert_ok();
+    pair_setup
+        .b_mock
+        .check_esdt_balance(&owner_addr, MEX_TOKEN_ID, &rust_biguint!(9_886));
+}
+
+#[test]
+fn swap_quotes_test() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            let mut amounts_in = MultiValueEncoded::new();
+            amounts_in.push(managed_biguint!(1_000));
+            amounts_in.push(managed_biguint!(100_000));
+            let quotes = sc
+                .get_swap_quotes(managed_token_id!(WEGLD_TOKEN_ID), amounts_in)
+                .to_vec();
+
+            let quote = quotes.get(0);
+            assert_eq!(quote.amount_out, managed_biguint!(996));
+            assert_eq!(
+                quote.effective_price,
+                managed_biguint!(996_000_000_000_000_000)
+            );
+            assert_eq!(quote.price_impact, 99);
+            assert_eq!(quote.fee_amount, managed_biguint!(3));
+
+            // same output as swapping through swapTokensFixedInput
+            let quote = quotes.get(1);
+            assert_eq!(quote.amount_out, managed_biguint!(90_669));
+            assert_eq!(quote.price_impact, 9_082);
+            assert_eq!(quote.fee_amount, managed_biguint!(300));
+
+            let max_amount_in =
+                sc.get_max_amount_in_for_price_impact(managed_token_id!(WEGLD_TOKEN_ID), 1_000);
+            assert_eq!(max_amount_in, managed_biguint!(10_111));
+        })
+        .assert_ok();
+
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            let _ = sc.get_max_amount_in_for_price_impact(
+                managed_token_id!(WEGLD_TOKEN_ID),
+                MAX_PERCENTAGE,
+            );
+        })
+        .assert_user_error("Invalid price impact");
+}
+
+#[test]
+fn stable_swap_max_amount_in_for_price_impact_test() {
+    let mut pair_setup = PairSetup::new(pair::contract_obj);
+    pair_setup.enable_stable_swap(10_000, 1, 1);
+    pair_setup.add_liquidity(
+        1_001_000, 1_000_000, 1_001_000, 1_000_000, 1_000_000, 1_001_000, 1_001_000,
+    );
+
+    pair_setup
+        .b_mock
+        .execute_query(&pair_setup.pair_wrapper, |sc| {
+            let max_amount_in =
+                sc.get_max_amount_in_for_price_impact(managed_token_id!(WEGLD_TOKEN_ID), 1_000);
+
+            // the stable pool allows a much larger input than the constant product one
+            assert!(max_amount_in > managed_biguint!(100_000));
+
+            let mut amounts_in = MultiValueEncoded::new();
+            amounts_in.push(max_amount_in.clone());
+            amounts_in.push(max_amount_in + 1u64);
+            let quotes = sc
+                .get_swap_quotes(managed_token_id!(WEGLD_TOKEN_ID), amounts_in)
+                .to_vec();
+            assert!(quotes.get(0).price_impact <= 1_000);
+            assert!(quotes.get(1).price_impact > 1_000);
+        })
+        .assert_ok();
+}
@@ -0,0 +1,65 @@
+use multiversx_sc::codec::multi_types::IgnoreValue;
+use multiversx_sc::io::load_endpoint_args;
+use multiversx_sc::{
+    contract_base::{CallableContract, ContractBase},
+    types::{BigUint, ContractCall, ManagedArgBuffer, ManagedBuffer, TokenIdentifier},
+};
+use multiversx_sc_scenario::DebugApi;
+
+pub static REPAY_FN_NAME: &str = "repayFlashSwap";
+pub static REENTER_FN_NAME: &str = "reenterFlashSwap";
+
+#[derive(Clone)]
+pub struct FlashBorrowerMock {}
+
+impl ContractBase for FlashBorrowerMock {
+    type Api = DebugApi;
+}
+
+impl CallableContract for FlashBorrowerMock {
+    fn call(&self, fn_name: &str) -> bool {
+        if fn_name == REPAY_FN_NAME {
+            self.repay();
+            true
+        } else if fn_name == REENTER_FN_NAME {
+            self.reenter();
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl FlashBorrowerMock {
+    pub fn new() -> Self {
+        FlashBorrowerMock {}
+    }
+
+    // sends back the given amount to the pair, as if the borrowed tokens were used in between
+    pub fn repay(&self) {
+        let (token_id, (amount, ())) = load_endpoint_args::<
+            DebugApi,
+            (TokenIdentifier<DebugApi>, (BigUint<DebugApi>, ())),
+        >(("token_id", ("amount", ())));
+
+        let pair_address = self.blockchain().get_caller();
+        self.send()
+            .direct_esdt(&pair_address, &token_id, 0, &amount);
+    }
+
+    pub fn reenter(&self) {
+        let payment = self.call_value().single_esdt();
+        let pair_address = self.blockchain().get_caller();
+
+        let mut args = ManagedArgBuffer::<DebugApi>::new();
+        args.push_arg(&payment.token_identifier);
+        args.push_arg(&payment.amount);
+        args.push_arg(ManagedBuffer::<DebugApi>::from(REPAY_FN_NAME));
+
+        let _: IgnoreValue = self
+            .send()
+            .contract_call::<IgnoreValue>(pair_address, "flashSwap")
+            .with_raw_arguments(args)
+            .execute_on_dest_context();
+    }
+}
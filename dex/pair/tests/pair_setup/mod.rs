@@ -1,13 +1,17 @@
-use multiversx_sc::codec::multi_types::MultiValue3;
+pub mod flash_borrower_mock;
+
+use multiversx_sc::codec::multi_types::{MultiValue3, OptionalValue};
 use multiversx_sc::types::{
     Address, BigUint, EsdtLocalRole, EsdtTokenPayment, ManagedAddress, MultiValueEncoded,
 };
 use multiversx_sc_scenario::whitebox_legacy::TxTokenTransfer;
 use multiversx_sc_scenario::{
-    managed_address, managed_biguint, managed_token_id, rust_biguint, whitebox_legacy::*, DebugApi,
+    managed_address, managed_biguint, managed_buffer, managed_token_id, rust_biguint,
+    whitebox_legacy::*, DebugApi,
 };
 
 pub const PAIR_WASM_PATH: &str = "pair/output/pair.wasm";
+pub const EGLD_WRAPPER_WASM_PATH: &str = "egld-wrapper-mock/output/egld-wrapper-mock.wasm";
 pub const MEX_TOKEN_ID: &[u8] = b"MEX-abcdef";
 pub const OTHER_TOKEN_ID: &[u8] = b"OTHER-abcdef";
 pub const WEGLD_TOKEN_ID: &[u8] = b"WEGLD-abcdef";
@@ -21,9 +25,12 @@ pub const USER_TOTAL_WEGLD_TOKENS: u64 = 5_000_000_000;
 
 use pair::config::ConfigModule as PairConfigModule;
 use pair::pair_actions::add_liq::AddLiquidityModule;
+use pair::pair_actions::flash_swap::FlashSwapModule;
+use pair::pair_actions::single_token_liq::SingleTokenLiquidityModule;
 use pair::pair_actions::swap::SwapModule;
 use pair::safe_price::SafePriceModule;
 use pair::safe_price_view::*;
+use pair::stable_swap::StableSwapModule;
 use pair::*;
 use pausable::{PausableModule, State};
 
@@ -180,6 +187,45 @@ where
             .assert_ok();
     }
 
+    pub fn add_liquidity_single_token(
+        &mut self,
+        payment_token_id: &[u8],
+        payment_amount: u64,
+        min_lp_out: u64,
+    ) -> TxResult {
+        self.b_mock.execute_esdt_transfer(
+            &self.user_address,
+            &self.pair_wrapper,
+            payment_token_id,
+            0,
+            &rust_biguint!(payment_amount),
+            |sc| {
+                let _ = sc.add_liquidity_single_token(managed_biguint!(min_lp_out));
+            },
+        )
+    }
+
+    pub fn remove_liquidity_to_single_token(
+        &mut self,
+        lp_amount: u64,
+        token_out: &[u8],
+        min_amount_out: u64,
+    ) -> TxResult {
+        self.b_mock.execute_esdt_transfer(
+            &self.user_address,
+            &self.pair_wrapper,
+            LP_TOKEN_ID,
+            0,
+            &rust_biguint!(lp_amount),
+            |sc| {
+                let _ = sc.remove_liquidity_to_single_token(
+                    managed_token_id!(token_out),
+                    managed_biguint!(min_amount_out),
+                );
+            },
+        )
+    }
+
     pub fn swap_fixed_input(
         &mut self,
         payment_token_id: &[u8],
@@ -199,6 +245,7 @@ where
                     let ret = sc.swap_tokens_fixed_input(
                         managed_token_id!(desired_token_id),
                         managed_biguint!(desired_amount_min),
+                        OptionalValue::None,
                     );
 
                     assert_eq!(ret.token_identifier, managed_token_id!(desired_token_id));
@@ -238,6 +285,7 @@ where
                     let ret = sc.swap_tokens_fixed_output(
                         managed_token_id!(desired_token_id),
                         managed_biguint!(desired_amount),
+                        OptionalValue::None,
                     );
 
                     let (desired_token_output, payment_token_residuum) = ret.into_tuple();
@@ -275,6 +323,52 @@ where
         );
     }
 
+    pub fn flash_swap(
+        &mut self,
+        borrower: &Address,
+        token_out: &[u8],
+        amount_out: u64,
+        callback_endpoint: &str,
+        callback_args: Vec<&[u8]>,
+    ) -> TxResult {
+        self.b_mock
+            .execute_tx(borrower, &self.pair_wrapper, &rust_biguint!(0), |sc| {
+                let mut args = MultiValueEncoded::new();
+                for arg in callback_args {
+                    args.push(managed_buffer!(arg));
+                }
+
+                sc.flash_swap(
+                    managed_token_id!(token_out),
+                    managed_biguint!(amount_out),
+                    managed_buffer!(callback_endpoint.as_bytes()),
+                    args,
+                );
+            })
+    }
+
+    pub fn enable_stable_swap(
+        &mut self,
+        amplification: u64,
+        first_token_rate_multiplier: u64,
+        second_token_rate_multiplier: u64,
+    ) {
+        self.b_mock
+            .execute_tx(
+                &self.owner_address,
+                &self.pair_wrapper,
+                &rust_biguint!(0),
+                |sc| {
+                    sc.enable_stable_swap(
+                        amplification,
+                        managed_biguint!(first_token_rate_multiplier),
+                        managed_biguint!(second_token_rate_multiplier),
+                    );
+                },
+            )
+            .assert_ok();
+    }
+
     pub fn check_lp_amount(&mut self, expected_amount: u64) {
         self.b_mock
             .execute_query(&self.pair_wrapper, |sc| {
@@ -6,9 +6,9 @@
 
 // Init:                                 1
 // Upgrade:                              1
-// Endpoints:                           63
+// Endpoints:                          106
 // Async Callback (empty):               1
-// Total number of exported functions:  66
+// Total number of exported functions: 109
 
 #![no_std]
 
@@ -21,18 +21,39 @@ multiversx_sc_wasm_adapter::endpoints! {
         init => init
         upgrade => upgrade
         setLpTokenIdentifier => set_lp_token_identifier
+        enableStableSwap => enable_stable_swap
+        rampAmplification => ramp_amplification
+        stopRampAmplification => stop_ramp_amplification
+        getAmplification => get_current_amplification
+        getPoolType => pool_type
+        getAmplificationRamp => amplification_ramp
+        getFirstTokenRateMultiplier => first_token_rate_multiplier
+        getSecondTokenRateMultiplier => second_token_rate_multiplier
         getFeeState => is_fee_enabled
         whitelist => whitelist_endpoint
         removeWhitelist => remove_whitelist
         addTrustedSwapPair => add_trusted_swap_pair
         removeTrustedSwapPair => remove_trusted_swap_pair
         setupFeesCollector => setup_fees_collector
+        addTreasuryFeeDestination => add_treasury_fee_destination
+        removeTreasuryFeeDestination => remove_treasury_fee_destination
+        setFeeDestinationWeights => set_fee_destination_weights
         setFeeOn => set_fee_on
         getFeeDestinations => get_fee_destinations
         getTrustedSwapPairs => get_trusted_swap_pairs
         getWhitelistedManagedAddresses => get_whitelisted_managed_addresses
+        getFeeDestinationWeights => get_fee_destination_weights
+        getFeeDestinationAccumulatedFees => get_fee_destination_accumulated_fees
         getFeesCollectorAddress => fees_collector_address
         getFeesCollectorCutPercentage => fees_collector_cut_percentage
+        getTreasuryFeeDestinations => treasury_fee_destinations
+        setFeeDiscountTiers => set_fee_discount_tiers
+        addTrustedSwapProxy => add_trusted_swap_proxy
+        removeTrustedSwapProxy => remove_trusted_swap_proxy
+        getFeeDiscountPercent => get_fee_discount_percent
+        getFeePercentsForAddress => get_fee_percents_for_address
+        getFeeDiscountTiers => fee_discount_tiers
+        getTrustedSwapProxies => trusted_swap_proxies
         setStateActiveNoSwaps => set_state_active_no_swaps
         setFeePercents => set_fee_percent
         getLpTokenIdentifier => get_lp_token_identifier
@@ -44,9 +65,17 @@ multiversx_sc_wasm_adapter::endpoints! {
         getTotalSupply => lp_token_supply
         getInitialLiquidtyAdder => initial_liquidity_adder
         getReserve => pair_reserve
+        getEgldWrapperAddress => egld_wrapper_address
         getSafePriceCurrentIndex => safe_price_current_index
         updateAndGetTokensForGivenPositionWithSafePrice => update_and_get_tokens_for_given_position_with_safe_price
         updateAndGetSafePrice => update_and_get_safe_price
+        setDynamicFeeConfig => set_dynamic_fee_config
+        disableDynamicFee => disable_dynamic_fee
+        getEffectiveFeePercent => get_effective_fee_percent
+        getDynamicFeeConfig => dynamic_fee_config
+        setCircuitBreakerConfig => set_circuit_breaker_config
+        disableCircuitBreaker => disable_circuit_breaker
+        getCircuitBreakerConfig => circuit_breaker_config
         setLockingDeadlineEpoch => set_locking_deadline_epoch
         setLockingScAddress => set_locking_sc_address
         setUnlockEpoch => set_unlock_epoch
@@ -69,11 +98,25 @@ multiversx_sc_wasm_adapter::endpoints! {
         swapNoFeeAndForward => swap_no_fee
         swapTokensFixedInput => swap_tokens_fixed_input
         swapTokensFixedOutput => swap_tokens_fixed_output
+        flashSwap => flash_swap
+        addLiquiditySingleToken => add_liquidity_single_token
+        removeLiquidityToSingleToken => remove_liquidity_to_single_token
+        skim => skim
+        sync => sync
+        setEgldWrapperAddress => set_egld_wrapper_address
+        addLiquidityEgld => add_liquidity_egld
+        removeLiquidityEgld => remove_liquidity_egld
         getTokensForGivenPosition => get_tokens_for_given_position
         getReservesAndTotalSupply => get_reserves_and_total_supply
         getAmountOut => get_amount_out_view
+        getAmountOutForAddress => get_amount_out_for_address_view
         getAmountIn => get_amount_in_view
+        getAmountInForAddress => get_amount_in_for_address_view
+        getSwapQuotes => get_swap_quotes
+        getMaxAmountInForPriceImpact => get_max_amount_in_for_price_impact
         getEquivalent => get_equivalent
+        setEnergyFactoryAddress => set_energy_factory_address
+        getEnergyFactoryAddress => energy_factory_address
         getLpTokensSafePriceByDefaultOffset => get_lp_tokens_safe_price_by_default_offset
         getLpTokensSafePriceByRoundOffset => get_lp_tokens_safe_price_by_round_offset
         getLpTokensSafePriceByTimestampOffset => get_lp_tokens_safe_price_by_timestamp_offset
@@ -1,7 +1,10 @@
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
-use crate::{enable_swap_by_user::EnableSwapByUserConfig, factory::PairTokens};
+use crate::{
+    enable_swap_by_user::EnableSwapByUserConfig,
+    factory::{FeeTierPairTokens, PairTokens},
+};
 use pair::read_pair_storage;
 
 #[multiversx_sc::module]
@@ -28,7 +31,13 @@ pub trait ConfigModule: read_pair_storage::ReadPairStorageModule {
             pair_map_address_opt = self.pair_map().get(&reverse_pair_tokens);
         }
 
-        require!(pair_map_address_opt.is_some(), "Not a pair SC");
+        if pair_map_address_opt.is_none() {
+            require!(
+                !self.pair_fee_tier(pair_address).is_empty(),
+                "Not a pair SC"
+            );
+            return;
+        }
 
         unsafe {
             let pair_map_address = pair_map_address_opt.unwrap_unchecked();
@@ -63,6 +72,25 @@ pub trait ConfigModule: read_pair_storage::ReadPairStorageModule {
     #[storage_mapper("pair_map")]
     fn pair_map(&self) -> MapMapper<PairTokens<Self::Api>, ManagedAddress>;
 
+    /// Total fee percent to special fee percent of each fee tier.
+    #[view(getFeeTiers)]
+    #[storage_mapper("feeTiers")]
+    fn fee_tiers(&self) -> MapMapper<u64, u64>;
+
+    #[storage_mapper("feeTierPairMap")]
+    fn fee_tier_pair_map(&self) -> MapMapper<FeeTierPairTokens<Self::Api>, ManagedAddress>;
+
+    #[storage_mapper("pairFeeTier")]
+    fn pair_fee_tier(&self, pair_address: &ManagedAddress) -> SingleValueMapper<u64>;
+
+    /// Set for pairs deployed or upgraded from a template that accepts
+    /// the original caller argument on swaps.
+    #[storage_mapper("pairAcceptsOriginalCaller")]
+    fn pair_accepts_original_caller(
+        &self,
+        pair_address: &ManagedAddress,
+    ) -> SingleValueMapper<bool>;
+
     #[view(getPairTemplateAddress)]
     #[storage_mapper("pair_template_address")]
     fn pair_template_address(&self) -> SingleValueMapper<ManagedAddress>;
@@ -9,11 +9,16 @@ pub mod enable_swap_by_user;
 mod events;
 pub mod factory;
 pub mod multi_pair_swap;
+pub mod multi_path_swap;
+pub mod referral;
+pub mod swap_path;
 
-use factory::PairTokens;
+use energy_query::ProxyTrait as _;
+use factory::{FeeTierPairTokens, PairTokens, UpgradeAllPairsStep, MIN_GAS_TO_SAVE_PROGRESS};
+use multiversx_sc_modules::ongoing_operation::{CONTINUE_OP, STOP_OP};
 use pair::config::ProxyTrait as _;
 use pair::fee::ProxyTrait as _;
-use pair::{read_pair_storage, ProxyTrait as _};
+use pair::{egld_wrapper, read_pair_storage, ProxyTrait as _};
 use pausable::ProxyTrait as _;
 
 const LP_TOKEN_DECIMALS: usize = 18;
@@ -30,9 +35,14 @@ pub trait Router:
     + read_pair_storage::ReadPairStorageModule
     + factory::FactoryModule
     + events::EventsModule
+    + egld_wrapper::EgldWrapperModule
+    + referral::ReferralModule
     + multi_pair_swap::MultiPairSwap
+    + multi_path_swap::MultiPathSwapModule
+    + swap_path::SwapPathModule
     + token_send::TokenSendModule
     + enable_swap_by_user::EnableSwapByUserModule
+    + multiversx_sc_modules::ongoing_operation::OngoingOperationModule
 {
     #[init]
     fn init(&self, pair_template_address_opt: OptionalValue<ManagedAddress>) {
@@ -106,7 +116,11 @@ pub trait Router:
             second_token_id.is_valid_esdt_identifier(),
             "Second Token ID is not a valid esdt token ID"
         );
-        let pair_address = self.get_pair(first_token_id.clone(), second_token_id.clone());
+        let pair_address = self.get_pair(
+            first_token_id.clone(),
+            second_token_id.clone(),
+            OptionalValue::None,
+        );
         require!(pair_address.is_zero(), "Pair already exists");
 
         let mut total_fee_percent_requested = DEFAULT_TOTAL_FEE_PERCENT;
@@ -151,6 +165,94 @@ pub trait Router:
         address
     }
 
+    /// Creates a pair with the fees of one of the tiers registered by the owner.
+    /// Multiple pairs can exist for the same tokens, one for each fee tier.
+    #[endpoint(createPairWithFeeTier)]
+    fn create_pair_with_fee_tier_endpoint(
+        &self,
+        first_token_id: TokenIdentifier,
+        second_token_id: TokenIdentifier,
+        initial_liquidity_adder: ManagedAddress,
+        total_fee_percent: u64,
+        mut admins: MultiValueEncoded<ManagedAddress>,
+    ) -> ManagedAddress {
+        require!(self.is_active(), "Not active");
+        let owner = self.owner().get();
+        let caller = self.blockchain().get_caller();
+
+        if caller != owner {
+            require!(
+                self.pair_creation_enabled().get(),
+                "Pair creation is disabled"
+            );
+        }
+
+        require!(first_token_id != second_token_id, "Identical tokens");
+        require!(
+            first_token_id.is_valid_esdt_identifier(),
+            "First Token ID is not a valid esdt token ID"
+        );
+        require!(
+            second_token_id.is_valid_esdt_identifier(),
+            "Second Token ID is not a valid esdt token ID"
+        );
+
+        let special_fee_percent = match self.fee_tiers().get(&total_fee_percent) {
+            Some(special_fee_percent) => special_fee_percent,
+            None => sc_panic!("Invalid fee tier"),
+        };
+        let pair_address = self.get_fee_tier_pair(
+            first_token_id.clone(),
+            second_token_id.clone(),
+            total_fee_percent,
+        );
+        require!(pair_address.is_zero(), "Pair already exists");
+
+        admins.push(caller.clone());
+
+        let address = self.create_fee_tier_pair(
+            &first_token_id,
+            &second_token_id,
+            &owner,
+            total_fee_percent,
+            special_fee_percent,
+            &initial_liquidity_adder,
+            admins,
+        );
+
+        self.emit_create_pair_event(
+            caller,
+            first_token_id,
+            second_token_id,
+            total_fee_percent,
+            special_fee_percent,
+            address.clone(),
+        );
+        address
+    }
+
+    /// Registers a fee tier, or updates its special fee percent.
+    /// Pairs already created with the tier keep their fees.
+    #[only_owner]
+    #[endpoint(addFeeTier)]
+    fn add_fee_tier(&self, total_fee_percent: u64, special_fee_percent: u64) {
+        require!(
+            total_fee_percent >= special_fee_percent && total_fee_percent < MAX_TOTAL_FEE_PERCENT,
+            "Bad percents"
+        );
+
+        self.fee_tiers()
+            .insert(total_fee_percent, special_fee_percent);
+    }
+
+    /// New pairs can no longer be created with the tier. The existing ones are not affected.
+    #[only_owner]
+    #[endpoint(removeFeeTier)]
+    fn remove_fee_tier(&self, total_fee_percent: u64) {
+        let removed = self.fee_tiers().remove(&total_fee_percent);
+        require!(removed.is_some(), "Invalid fee tier");
+    }
+
     #[only_owner]
     #[endpoint(upgradePair)]
     fn upgrade_pair_endpoint(
@@ -169,12 +271,73 @@ pub trait Router:
             second_token_id.is_valid_esdt_identifier(),
             "Second Token ID is not a valid esdt token ID"
         );
-        let pair_address = self.get_pair(first_token_id.clone(), second_token_id.clone());
+        let pair_address = self.get_pair(
+            first_token_id.clone(),
+            second_token_id.clone(),
+            OptionalValue::None,
+        );
         require!(!pair_address.is_zero(), "Pair does not exists");
 
+        self.emit_upgrade_pair_event(
+            first_token_id,
+            second_token_id,
+            pair_address.clone(),
+            self.pair_template_address().get(),
+        );
         self.upgrade_pair(pair_address);
     }
 
+    /// Will attempt to upgrade all the pairs from the pair template.
+    /// Returns "completed" if all were upgraded.
+    /// Otherwise, it will save progress and return "interrupted",
+    /// and will require more calls to complete.
+    /// The pairs are snapshotted first, across as many calls as needed, so pairs created
+    /// or removed while the upgrade is in progress do not shift the remaining ones.
+    #[only_owner]
+    #[endpoint(upgradeAllPairs)]
+    fn upgrade_all_pairs(&self) -> OperationCompletionStatus {
+        require!(self.is_active(), "Not active");
+        require!(
+            !self.pair_template_address().is_empty(),
+            "pair contract template is empty"
+        );
+
+        let pair_template_address = self.pair_template_address().get();
+        let mut pairs_to_upgrade = self.pairs_to_upgrade();
+        let mut step: UpgradeAllPairsStep<Self::Api> = self.load_operation();
+        let run_result = self.run_while_it_has_gas(MIN_GAS_TO_SAVE_PROGRESS, || {
+            let pair_index = match step {
+                UpgradeAllPairsStep::UpgradePairs(pair_index) => pair_index,
+                _ => {
+                    step = self.snapshot_pair_to_upgrade(core::mem::take(&mut step));
+                    return CONTINUE_OP;
+                }
+            };
+            if pair_index >= pairs_to_upgrade.len() {
+                return STOP_OP;
+            }
+
+            let pair = pairs_to_upgrade.get(pair_index + 1);
+            self.upgrade_pair_sync(&pair.address, &pair_template_address);
+            self.emit_upgrade_pair_event(
+                pair.first_token_id,
+                pair.second_token_id,
+                pair.address,
+                pair_template_address.clone(),
+            );
+            step = UpgradeAllPairsStep::UpgradePairs(pair_index + 1);
+
+            CONTINUE_OP
+        });
+        if run_result == OperationCompletionStatus::InterruptedBeforeOutOfGas {
+            self.save_progress(&step);
+        } else {
+            pairs_to_upgrade.clear();
+        }
+
+        run_result
+    }
+
     #[payable("EGLD")]
     #[endpoint(issueLpToken)]
     fn issue_lp_token(
@@ -277,12 +440,15 @@ pub trait Router:
             second_token_id.is_valid_esdt_identifier(),
             "Second Token ID is not a valid esdt token ID"
         );
-        let mut pair_address = self.get_pair(first_token_id.clone(), second_token_id.clone());
+        let mut pair_address = self.get_pair(
+            first_token_id.clone(),
+            second_token_id.clone(),
+            OptionalValue::None,
+        );
         require!(!pair_address.is_zero(), "Pair does not exists");
 
         pair_address = self
-            .pair_map()
-            .remove(&PairTokens {
+            .remove_from_pair_map(&PairTokens {
                 first_token_id: first_token_id.clone(),
                 second_token_id: second_token_id.clone(),
             })
@@ -290,13 +456,55 @@ pub trait Router:
 
         if pair_address.is_zero() {
             pair_address = self
-                .pair_map()
-                .remove(&PairTokens {
+                .remove_from_pair_map(&PairTokens {
                     first_token_id: second_token_id,
                     second_token_id: first_token_id,
                 })
                 .unwrap_or_else(ManagedAddress::zero);
         }
+        self.pair_accepts_original_caller(&pair_address).clear();
+
+        pair_address
+    }
+
+    #[only_owner]
+    #[endpoint(removeFeeTierPair)]
+    fn remove_fee_tier_pair(
+        &self,
+        first_token_id: TokenIdentifier,
+        second_token_id: TokenIdentifier,
+        total_fee_percent: u64,
+    ) -> ManagedAddress {
+        require!(self.is_active(), "Not active");
+
+        let mut pair_address = self
+            .remove_from_fee_tier_pair_map(&FeeTierPairTokens {
+                first_token_id: first_token_id.clone(),
+                second_token_id: second_token_id.clone(),
+                total_fee_percent,
+            })
+            .unwrap_or_else(ManagedAddress::zero);
+
+        if pair_address.is_zero() {
+            pair_address = self
+                .remove_from_fee_tier_pair_map(&FeeTierPairTokens {
+                    first_token_id: second_token_id.clone(),
+                    second_token_id: first_token_id.clone(),
+                    total_fee_percent,
+                })
+                .unwrap_or_else(ManagedAddress::zero);
+        }
+        require!(!pair_address.is_zero(), "Pair does not exists");
+
+        self.pair_fee_tier(&pair_address).clear();
+        self.pair_accepts_original_caller(&pair_address).clear();
+
+        self.emit_remove_fee_tier_pair_event(
+            first_token_id,
+            second_token_id,
+            total_fee_percent,
+            pair_address.clone(),
+        );
 
         pair_address
     }
@@ -335,6 +543,40 @@ pub trait Router:
             .execute_on_dest_context();
     }
 
+    /// The energy factory is used by the pair to compute the swap fee discounts.
+    #[only_owner]
+    #[endpoint(setPairEnergyFactoryAddress)]
+    fn set_pair_energy_factory_address(
+        &self,
+        pair_address: ManagedAddress,
+        energy_factory_address: ManagedAddress,
+    ) {
+        require!(self.is_active(), "Not active");
+        self.check_is_pair_sc(&pair_address);
+
+        let _: IgnoreValue = self
+            .pair_contract_proxy(pair_address)
+            .set_energy_factory_address(energy_factory_address)
+            .execute_on_dest_context();
+    }
+
+    /// Has to be set once the pairs run a version that accepts the original caller
+    /// argument on swaps. The swaps of the router then pass it, so the fee discount
+    /// of the user is applied.
+    #[only_owner]
+    #[endpoint(setPairsAcceptOriginalCaller)]
+    fn set_pairs_accept_original_caller(
+        &self,
+        accepts: bool,
+        pair_addresses: MultiValueEncoded<ManagedAddress>,
+    ) {
+        for pair_address in pair_addresses {
+            self.check_is_pair_sc(&pair_address);
+            self.pair_accepts_original_caller(&pair_address)
+                .set(accepts);
+        }
+    }
+
     #[only_owner]
     #[endpoint(claimDeveloperRewardsPairs)]
     fn claim_developer_rewards_pairs(&self, pairs: MultiValueEncoded<ManagedAddress>) {
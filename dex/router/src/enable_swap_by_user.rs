@@ -30,6 +30,7 @@ pub trait EnableSwapByUserModule:
     config::ConfigModule
     + read_pair_storage::ReadPairStorageModule
     + crate::factory::FactoryModule
+    + multiversx_sc_modules::ongoing_operation::OngoingOperationModule
     + crate::events::EventsModule
 {
     #[only_owner]
@@ -134,7 +135,10 @@ pub trait EnableSwapByUserModule:
         let caller = self.blockchain().get_caller();
         self.require_caller_initial_liquidity_adder(&pair_address, &caller);
 
-        self.set_fee_percents(pair_address.clone());
+        // pairs created with a fee tier keep the fees of their tier
+        if self.pair_fee_tier(&pair_address).is_empty() {
+            self.set_fee_percents(pair_address.clone());
+        }
         self.pair_resume(pair_address.clone());
 
         self.send().direct_esdt(
@@ -14,6 +14,18 @@ pub struct CreatePairEvent<M: ManagedTypeApi> {
     timestamp: u64,
 }
 
+#[derive(TypeAbi, TopEncode)]
+pub struct RemoveFeeTierPairEvent<M: ManagedTypeApi> {
+    caller: ManagedAddress<M>,
+    first_token_id: TokenIdentifier<M>,
+    second_token_id: TokenIdentifier<M>,
+    total_fee_percent: u64,
+    pair_address: ManagedAddress<M>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
 #[derive(TypeAbi, TopEncode)]
 pub struct UserPairSwapEnabledEvent<M: ManagedTypeApi> {
     caller: ManagedAddress<M>,
@@ -33,6 +45,56 @@ pub struct MultiPairSwapEvent<M: ManagedTypeApi> {
     block: u64,
     epoch: u64,
     timestamp: u64,
+    destination: ManagedAddress<M>,
+    min_amount_out: BigUint<M>,
+    deadline: u64,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct MultiPathSwapEvent<M: ManagedTypeApi> {
+    caller: ManagedAddress<M>,
+    token_in: TokenIdentifier<M>,
+    amount_in: BigUint<M>,
+    token_out: TokenIdentifier<M>,
+    amount_out: BigUint<M>,
+    min_amount_out: BigUint<M>,
+    route_amounts_in: ManagedVec<M, BigUint<M>>,
+    payments_out: ManagedVec<M, EsdtTokenPayment<M>>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct UpgradePairEvent<M: ManagedTypeApi> {
+    caller: ManagedAddress<M>,
+    first_token_id: TokenIdentifier<M>,
+    second_token_id: TokenIdentifier<M>,
+    pair_address: ManagedAddress<M>,
+    pair_template_address: ManagedAddress<M>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct ReferralFeeEvent<M: ManagedTypeApi> {
+    caller: ManagedAddress<M>,
+    referrer: ManagedAddress<M>,
+    token_id: TokenIdentifier<M>,
+    fee_amount: BigUint<M>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
+}
+
+#[derive(TypeAbi, TopEncode)]
+pub struct ClaimReferralFeesEvent<M: ManagedTypeApi> {
+    referrer: ManagedAddress<M>,
+    payments: ManagedVec<M, EsdtTokenPayment<M>>,
+    block: u64,
+    epoch: u64,
+    timestamp: u64,
 }
 
 #[multiversx_sc::module]
@@ -66,6 +128,33 @@ pub trait EventsModule {
         )
     }
 
+    fn emit_remove_fee_tier_pair_event(
+        &self,
+        first_token_id: TokenIdentifier,
+        second_token_id: TokenIdentifier,
+        total_fee_percent: u64,
+        pair_address: ManagedAddress,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.remove_fee_tier_pair_event(
+            first_token_id.clone(),
+            second_token_id.clone(),
+            caller.clone(),
+            epoch,
+            RemoveFeeTierPairEvent {
+                caller,
+                first_token_id,
+                second_token_id,
+                total_fee_percent,
+                pair_address,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
     fn emit_user_swaps_enabled_event(
         &self,
         caller: ManagedAddress,
@@ -94,6 +183,9 @@ pub trait EventsModule {
         token_in: TokenIdentifier,
         amount_in: BigUint,
         payments_out: ManagedVec<EsdtTokenPayment>,
+        destination: ManagedAddress,
+        min_amount_out: BigUint,
+        deadline: u64,
     ) {
         if payments_out.is_empty() {
             return;
@@ -121,6 +213,121 @@ pub trait EventsModule {
                 block,
                 epoch,
                 timestamp,
+                destination,
+                min_amount_out,
+                deadline,
+            },
+        )
+    }
+
+    fn emit_multi_path_swap_event(
+        &self,
+        caller: ManagedAddress,
+        token_in: TokenIdentifier,
+        amount_in: BigUint,
+        min_amount_out: BigUint,
+        route_amounts_in: ManagedVec<BigUint>,
+        payments_out: ManagedVec<EsdtTokenPayment>,
+    ) {
+        if payments_out.is_empty() {
+            return;
+        }
+
+        let block = self.blockchain().get_block_nonce();
+        let epoch = self.blockchain().get_block_epoch();
+        let timestamp = self.blockchain().get_block_timestamp();
+        let last_payment_index = payments_out.len() - 1;
+        let token_out = payments_out.get(last_payment_index);
+        self.multi_path_swap_event(
+            caller.clone(),
+            token_in.clone(),
+            amount_in.clone(),
+            token_out.token_identifier.clone(),
+            token_out.amount.clone(),
+            epoch,
+            MultiPathSwapEvent {
+                caller,
+                token_in,
+                amount_in,
+                token_out: token_out.token_identifier,
+                amount_out: token_out.amount,
+                min_amount_out,
+                route_amounts_in,
+                payments_out,
+                block,
+                epoch,
+                timestamp,
+            },
+        )
+    }
+
+    fn emit_upgrade_pair_event(
+        &self,
+        first_token_id: TokenIdentifier,
+        second_token_id: TokenIdentifier,
+        pair_address: ManagedAddress,
+        pair_template_address: ManagedAddress,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.upgrade_pair_event(
+            first_token_id.clone(),
+            second_token_id.clone(),
+            pair_address.clone(),
+            epoch,
+            UpgradePairEvent {
+                caller,
+                first_token_id,
+                second_token_id,
+                pair_address,
+                pair_template_address,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_referral_fee_event(
+        &self,
+        referrer: ManagedAddress,
+        token_id: TokenIdentifier,
+        fee_amount: BigUint,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let epoch = self.blockchain().get_block_epoch();
+        self.referral_fee_event(
+            referrer.clone(),
+            caller.clone(),
+            token_id.clone(),
+            epoch,
+            ReferralFeeEvent {
+                caller,
+                referrer,
+                token_id,
+                fee_amount,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
+            },
+        )
+    }
+
+    fn emit_claim_referral_fees_event(
+        &self,
+        referrer: ManagedAddress,
+        payments: ManagedVec<EsdtTokenPayment>,
+    ) {
+        let epoch = self.blockchain().get_block_epoch();
+        self.claim_referral_fees_event(
+            referrer.clone(),
+            epoch,
+            ClaimReferralFeesEvent {
+                referrer,
+                payments,
+                block: self.blockchain().get_block_nonce(),
+                epoch,
+                timestamp: self.blockchain().get_block_timestamp(),
             },
         )
     }
@@ -135,6 +342,16 @@ pub trait EventsModule {
         swap_event: CreatePairEvent<Self::Api>,
     );
 
+    #[event("removeFeeTierPair")]
+    fn remove_fee_tier_pair_event(
+        &self,
+        #[indexed] first_token_id: TokenIdentifier,
+        #[indexed] second_token_id: TokenIdentifier,
+        #[indexed] caller: ManagedAddress,
+        #[indexed] epoch: u64,
+        remove_fee_tier_pair_event: RemoveFeeTierPairEvent<Self::Api>,
+    );
+
     #[event("pairSwapEnabled")]
     fn pair_swap_enabled_event(
         &self,
@@ -156,4 +373,44 @@ pub trait EventsModule {
         #[indexed] epoch: u64,
         multi_pair_swap_event: MultiPairSwapEvent<Self::Api>,
     );
+
+    #[event("multiPathSwap")]
+    fn multi_path_swap_event(
+        &self,
+        #[indexed] caller: ManagedAddress,
+        #[indexed] token_in: TokenIdentifier,
+        #[indexed] amount_in: BigUint,
+        #[indexed] token_out: TokenIdentifier,
+        #[indexed] amount_out: BigUint,
+        #[indexed] epoch: u64,
+        multi_path_swap_event: MultiPathSwapEvent<Self::Api>,
+    );
+
+    #[event("upgradePair")]
+    fn upgrade_pair_event(
+        &self,
+        #[indexed] first_token_id: TokenIdentifier,
+        #[indexed] second_token_id: TokenIdentifier,
+        #[indexed] pair_address: ManagedAddress,
+        #[indexed] epoch: u64,
+        upgrade_pair_event: UpgradePairEvent<Self::Api>,
+    );
+
+    #[event("referralFee")]
+    fn referral_fee_event(
+        &self,
+        #[indexed] referrer: ManagedAddress,
+        #[indexed] caller: ManagedAddress,
+        #[indexed] token_id: TokenIdentifier,
+        #[indexed] epoch: u64,
+        referral_fee_event: ReferralFeeEvent<Self::Api>,
+    );
+
+    #[event("claimReferralFees")]
+    fn claim_referral_fees_event(
+        &self,
+        #[indexed] referrer: ManagedAddress,
+        #[indexed] epoch: u64,
+        claim_referral_fees_event: ClaimReferralFeesEvent<Self::Api>,
+    );
 }
@@ -2,9 +2,11 @@ multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
 use crate::config;
+use multiversx_sc_modules::ongoing_operation;
 use pair::read_pair_storage;
 
 const TEMPORARY_OWNER_PERIOD_BLOCKS: u64 = 50;
+pub const MIN_GAS_TO_SAVE_PROGRESS: u64 = 10_000_000;
 
 #[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, TypeAbi)]
 pub struct PairTokens<M: ManagedTypeApi> {
@@ -14,13 +16,43 @@ pub struct PairTokens<M: ManagedTypeApi> {
 
 #[derive(ManagedVecItem, TopEncode, TopDecode, PartialEq, TypeAbi)]
 pub struct PairContractMetadata<M: ManagedTypeApi> {
+    pub first_token_id: TokenIdentifier<M>,
+    pub second_token_id: TokenIdentifier<M>,
+    pub address: ManagedAddress<M>,
+}
+
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, TypeAbi)]
+pub struct FeeTierPairTokens<M: ManagedTypeApi> {
+    pub first_token_id: TokenIdentifier<M>,
+    pub second_token_id: TokenIdentifier<M>,
+    pub total_fee_percent: u64,
+}
+
+#[derive(ManagedVecItem, TopEncode, TopDecode, PartialEq, TypeAbi)]
+pub struct FeeTierPairContractMetadata<M: ManagedTypeApi> {
     first_token_id: TokenIdentifier<M>,
     second_token_id: TokenIdentifier<M>,
+    total_fee_percent: u64,
     address: ManagedAddress<M>,
 }
 
+/// Progress of upgradeAllPairs. The pairs are first snapshotted, one per step,
+/// starting from the given pair, and then upgraded in order, starting from the given index.
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Default)]
+pub enum UpgradeAllPairsStep<M: ManagedTypeApi> {
+    #[default]
+    Start,
+    SnapshotPairs(PairTokens<M>),
+    SnapshotFeeTierPairs(FeeTierPairTokens<M>),
+    UpgradePairs(usize),
+}
+
 #[multiversx_sc::module]
-pub trait FactoryModule: config::ConfigModule + read_pair_storage::ReadPairStorageModule {
+pub trait FactoryModule:
+    config::ConfigModule
+    + read_pair_storage::ReadPairStorageModule
+    + ongoing_operation::OngoingOperationModule
+{
     #[proxy]
     fn pair_contract_deploy_proxy(&self) -> pair::Proxy<Self::Api>;
 
@@ -42,6 +74,68 @@ pub trait FactoryModule: config::ConfigModule + read_pair_storage::ReadPairStora
         special_fee_percent: u64,
         initial_liquidity_adder: &ManagedAddress,
         admins: MultiValueEncoded<ManagedAddress>,
+    ) -> ManagedAddress {
+        let new_address = self.deploy_pair(
+            first_token_id,
+            second_token_id,
+            owner,
+            total_fee_percent,
+            special_fee_percent,
+            initial_liquidity_adder,
+            admins,
+        );
+
+        self.pair_map().insert(
+            PairTokens {
+                first_token_id: first_token_id.clone(),
+                second_token_id: second_token_id.clone(),
+            },
+            new_address.clone(),
+        );
+        new_address
+    }
+
+    fn create_fee_tier_pair(
+        &self,
+        first_token_id: &TokenIdentifier,
+        second_token_id: &TokenIdentifier,
+        owner: &ManagedAddress,
+        total_fee_percent: u64,
+        special_fee_percent: u64,
+        initial_liquidity_adder: &ManagedAddress,
+        admins: MultiValueEncoded<ManagedAddress>,
+    ) -> ManagedAddress {
+        let new_address = self.deploy_pair(
+            first_token_id,
+            second_token_id,
+            owner,
+            total_fee_percent,
+            special_fee_percent,
+            initial_liquidity_adder,
+            admins,
+        );
+
+        self.fee_tier_pair_map().insert(
+            FeeTierPairTokens {
+                first_token_id: first_token_id.clone(),
+                second_token_id: second_token_id.clone(),
+                total_fee_percent,
+            },
+            new_address.clone(),
+        );
+        self.pair_fee_tier(&new_address).set(total_fee_percent);
+        new_address
+    }
+
+    fn deploy_pair(
+        &self,
+        first_token_id: &TokenIdentifier,
+        second_token_id: &TokenIdentifier,
+        owner: &ManagedAddress,
+        total_fee_percent: u64,
+        special_fee_percent: u64,
+        initial_liquidity_adder: &ManagedAddress,
+        admins: MultiValueEncoded<ManagedAddress>,
     ) -> ManagedAddress {
         require!(
             !self.pair_template_address().is_empty(),
@@ -65,13 +159,6 @@ pub trait FactoryModule: config::ConfigModule + read_pair_storage::ReadPairStora
                 CodeMetadata::UPGRADEABLE | CodeMetadata::READABLE | CodeMetadata::PAYABLE_BY_SC,
             );
 
-        self.pair_map().insert(
-            PairTokens {
-                first_token_id: first_token_id.clone(),
-                second_token_id: second_token_id.clone(),
-            },
-            new_address.clone(),
-        );
         self.pair_temporary_owner().insert(
             new_address.clone(),
             (
@@ -94,12 +181,131 @@ pub trait FactoryModule: config::ConfigModule + read_pair_storage::ReadPairStora
             .upgrade_async_call_and_exit();
     }
 
+    /// Upgrades the pair synchronously, so multiple pairs can be upgraded in the same transaction.
+    fn upgrade_pair_sync(
+        &self,
+        pair_address: &ManagedAddress,
+        pair_template_address: &ManagedAddress,
+    ) {
+        let gas_left = self.blockchain().get_gas_left();
+        require!(
+            gas_left > MIN_GAS_TO_SAVE_PROGRESS,
+            "Not enough gas to upgrade pair"
+        );
+
+        let code_metadata =
+            CodeMetadata::UPGRADEABLE | CodeMetadata::READABLE | CodeMetadata::PAYABLE_BY_SC;
+        self.send_raw().upgrade_from_source_contract(
+            pair_address,
+            gas_left - MIN_GAS_TO_SAVE_PROGRESS,
+            &BigUint::zero(),
+            pair_template_address,
+            code_metadata,
+            &ManagedArgBuffer::new(),
+        );
+    }
+
+    /// Adds the next pair to the snapshot of upgradeAllPairs, and returns the following step.
+    /// The snapshot is built across calls, so an interrupted upgradeAllPairs
+    /// resumes over the same list even if pairs are created or removed meanwhile.
+    fn snapshot_pair_to_upgrade(
+        &self,
+        step: UpgradeAllPairsStep<Self::Api>,
+    ) -> UpgradeAllPairsStep<Self::Api> {
+        match step {
+            UpgradeAllPairsStep::Start => match self.pair_map_keys().front() {
+                Some(pair_tokens) => UpgradeAllPairsStep::SnapshotPairs(pair_tokens),
+                None => self.get_fee_tier_pairs_snapshot_start(),
+            },
+            UpgradeAllPairsStep::SnapshotPairs(pair_tokens) => {
+                let address = self.pair_map().get(&pair_tokens).unwrap();
+                let next_step = self.get_pairs_snapshot_step_after(&pair_tokens);
+                self.pairs_to_upgrade().push(&PairContractMetadata {
+                    first_token_id: pair_tokens.first_token_id,
+                    second_token_id: pair_tokens.second_token_id,
+                    address,
+                });
+
+                next_step
+            }
+            UpgradeAllPairsStep::SnapshotFeeTierPairs(pair_tokens) => {
+                let address = self.fee_tier_pair_map().get(&pair_tokens).unwrap();
+                let next_step = self.get_fee_tier_pairs_snapshot_step_after(&pair_tokens);
+                self.pairs_to_upgrade().push(&PairContractMetadata {
+                    first_token_id: pair_tokens.first_token_id,
+                    second_token_id: pair_tokens.second_token_id,
+                    address,
+                });
+
+                next_step
+            }
+            UpgradeAllPairsStep::UpgradePairs(_) => step,
+        }
+    }
+
+    fn get_pairs_snapshot_step_after(
+        &self,
+        pair_tokens: &PairTokens<Self::Api>,
+    ) -> UpgradeAllPairsStep<Self::Api> {
+        match self.pair_map_keys().next(pair_tokens) {
+            Some(next_pair_tokens) => UpgradeAllPairsStep::SnapshotPairs(next_pair_tokens),
+            None => self.get_fee_tier_pairs_snapshot_start(),
+        }
+    }
+
+    fn get_fee_tier_pairs_snapshot_start(&self) -> UpgradeAllPairsStep<Self::Api> {
+        match self.fee_tier_pair_map_keys().front() {
+            Some(pair_tokens) => UpgradeAllPairsStep::SnapshotFeeTierPairs(pair_tokens),
+            None => UpgradeAllPairsStep::UpgradePairs(0),
+        }
+    }
+
+    fn get_fee_tier_pairs_snapshot_step_after(
+        &self,
+        pair_tokens: &FeeTierPairTokens<Self::Api>,
+    ) -> UpgradeAllPairsStep<Self::Api> {
+        match self.fee_tier_pair_map_keys().next(pair_tokens) {
+            Some(next_pair_tokens) => UpgradeAllPairsStep::SnapshotFeeTierPairs(next_pair_tokens),
+            None => UpgradeAllPairsStep::UpgradePairs(0),
+        }
+    }
+
+    /// Removes the pair, moving an interrupted snapshot of upgradeAllPairs past it first,
+    /// as the snapshot resumes from the next pair to be added.
+    fn remove_from_pair_map(&self, pair_tokens: &PairTokens<Self::Api>) -> Option<ManagedAddress> {
+        let step: UpgradeAllPairsStep<Self::Api> = self.load_operation();
+        if let UpgradeAllPairsStep::SnapshotPairs(next_pair_tokens) = &step {
+            if next_pair_tokens == pair_tokens {
+                self.save_progress(&self.get_pairs_snapshot_step_after(pair_tokens));
+            }
+        }
+
+        self.pair_map().remove(pair_tokens)
+    }
+
+    fn remove_from_fee_tier_pair_map(
+        &self,
+        pair_tokens: &FeeTierPairTokens<Self::Api>,
+    ) -> Option<ManagedAddress> {
+        let step: UpgradeAllPairsStep<Self::Api> = self.load_operation();
+        if let UpgradeAllPairsStep::SnapshotFeeTierPairs(next_pair_tokens) = &step {
+            if next_pair_tokens == pair_tokens {
+                self.save_progress(&self.get_fee_tier_pairs_snapshot_step_after(pair_tokens));
+            }
+        }
+
+        self.fee_tier_pair_map().remove(pair_tokens)
+    }
+
     #[view(getAllPairsManagedAddresses)]
     fn get_all_pairs_addresses(&self) -> MultiValueEncoded<ManagedAddress> {
         let mut result = MultiValueEncoded::new();
         for pair in self.pair_map().values() {
             result.push(pair);
         }
+        for pair in self.fee_tier_pair_map().values() {
+            result.push(pair);
+        }
         result
     }
 
@@ -126,12 +332,36 @@ pub trait FactoryModule: config::ConfigModule + read_pair_storage::ReadPairStora
         result
     }
 
+    #[view(getAllFeeTierPairContractMetadata)]
+    fn get_all_fee_tier_pair_contract_metadata(
+        &self,
+    ) -> MultiValueEncoded<FeeTierPairContractMetadata<Self::Api>> {
+        let mut result = MultiValueEncoded::new();
+        for (k, v) in self.fee_tier_pair_map().iter() {
+            let pair_metadata = FeeTierPairContractMetadata {
+                first_token_id: k.first_token_id,
+                second_token_id: k.second_token_id,
+                total_fee_percent: k.total_fee_percent,
+                address: v,
+            };
+            result.push(pair_metadata);
+        }
+        result
+    }
+
+    /// Returns the default pair of the tokens, or the pair of the given fee tier,
+    /// if `opt_total_fee_percent` is provided. Returns the zero address if there is none.
     #[view(getPair)]
     fn get_pair(
         &self,
         first_token_id: TokenIdentifier,
         second_token_id: TokenIdentifier,
+        opt_total_fee_percent: OptionalValue<u64>,
     ) -> ManagedAddress {
+        if let OptionalValue::Some(total_fee_percent) = opt_total_fee_percent {
+            return self.get_fee_tier_pair(first_token_id, second_token_id, total_fee_percent);
+        }
+
         let mut address = self
             .pair_map()
             .get(&PairTokens {
@@ -152,6 +382,34 @@ pub trait FactoryModule: config::ConfigModule + read_pair_storage::ReadPairStora
         address
     }
 
+    fn get_fee_tier_pair(
+        &self,
+        first_token_id: TokenIdentifier,
+        second_token_id: TokenIdentifier,
+        total_fee_percent: u64,
+    ) -> ManagedAddress {
+        let mut address = self
+            .fee_tier_pair_map()
+            .get(&FeeTierPairTokens {
+                first_token_id: first_token_id.clone(),
+                second_token_id: second_token_id.clone(),
+                total_fee_percent,
+            })
+            .unwrap_or_else(ManagedAddress::zero);
+
+        if address.is_zero() {
+            address = self
+                .fee_tier_pair_map()
+                .get(&FeeTierPairTokens {
+                    first_token_id: second_token_id,
+                    second_token_id: first_token_id,
+                    total_fee_percent,
+                })
+                .unwrap_or_else(ManagedAddress::zero);
+        }
+        address
+    }
+
     fn get_pair_temporary_owner(&self, pair_address: &ManagedAddress) -> Option<ManagedAddress> {
         let result = self.pair_temporary_owner().get(pair_address);
 
@@ -177,4 +435,15 @@ pub trait FactoryModule: config::ConfigModule + read_pair_storage::ReadPairStora
         self.pair_temporary_owner().clear();
         size
     }
+
+    /// The keys of pair_map, used to resume its snapshot from a given pair.
+    #[storage_mapper("pair_map")]
+    fn pair_map_keys(&self) -> SetMapper<PairTokens<Self::Api>>;
+
+    /// The keys of fee_tier_pair_map, used to resume its snapshot from a given pair.
+    #[storage_mapper("feeTierPairMap")]
+    fn fee_tier_pair_map_keys(&self) -> SetMapper<FeeTierPairTokens<Self::Api>>;
+
+    #[storage_mapper("pairsToUpgrade")]
+    fn pairs_to_upgrade(&self) -> VecMapper<PairContractMetadata<Self::Api>>;
 }
@@ -2,12 +2,22 @@ multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
 use super::factory;
-use crate::{config, events};
-use pair::{pair_actions::swap::ProxyTrait as _, read_pair_storage};
+use crate::{config, events, referral};
+use pair::{egld_wrapper, pair_actions::swap::ProxyTrait as _, read_pair_storage};
 
-type SwapOperationType<M> =
+pub type SwapOperationType<M> =
     MultiValue4<ManagedAddress<M>, ManagedBuffer<M>, TokenIdentifier<M>, BigUint<M>>;
 
+#[derive(
+    ManagedVecItem, TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone, PartialEq,
+)]
+pub struct SwapOperation<M: ManagedTypeApi> {
+    pub pair_address: ManagedAddress<M>,
+    pub function: ManagedBuffer<M>,
+    pub token_wanted: TokenIdentifier<M>,
+    pub amount_wanted: BigUint<M>,
+}
+
 pub const SWAP_TOKENS_FIXED_INPUT_FUNC_NAME: &[u8] = b"swapTokensFixedInput";
 pub const SWAP_TOKENS_FIXED_OUTPUT_FUNC_NAME: &[u8] = b"swapTokensFixedOutput";
 
@@ -16,69 +26,242 @@ pub trait MultiPairSwap:
     config::ConfigModule
     + read_pair_storage::ReadPairStorageModule
     + factory::FactoryModule
+    + multiversx_sc_modules::ongoing_operation::OngoingOperationModule
     + token_send::TokenSendModule
     + events::EventsModule
+    + egld_wrapper::EgldWrapperModule
+    + referral::ReferralModule
 {
+    #[only_owner]
+    #[endpoint(setEgldWrapperAddress)]
+    fn set_egld_wrapper_address(&self, wrapper_address: ManagedAddress) {
+        require!(
+            self.blockchain().is_smart_contract(&wrapper_address),
+            "Invalid SC Address"
+        );
+
+        self.egld_wrapper_address().set(&wrapper_address);
+    }
+
+    /// EGLD payments are wrapped before the first swap.
+    ///
+    /// No referrer can be passed, as the swap operations are variable arguments.
+    /// Referred swaps go through multiPairSwapWithLimits.
     #[payable("*")]
     #[endpoint(multiPairSwap)]
     fn multi_pair_swap(
         &self,
         swap_operations: MultiValueEncoded<SwapOperationType<Self::Api>>,
+    ) -> ManagedVec<EsdtTokenPayment> {
+        let caller = self.blockchain().get_caller();
+        self.perform_multi_pair_swap(
+            caller,
+            BigUint::zero(),
+            0,
+            OptionalValue::None,
+            false,
+            self.collect_swap_operations(swap_operations),
+        )
+    }
+
+    /// Same as multiPairSwap, but the last swap has to output the wrapped EGLD,
+    /// which is unwrapped and sent as EGLD. The last returned payment is the unwrapped amount.
+    #[payable("*")]
+    #[endpoint(multiPairSwapToEgld)]
+    fn multi_pair_swap_to_egld(
+        &self,
+        swap_operations: MultiValueEncoded<SwapOperationType<Self::Api>>,
+    ) -> ManagedVec<EsdtTokenPayment> {
+        let caller = self.blockchain().get_caller();
+        self.perform_multi_pair_swap(
+            caller,
+            BigUint::zero(),
+            0,
+            OptionalValue::None,
+            true,
+            self.collect_swap_operations(swap_operations),
+        )
+    }
+
+    /// Same as multiPairSwap, but the whole transaction fails if the final output is
+    /// lower than `min_amount_out` or if the block timestamp is past `deadline`.
+    ///
+    /// The output and the residuals are sent to `destination`, or to the caller
+    /// if the zero address is given.
+    ///
+    /// The swap operations are passed as a single list, so that an optional `referrer`
+    /// can follow them. If given, the referral fee is taken from the input
+    /// and credited to the referrer.
+    #[payable("*")]
+    #[endpoint(multiPairSwapWithLimits)]
+    fn multi_pair_swap_with_limits(
+        &self,
+        min_amount_out: BigUint,
+        deadline: u64,
+        destination: ManagedAddress,
+        swap_operations: ManagedVec<SwapOperation<Self::Api>>,
+        opt_referrer: OptionalValue<ManagedAddress>,
+    ) -> ManagedVec<EsdtTokenPayment> {
+        require!(
+            self.blockchain().get_block_timestamp() <= deadline,
+            "Deadline passed"
+        );
+
+        let destination = if destination.is_zero() {
+            self.blockchain().get_caller()
+        } else {
+            destination
+        };
+        self.perform_multi_pair_swap(
+            destination,
+            min_amount_out,
+            deadline,
+            opt_referrer,
+            false,
+            swap_operations,
+        )
+    }
+
+    fn perform_multi_pair_swap(
+        &self,
+        destination: ManagedAddress,
+        min_amount_out: BigUint,
+        deadline: u64,
+        opt_referrer: OptionalValue<ManagedAddress>,
+        unwrap_egld_output: bool,
+        swap_operations: ManagedVec<SwapOperation<Self::Api>>,
     ) -> ManagedVec<EsdtTokenPayment> {
         require!(self.is_active(), "Not active");
 
-        let (token_id, nonce, amount) = self.call_value().single_esdt().into_tuple();
-        require!(nonce == 0, "Invalid nonce. Should be zero");
-        require!(amount > 0u64, "Invalid amount. Should not be zero");
+        let (token_id, nonce, amount) = self.get_swap_payment().into_tuple();
         require!(
             !swap_operations.is_empty(),
             "Invalid swap operations chain. Should not be empty"
         );
 
-        let swap_fixed_input_endpoint = ManagedBuffer::from(SWAP_TOKENS_FIXED_INPUT_FUNC_NAME);
-        let swap_fixed_output_endpoint = ManagedBuffer::from(SWAP_TOKENS_FIXED_OUTPUT_FUNC_NAME);
-
         let caller = self.blockchain().get_caller();
         let mut payments = ManagedVec::new();
         let mut last_payment = EsdtTokenPayment::new(token_id.clone(), nonce, amount.clone());
+        self.take_referral_fee(opt_referrer, &mut last_payment);
+
+        for swap_operation in swap_operations.iter() {
+            last_payment = self.perform_swap_operation(
+                last_payment,
+                swap_operation.pair_address,
+                swap_operation.function,
+                swap_operation.token_wanted,
+                swap_operation.amount_wanted,
+                &caller,
+                &mut payments,
+            );
+        }
+
+        require!(last_payment.amount >= min_amount_out, "Slippage exceeded");
+
+        if unwrap_egld_output {
+            let wrapper_address = self.get_egld_wrapper_address();
+            require!(
+                last_payment.token_identifier == self.get_wrapped_egld_token_id(wrapper_address),
+                "Last swap should output the wrapped EGLD"
+            );
+
+            self.send_multiple_tokens_if_not_zero(&destination, &payments);
+            self.unwrap_egld_payment(last_payment.clone());
+            self.send().direct_egld(&destination, &last_payment.amount);
+            payments.push(last_payment);
+        } else {
+            payments.push(last_payment);
+            self.send().direct_multi(&destination, &payments);
+        }
+
+        self.emit_multi_pair_swap_event(
+            caller,
+            token_id,
+            amount,
+            payments.clone(),
+            destination,
+            min_amount_out,
+            deadline,
+        );
+
+        payments
+    }
 
+    fn collect_swap_operations(
+        &self,
+        swap_operations: MultiValueEncoded<SwapOperationType<Self::Api>>,
+    ) -> ManagedVec<SwapOperation<Self::Api>> {
+        let mut result = ManagedVec::new();
         for entry in swap_operations.into_iter() {
             let (pair_address, function, token_wanted, amount_wanted) = entry.into_tuple();
-            self.check_is_pair_sc(&pair_address);
-
-            if function == swap_fixed_input_endpoint {
-                last_payment = self.actual_swap_fixed_input(
-                    pair_address,
-                    last_payment.token_identifier,
-                    last_payment.amount,
-                    token_wanted,
-                    amount_wanted,
-                );
-            } else if function == swap_fixed_output_endpoint {
-                let (payment, residuum) = self.actual_swap_fixed_output(
-                    pair_address,
-                    last_payment.token_identifier,
-                    last_payment.amount,
-                    token_wanted,
-                    amount_wanted,
-                );
-
-                last_payment = payment;
-
-                if residuum.amount > 0 {
-                    payments.push(residuum);
-                }
-            } else {
-                sc_panic!("Invalid function to call");
-            }
+            result.push(SwapOperation {
+                pair_address,
+                function,
+                token_wanted,
+                amount_wanted,
+            });
         }
 
-        payments.push(last_payment);
-        self.send().direct_multi(&caller, &payments);
+        result
+    }
 
-        self.emit_multi_pair_swap_event(caller, token_id, amount, payments.clone());
+    /// EGLD payments are wrapped, so the swaps always start from an ESDT.
+    fn get_swap_payment(&self) -> EsdtTokenPayment {
+        let (payment_token, nonce, amount) = self.call_value().egld_or_single_esdt().into_tuple();
+        require!(nonce == 0, "Invalid nonce. Should be zero");
+        require!(amount > 0u64, "Invalid amount. Should not be zero");
 
-        payments
+        let token_id = if payment_token.is_egld() {
+            self.wrap_egld_payment(amount.clone()).token_identifier
+        } else {
+            payment_token.unwrap_esdt()
+        };
+
+        EsdtTokenPayment::new(token_id, nonce, amount)
+    }
+
+    /// Residuals of fixed output swaps are pushed to `residuals`.
+    /// The pair applies the fee discount of `original_caller`.
+    #[allow(clippy::too_many_arguments)]
+    fn perform_swap_operation(
+        &self,
+        payment: EsdtTokenPayment,
+        pair_address: ManagedAddress,
+        function: ManagedBuffer,
+        token_wanted: TokenIdentifier,
+        amount_wanted: BigUint,
+        original_caller: &ManagedAddress,
+        residuals: &mut ManagedVec<EsdtTokenPayment>,
+    ) -> EsdtTokenPayment {
+        self.check_is_pair_sc(&pair_address);
+
+        if function == *SWAP_TOKENS_FIXED_INPUT_FUNC_NAME {
+            self.actual_swap_fixed_input(
+                pair_address,
+                payment.token_identifier,
+                payment.amount,
+                token_wanted,
+                amount_wanted,
+                original_caller.clone(),
+            )
+        } else if function == *SWAP_TOKENS_FIXED_OUTPUT_FUNC_NAME {
+            let (payment, residuum) = self.actual_swap_fixed_output(
+                pair_address,
+                payment.token_identifier,
+                payment.amount,
+                token_wanted,
+                amount_wanted,
+                original_caller.clone(),
+            );
+
+            if residuum.amount > 0 {
+                residuals.push(residuum);
+            }
+
+            payment
+        } else {
+            sc_panic!("Invalid function to call");
+        }
     }
 
     fn actual_swap_fixed_input(
@@ -88,9 +271,11 @@ pub trait MultiPairSwap:
         amount_in: BigUint,
         token_out: TokenIdentifier,
         amount_out_min: BigUint,
+        original_caller: ManagedAddress,
     ) -> EsdtTokenPayment<Self::Api> {
+        let opt_original_caller = self.get_original_caller_arg(&pair_address, original_caller);
         self.pair_contract_proxy(pair_address)
-            .swap_tokens_fixed_input(token_out, amount_out_min)
+            .swap_tokens_fixed_input(token_out, amount_out_min, opt_original_caller)
             .with_esdt_transfer((token_in, 0, amount_in))
             .execute_on_dest_context()
     }
@@ -102,16 +287,32 @@ pub trait MultiPairSwap:
         amount_in_max: BigUint,
         token_out: TokenIdentifier,
         amount_out: BigUint,
+        original_caller: ManagedAddress,
     ) -> (EsdtTokenPayment<Self::Api>, EsdtTokenPayment<Self::Api>) {
+        let opt_original_caller = self.get_original_caller_arg(&pair_address, original_caller);
         let call_result: MultiValue2<EsdtTokenPayment<Self::Api>, EsdtTokenPayment<Self::Api>> =
             self.pair_contract_proxy(pair_address)
-                .swap_tokens_fixed_output(token_out, amount_out)
+                .swap_tokens_fixed_output(token_out, amount_out, opt_original_caller)
                 .with_esdt_transfer((token_in, 0, amount_in_max))
                 .execute_on_dest_context();
 
         call_result.into_tuple()
     }
 
+    /// Pairs not marked through setPairsAcceptOriginalCaller may reject the extra argument,
+    /// so they are called without it and apply no fee discount.
+    fn get_original_caller_arg(
+        &self,
+        pair_address: &ManagedAddress,
+        original_caller: ManagedAddress,
+    ) -> OptionalValue<ManagedAddress> {
+        if self.pair_accepts_original_caller(pair_address).get() {
+            OptionalValue::Some(original_caller)
+        } else {
+            OptionalValue::None
+        }
+    }
+
     #[proxy]
     fn pair_contract_proxy(&self, to: ManagedAddress) -> pair::Proxy<Self::Api>;
 }
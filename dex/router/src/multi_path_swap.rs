@@ -0,0 +1,126 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use super::factory;
+use crate::{
+    config, events,
+    multi_pair_swap::{self, SwapOperation},
+    referral,
+};
+use pair::{egld_wrapper, read_pair_storage};
+
+#[derive(ManagedVecItem, TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone)]
+pub struct SwapRoute<M: ManagedTypeApi> {
+    pub weight: u64,
+    pub swap_operations: ManagedVec<M, SwapOperation<M>>,
+}
+
+#[multiversx_sc::module]
+pub trait MultiPathSwapModule:
+    config::ConfigModule
+    + read_pair_storage::ReadPairStorageModule
+    + factory::FactoryModule
+    + multiversx_sc_modules::ongoing_operation::OngoingOperationModule
+    + token_send::TokenSendModule
+    + events::EventsModule
+    + egld_wrapper::EgldWrapperModule
+    + referral::ReferralModule
+    + multi_pair_swap::MultiPairSwap
+{
+    /// Splits the payment between the given routes, proportionally to their weights, and
+    /// swaps each part through its chain of swap operations. The last route receives the
+    /// rounding leftover.
+    ///
+    /// All the routes have to end in the same token. The summed output has to be at least
+    /// `min_amount_out`. Returns the residuals of the fixed output swaps, followed by the output.
+    ///
+    /// If a `referrer` is given, the referral fee is taken from the input
+    /// before splitting it. EGLD payments are wrapped before the first swap.
+    #[payable("*")]
+    #[endpoint(multiPathSwap)]
+    fn multi_path_swap(
+        &self,
+        min_amount_out: BigUint,
+        routes: ManagedVec<SwapRoute<Self::Api>>,
+        opt_referrer: OptionalValue<ManagedAddress>,
+    ) -> ManagedVec<EsdtTokenPayment> {
+        require!(self.is_active(), "Not active");
+
+        let (token_id, nonce, amount) = self.get_swap_payment().into_tuple();
+        require!(!routes.is_empty(), "Invalid routes. Should not be empty");
+
+        let mut total_weight = 0u64;
+        for route in routes.iter() {
+            require!(route.weight > 0, "Invalid route weight. Should not be zero");
+            require!(
+                !route.swap_operations.is_empty(),
+                "Invalid swap operations chain. Should not be empty"
+            );
+
+            total_weight += route.weight;
+        }
+
+        let mut payment_after_fee = EsdtTokenPayment::new(token_id.clone(), nonce, amount.clone());
+        self.take_referral_fee(opt_referrer, &mut payment_after_fee);
+
+        let caller = self.blockchain().get_caller();
+        let mut payments = ManagedVec::new();
+        let mut route_amounts_in = ManagedVec::new();
+        let mut opt_token_out: Option<TokenIdentifier> = None;
+        let mut amount_out = BigUint::zero();
+        let mut amount_left = payment_after_fee.amount.clone();
+        let last_route_index = routes.len() - 1;
+        for (route_index, route) in routes.iter().enumerate() {
+            let route_amount_in = if route_index == last_route_index {
+                amount_left.clone()
+            } else {
+                &payment_after_fee.amount * route.weight / total_weight
+            };
+            require!(route_amount_in > 0u64, "Route amount should not be zero");
+            amount_left -= &route_amount_in;
+
+            let mut last_payment =
+                EsdtTokenPayment::new(token_id.clone(), nonce, route_amount_in.clone());
+            for swap_operation in route.swap_operations.iter() {
+                last_payment = self.perform_swap_operation(
+                    last_payment,
+                    swap_operation.pair_address,
+                    swap_operation.function,
+                    swap_operation.token_wanted,
+                    swap_operation.amount_wanted,
+                    &caller,
+                    &mut payments,
+                );
+            }
+
+            match &opt_token_out {
+                Some(token_out) => require!(
+                    &last_payment.token_identifier == token_out,
+                    "All routes should end in the same token"
+                ),
+                None => opt_token_out = Some(last_payment.token_identifier.clone()),
+            }
+
+            amount_out += last_payment.amount;
+            route_amounts_in.push(route_amount_in);
+        }
+
+        require!(amount_out >= min_amount_out, "Slippage exceeded");
+
+        let token_out =
+            opt_token_out.unwrap_or_else(|| sc_panic!("Invalid routes. Should not be empty"));
+        payments.push(EsdtTokenPayment::new(token_out, 0, amount_out));
+        self.send().direct_multi(&caller, &payments);
+
+        self.emit_multi_path_swap_event(
+            caller,
+            token_id,
+            amount,
+            min_amount_out,
+            route_amounts_in,
+            payments.clone(),
+        );
+
+        payments
+    }
+}
@@ -0,0 +1,95 @@
+multiversx_sc::imports!();
+
+use crate::events;
+
+pub const MAX_REFERRAL_FEE_PERCENT: u64 = 100;
+pub const REFERRAL_FEE_MAX_PERCENTAGE: u64 = 10_000;
+
+#[multiversx_sc::module]
+pub trait ReferralModule: events::EventsModule {
+    /// The referral fee is expressed in basis points of the swapped input, capped at 1%.
+    #[only_owner]
+    #[endpoint(setReferralFeePercent)]
+    fn set_referral_fee_percent(&self, fee_percent: u64) {
+        require!(
+            fee_percent <= MAX_REFERRAL_FEE_PERCENT,
+            "Referral fee percent too high"
+        );
+
+        self.referral_fee_percent().set(fee_percent);
+    }
+
+    /// Sends all the fees accumulated by the caller as a referrer.
+    #[endpoint(claimReferralFees)]
+    fn claim_referral_fees(&self) -> ManagedVec<EsdtTokenPayment> {
+        let caller = self.blockchain().get_caller();
+        let mut fee_tokens = self.referrer_fee_tokens(&caller);
+        require!(!fee_tokens.is_empty(), "No referral fees to claim");
+
+        let mut payments = ManagedVec::new();
+        for token_id in fee_tokens.iter() {
+            let amount = self.referrer_fee_amount(&caller, &token_id).take();
+            payments.push(EsdtTokenPayment::new(token_id, 0, amount));
+        }
+        fee_tokens.clear();
+
+        self.send().direct_multi(&caller, &payments);
+        self.emit_claim_referral_fees_event(caller, payments.clone());
+
+        payments
+    }
+
+    /// Credits the referral fee to the referrer and deducts it from the payment.
+    /// Nothing is taken if no referrer is given.
+    fn take_referral_fee(
+        &self,
+        opt_referrer: OptionalValue<ManagedAddress>,
+        payment: &mut EsdtTokenPayment,
+    ) {
+        let referrer = match opt_referrer {
+            OptionalValue::Some(referrer) => referrer,
+            OptionalValue::None => return,
+        };
+        require!(!referrer.is_zero(), "Invalid referrer");
+
+        let fee_amount =
+            &payment.amount * self.referral_fee_percent().get() / REFERRAL_FEE_MAX_PERCENTAGE;
+        if fee_amount == 0 {
+            return;
+        }
+
+        payment.amount -= &fee_amount;
+        self.referrer_fee_tokens(&referrer)
+            .insert(payment.token_identifier.clone());
+        self.referrer_fee_amount(&referrer, &payment.token_identifier)
+            .update(|amount| *amount += &fee_amount);
+
+        self.emit_referral_fee_event(referrer, payment.token_identifier.clone(), fee_amount);
+    }
+
+    #[view(getReferrerFees)]
+    fn get_referrer_fees(&self, referrer: ManagedAddress) -> MultiValueEncoded<EsdtTokenPayment> {
+        let mut result = MultiValueEncoded::new();
+        for token_id in self.referrer_fee_tokens(&referrer).iter() {
+            let amount = self.referrer_fee_amount(&referrer, &token_id).get();
+            result.push(EsdtTokenPayment::new(token_id, 0, amount));
+        }
+
+        result
+    }
+
+    #[view(getReferralFeePercent)]
+    #[storage_mapper("referralFeePercent")]
+    fn referral_fee_percent(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("referrerFeeTokens")]
+    fn referrer_fee_tokens(&self, referrer: &ManagedAddress)
+        -> UnorderedSetMapper<TokenIdentifier>;
+
+    #[storage_mapper("referrerFeeAmount")]
+    fn referrer_fee_amount(
+        &self,
+        referrer: &ManagedAddress,
+        token_id: &TokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
+}
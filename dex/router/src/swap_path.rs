@@ -0,0 +1,268 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use super::factory;
+use crate::{
+    config, events,
+    multi_pair_swap::{self, SwapOperationType, SWAP_TOKENS_FIXED_INPUT_FUNC_NAME},
+    referral,
+};
+use pair::{egld_wrapper, pair_actions::views::ProxyTrait as _, read_pair_storage};
+use pausable::State;
+
+pub const MAX_SWAP_PATH_HOPS: usize = 4;
+
+const ROOT_NODE_INDEX: usize = 0;
+
+#[derive(ManagedVecItem)]
+pub struct SwapPathPair<M: ManagedTypeApi> {
+    pub first_token_id: TokenIdentifier<M>,
+    pub second_token_id: TokenIdentifier<M>,
+    pub address: ManagedAddress<M>,
+}
+
+#[derive(ManagedVecItem)]
+pub struct SwapPathNode<M: ManagedTypeApi> {
+    pub token_id: TokenIdentifier<M>,
+    pub amount: BigUint<M>,
+    pub pair_address: ManagedAddress<M>,
+    pub prev_index: usize,
+}
+
+#[multiversx_sc::module]
+pub trait SwapPathModule:
+    config::ConfigModule
+    + read_pair_storage::ReadPairStorageModule
+    + factory::FactoryModule
+    + multiversx_sc_modules::ongoing_operation::OngoingOperationModule
+    + token_send::TokenSendModule
+    + events::EventsModule
+    + egld_wrapper::EgldWrapperModule
+    + referral::ReferralModule
+    + multi_pair_swap::MultiPairSwap
+{
+    /// Searches the active pairs for the path of at most `max_hops` swaps that yields
+    /// the most `token_out` for `amount_in` of `token_in`.
+    ///
+    /// Returns the expected output and the swap operations, which can be passed to multiPairSwap.
+    /// The amount of each operation is the expected output of that hop, so callers should
+    /// lower them according to their slippage tolerance.
+    #[view(getBestSwapPath)]
+    fn get_best_swap_path(
+        &self,
+        token_in: TokenIdentifier,
+        token_out: TokenIdentifier,
+        amount_in: BigUint,
+        max_hops: usize,
+    ) -> MultiValue2<BigUint, MultiValueEncoded<SwapOperationType<Self::Api>>> {
+        require!(token_in != token_out, "Tokens should be different");
+        require!(amount_in > 0u64, "Invalid amount. Should not be zero");
+        require!(
+            max_hops > 0 && max_hops <= MAX_SWAP_PATH_HOPS,
+            "Invalid number of hops"
+        );
+
+        let pairs = self.get_swap_path_pairs();
+        let mut nodes = ManagedVec::<Self::Api, SwapPathNode<Self::Api>>::new();
+        nodes.push(SwapPathNode {
+            token_id: token_in.clone(),
+            amount: amount_in,
+            pair_address: ManagedAddress::zero(),
+            prev_index: ROOT_NODE_INDEX,
+        });
+
+        let mut best_index_opt = None;
+        let mut level_start = ROOT_NODE_INDEX;
+        for hop in 1..=max_hops {
+            let level_end = nodes.len();
+            for node_index in level_start..level_end {
+                let node = nodes.get(node_index);
+                if node.token_id == token_out {
+                    continue;
+                }
+
+                for pair in pairs.iter() {
+                    let next_token_id = if pair.first_token_id == node.token_id {
+                        pair.second_token_id.clone()
+                    } else if pair.second_token_id == node.token_id {
+                        pair.first_token_id.clone()
+                    } else {
+                        continue;
+                    };
+                    let is_last_hop = hop == max_hops;
+                    if next_token_id == token_in
+                        || (is_last_hop && next_token_id != token_out)
+                        || self.is_pair_in_path(&nodes, node_index, &pair.address)
+                    {
+                        continue;
+                    }
+
+                    let amount_out: BigUint = self
+                        .pair_contract_proxy(pair.address.clone())
+                        .get_amount_out_view(node.token_id.clone(), node.amount.clone())
+                        .execute_on_dest_context();
+                    if amount_out == 0 {
+                        continue;
+                    }
+
+                    let new_node = SwapPathNode {
+                        token_id: next_token_id,
+                        amount: amount_out,
+                        pair_address: pair.address.clone(),
+                        prev_index: node_index,
+                    };
+                    if new_node.token_id == token_out {
+                        let is_better = match best_index_opt {
+                            Some(best_index) => new_node.amount > nodes.get(best_index).amount,
+                            None => true,
+                        };
+                        if is_better {
+                            best_index_opt = Some(nodes.len());
+                            nodes.push(new_node);
+                        }
+
+                        continue;
+                    }
+
+                    self.insert_or_replace_node(&mut nodes, level_end, new_node);
+                }
+            }
+
+            level_start = level_end;
+        }
+
+        let best_index = match best_index_opt {
+            Some(best_index) => best_index,
+            None => sc_panic!("No swap path found"),
+        };
+
+        self.build_swap_operations(&nodes, best_index)
+    }
+
+    fn get_swap_path_pairs(&self) -> ManagedVec<SwapPathPair<Self::Api>> {
+        let mut pairs = ManagedVec::new();
+        for (pair_tokens, address) in self.pair_map().iter() {
+            self.push_swap_path_pair(
+                &mut pairs,
+                pair_tokens.first_token_id,
+                pair_tokens.second_token_id,
+                address,
+            );
+        }
+        for (pair_tokens, address) in self.fee_tier_pair_map().iter() {
+            self.push_swap_path_pair(
+                &mut pairs,
+                pair_tokens.first_token_id,
+                pair_tokens.second_token_id,
+                address,
+            );
+        }
+
+        pairs
+    }
+
+    /// Only active pairs with liquidity can be part of a swap path.
+    fn push_swap_path_pair(
+        &self,
+        pairs: &mut ManagedVec<SwapPathPair<Self::Api>>,
+        first_token_id: TokenIdentifier,
+        second_token_id: TokenIdentifier,
+        address: ManagedAddress,
+    ) {
+        let state = self.get_pair_state_mapper(address.clone()).get();
+        if state != State::Active {
+            return;
+        }
+
+        let first_reserve = self
+            .get_pair_reserve_mapper(address.clone(), &first_token_id)
+            .get();
+        let second_reserve = self
+            .get_pair_reserve_mapper(address.clone(), &second_token_id)
+            .get();
+        if first_reserve == 0 || second_reserve == 0 {
+            return;
+        }
+
+        pairs.push(SwapPathPair {
+            first_token_id,
+            second_token_id,
+            address,
+        });
+    }
+
+    /// The output of a pair changes after swapping through it, so each pair is used at most once.
+    fn is_pair_in_path(
+        &self,
+        nodes: &ManagedVec<SwapPathNode<Self::Api>>,
+        node_index: usize,
+        pair_address: &ManagedAddress,
+    ) -> bool {
+        let mut current_index = node_index;
+        while current_index != ROOT_NODE_INDEX {
+            let node = nodes.get(current_index);
+            if &node.pair_address == pair_address {
+                return true;
+            }
+
+            current_index = node.prev_index;
+        }
+
+        false
+    }
+
+    /// Keeps only the best path to each token within the level starting at `level_start`.
+    fn insert_or_replace_node(
+        &self,
+        nodes: &mut ManagedVec<SwapPathNode<Self::Api>>,
+        level_start: usize,
+        new_node: SwapPathNode<Self::Api>,
+    ) {
+        for index in level_start..nodes.len() {
+            let node = nodes.get(index);
+            if node.token_id != new_node.token_id {
+                continue;
+            }
+
+            if new_node.amount > node.amount {
+                let _ = nodes.set(index, &new_node);
+            }
+
+            return;
+        }
+
+        nodes.push(new_node);
+    }
+
+    fn build_swap_operations(
+        &self,
+        nodes: &ManagedVec<SwapPathNode<Self::Api>>,
+        last_index: usize,
+    ) -> MultiValue2<BigUint, MultiValueEncoded<SwapOperationType<Self::Api>>> {
+        let mut reversed_path = ManagedVec::<Self::Api, SwapPathNode<Self::Api>>::new();
+        let mut current_index = last_index;
+        while current_index != ROOT_NODE_INDEX {
+            let node = nodes.get(current_index);
+            current_index = node.prev_index;
+            reversed_path.push(node);
+        }
+
+        let amount_out = nodes.get(last_index).amount;
+        let function = ManagedBuffer::from(SWAP_TOKENS_FIXED_INPUT_FUNC_NAME);
+        let mut swap_operations = MultiValueEncoded::new();
+        for index in (0..reversed_path.len()).rev() {
+            let node = reversed_path.get(index);
+            swap_operations.push(
+                (
+                    node.pair_address,
+                    function.clone(),
+                    node.token_id,
+                    node.amount,
+                )
+                    .into(),
+            );
+        }
+
+        (amount_out, swap_operations).into()
+    }
+}
@@ -1,6 +1,6 @@
 use multiversx_sc::codec::multi_types::{MultiValue4, OptionalValue};
-use multiversx_sc::types::{Address, EsdtLocalRole, ManagedAddress, MultiValueEncoded};
-use multiversx_sc_scenario::whitebox_legacy::TxTokenTransfer;
+use multiversx_sc::types::{Address, EsdtLocalRole, ManagedAddress, ManagedVec, MultiValueEncoded};
+use multiversx_sc_scenario::whitebox_legacy::{TxResult, TxTokenTransfer};
 use multiversx_sc_scenario::{
     managed_address, managed_biguint, managed_buffer, managed_token_id, rust_biguint,
     whitebox_legacy::*, DebugApi,
@@ -8,6 +8,7 @@ use multiversx_sc_scenario::{
 
 pub const PAIR_WASM_PATH: &str = "pair/output/pair.wasm";
 pub const ROUTER_WASM_PATH: &str = "router/output/router.wasm";
+pub const EGLD_WRAPPER_WASM_PATH: &str = "egld-wrapper-mock/output/egld-wrapper-mock.wasm";
 pub const MEX_TOKEN_ID: &[u8] = b"MEX-abcdef";
 pub const WEGLD_TOKEN_ID: &[u8] = b"WEGLD-abcdef";
 pub const USDC_TOKEN_ID: &[u8] = b"USDC-abcdef";
@@ -34,8 +35,11 @@ use pausable::{PausableModule, State};
 use router::config::ConfigModule;
 use router::factory::*;
 use router::multi_pair_swap::*;
+use router::multi_path_swap::*;
 use router::*;
 
+pub type SwapOperationArgs<'a> = (Address, &'a [u8], &'a [u8], u64);
+
 #[allow(dead_code)]
 pub struct RouterSetup<RouterObjBuilder, PairObjBuilder>
 where
@@ -85,7 +89,7 @@ where
             .execute_tx(&owner_addr, &mex_pair_wrapper, &rust_zero, |sc| {
                 let first_token_id = managed_token_id!(WEGLD_TOKEN_ID);
                 let second_token_id = managed_token_id!(MEX_TOKEN_ID);
-                let router_address = managed_address!(&owner_addr);
+                let router_address = managed_address!(router_wrapper.address_ref());
                 let router_owner_address = managed_address!(&owner_addr);
                 let total_fee_percent = 300u64;
                 let special_fee_percent = 50u64;
@@ -112,7 +116,7 @@ where
             .execute_tx(&owner_addr, &usdc_pair_wrapper, &rust_zero, |sc| {
                 let first_token_id = managed_token_id!(WEGLD_TOKEN_ID);
                 let second_token_id = managed_token_id!(USDC_TOKEN_ID);
-                let router_address = managed_address!(&owner_addr);
+                let router_address = managed_address!(router_wrapper.address_ref());
                 let router_owner_address = managed_address!(&owner_addr);
                 let total_fee_percent = 300u64;
                 let special_fee_percent = 50u64;
@@ -284,4 +288,93 @@ where
             )
             .assert_ok();
     }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn multi_pair_swap_with_limits(
+        &mut self,
+        payment_token: &[u8],
+        payment_amount: u64,
+        min_amount_out: u64,
+        deadline: u64,
+        destination: &Address,
+        opt_referrer: Option<&Address>,
+        args: &[SwapOperationArgs],
+    ) -> TxResult {
+        let payment_amount_big = rust_biguint!(payment_amount);
+
+        self.blockchain_wrapper.execute_esdt_transfer(
+            &self.user_address,
+            &self.router_wrapper,
+            payment_token,
+            0,
+            &payment_amount_big,
+            |sc| {
+                let mut swap_operations = ManagedVec::new();
+                for x in args.iter() {
+                    swap_operations.push(SwapOperation {
+                        pair_address: managed_address!(&x.0),
+                        function: managed_buffer!(x.1),
+                        token_wanted: managed_token_id!(x.2.to_owned()),
+                        amount_wanted: managed_biguint!(x.3),
+                    });
+                }
+
+                sc.multi_pair_swap_with_limits(
+                    managed_biguint!(min_amount_out),
+                    deadline,
+                    managed_address!(destination),
+                    swap_operations,
+                    opt_referrer
+                        .map(|referrer| managed_address!(referrer))
+                        .into(),
+                );
+            },
+        )
+    }
+
+    pub fn multi_path_swap(
+        &mut self,
+        payment_token: &[u8],
+        payment_amount: u64,
+        min_amount_out: u64,
+        opt_referrer: Option<&Address>,
+        routes: &[(u64, Vec<SwapOperationArgs>)],
+    ) -> TxResult {
+        let payment_amount_big = rust_biguint!(payment_amount);
+
+        self.blockchain_wrapper.execute_esdt_transfer(
+            &self.user_address,
+            &self.router_wrapper,
+            payment_token,
+            0,
+            &payment_amount_big,
+            |sc| {
+                let mut swap_routes = ManagedVec::new();
+                for (weight, ops) in routes.iter() {
+                    let mut swap_operations = ManagedVec::new();
+                    for x in ops.iter() {
+                        swap_operations.push(SwapOperation {
+                            pair_address: managed_address!(&x.0),
+                            function: managed_buffer!(x.1),
+                            token_wanted: managed_token_id!(x.2.to_owned()),
+                            amount_wanted: managed_biguint!(x.3),
+                        });
+                    }
+
+                    swap_routes.push(SwapRoute {
+                        weight: *weight,
+                        swap_operations,
+                    });
+                }
+
+                sc.multi_path_swap(
+                    managed_biguint!(min_amount_out),
+                    swap_routes,
+                    opt_referrer
+                        .map(|referrer| managed_address!(referrer))
+                        .into(),
+                );
+            },
+        )
+    }
 }
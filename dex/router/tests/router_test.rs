@@ -1,12 +1,13 @@
 #![allow(deprecated)]
 
 mod router_setup;
+use egld_wrapper_mock::EgldWrapperMock;
 use multiversx_sc::{
     codec::multi_types::OptionalValue,
     storage::mappers::StorageTokenWrapper,
     types::{
         Address, EgldOrEsdtTokenIdentifier, EsdtLocalRole, ManagedAddress, ManagedVec,
-        MultiValueEncoded,
+        MultiValueEncoded, OperationCompletionStatus,
     },
 };
 use pair::{
@@ -15,14 +16,23 @@ use pair::{
 };
 use pausable::{PausableModule, State};
 use router::{
-    config::ConfigModule, enable_swap_by_user::EnableSwapByUserModule, factory::PairTokens,
-    multi_pair_swap::SWAP_TOKENS_FIXED_INPUT_FUNC_NAME, Router,
+    config::ConfigModule,
+    enable_swap_by_user::EnableSwapByUserModule,
+    factory::{FactoryModule, PairTokens, UpgradeAllPairsStep},
+    multi_pair_swap::{MultiPairSwap, SwapOperation, SWAP_TOKENS_FIXED_INPUT_FUNC_NAME},
+    multi_path_swap::{MultiPathSwapModule, SwapRoute},
+    referral::ReferralModule,
+    swap_path::SwapPathModule,
+    Router,
 };
 use router_setup::*;
 
+use multiversx_sc_modules::ongoing_operation::OngoingOperationModule;
+
 use multiversx_sc_scenario::{
-    managed_address, managed_biguint, managed_token_id, managed_token_id_wrapped, rust_biguint,
-    whitebox_legacy::BlockchainStateWrapper, whitebox_legacy::TxTokenTransfer, DebugApi,
+    managed_address, managed_biguint, managed_buffer, managed_token_id, managed_token_id_wrapped,
+    rust_biguint, whitebox_legacy::BlockchainStateWrapper, whitebox_legacy::TxTokenTransfer,
+    DebugApi,
 };
 use simple_lock::{
     locked_token::{LockedTokenAttributes, LockedTokenModule},
@@ -109,6 +119,19 @@ fn test_router_upgrade_pair() {
         })
         .assert_ok();
 
+    // the original caller is passed only once the owner marks the pair as accepting it
+    b_mock
+        .execute_tx(&owner, &router_wrapper, &rust_zero, |sc| {
+            let pair_address = managed_address!(pair_wrapper.address_ref());
+            assert!(!sc.pair_accepts_original_caller(&pair_address).get());
+
+            let mut pair_addresses = MultiValueEncoded::new();
+            pair_addresses.push(pair_address.clone());
+            sc.set_pairs_accept_original_caller(true, pair_addresses);
+            assert!(sc.pair_accepts_original_caller(&pair_address).get());
+        })
+        .assert_ok();
+
     b_mock
         .execute_tx(&owner, &router_wrapper, &rust_zero, |sc| {
             let first_token_id = managed_token_id!(CUSTOM_TOKEN_ID);
@@ -125,6 +148,289 @@ fn test_router_upgrade_pair() {
         .assert_ok();
 }
 
+#[test]
+fn test_router_upgrade_all_pairs() {
+    let rust_zero = rust_biguint!(0u64);
+    let mut b_mock = BlockchainStateWrapper::new();
+    let owner = b_mock.create_user_account(&rust_zero);
+    let user = b_mock.create_user_account(&rust_zero);
+
+    let router_wrapper = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        router::contract_obj,
+        ROUTER_WASM_PATH,
+    );
+
+    let pair_template_wrapper = b_mock.create_sc_account(
+        &rust_zero,
+        Some(router_wrapper.address_ref()),
+        pair::contract_obj,
+        PAIR_WASM_PATH,
+    );
+
+    b_mock
+        .execute_tx(&owner, &pair_template_wrapper, &rust_zero, |sc| {
+            sc.init(
+                managed_token_id!(CUSTOM_TOKEN_ID),
+                managed_token_id!(USDC_TOKEN_ID),
+                managed_address!(&Address::zero()),
+                managed_address!(&owner),
+                0,
+                0,
+                managed_address!(&user),
+                MultiValueEncoded::<DebugApi, ManagedAddress<DebugApi>>::new(),
+            );
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_tx(&owner, &router_wrapper, &rust_zero, |sc| {
+            sc.init(OptionalValue::Some(managed_address!(
+                pair_template_wrapper.address_ref()
+            )));
+            sc.set_pair_creation_enabled(true);
+        })
+        .assert_ok();
+
+    // nothing to upgrade yet
+    b_mock
+        .execute_tx(&owner, &router_wrapper, &rust_zero, |sc| {
+            let result = sc.upgrade_all_pairs();
+            assert_eq!(result, OperationCompletionStatus::Completed);
+        })
+        .assert_ok();
+
+    let mut pair_wrappers = Vec::new();
+    for second_token_id in [USDC_TOKEN_ID, MEX_TOKEN_ID] {
+        let pair_wrapper =
+            b_mock.prepare_deploy_from_sc(router_wrapper.address_ref(), pair::contract_obj);
+        b_mock
+            .execute_tx(&user, &router_wrapper, &rust_zero, |sc| {
+                let _new_pair_address = sc.create_pair_endpoint(
+                    managed_token_id!(CUSTOM_TOKEN_ID),
+                    managed_token_id!(second_token_id),
+                    managed_address!(&user),
+                    OptionalValue::None,
+                    MultiValueEncoded::<DebugApi, ManagedAddress<DebugApi>>::new(),
+                );
+            })
+            .assert_ok();
+
+        pair_wrappers.push(pair_wrapper);
+    }
+
+    b_mock
+        .execute_tx(&owner, &router_wrapper, &rust_zero, |sc| {
+            let result = sc.upgrade_all_pairs();
+            assert_eq!(result, OperationCompletionStatus::Completed);
+            assert!(sc.pairs_to_upgrade().is_empty());
+        })
+        .assert_ok();
+
+    for pair_wrapper in pair_wrappers.iter() {
+        b_mock
+            .execute_query(pair_wrapper, |sc| {
+                let inital_liquidity_adder = sc.initial_liquidity_adder().get().unwrap();
+                assert_eq!(inital_liquidity_adder, managed_address!(&user))
+            })
+            .assert_ok();
+    }
+
+    // a pair removed while the snapshot is interrupted right before it is skipped
+    b_mock
+        .execute_tx(&owner, &router_wrapper, &rust_zero, |sc| {
+            sc.save_progress(&UpgradeAllPairsStep::<DebugApi>::SnapshotPairs(
+                PairTokens {
+                    first_token_id: managed_token_id!(CUSTOM_TOKEN_ID),
+                    second_token_id: managed_token_id!(USDC_TOKEN_ID),
+                },
+            ));
+            let _ = sc.remove_pair(
+                managed_token_id!(CUSTOM_TOKEN_ID),
+                managed_token_id!(USDC_TOKEN_ID),
+            );
+
+            let step: UpgradeAllPairsStep<DebugApi> = sc.load_operation();
+            assert!(
+                step == UpgradeAllPairsStep::SnapshotPairs(PairTokens {
+                    first_token_id: managed_token_id!(CUSTOM_TOKEN_ID),
+                    second_token_id: managed_token_id!(MEX_TOKEN_ID),
+                })
+            );
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_tx(&owner, &router_wrapper, &rust_zero, |sc| {
+            let result = sc.upgrade_all_pairs();
+            assert_eq!(result, OperationCompletionStatus::Completed);
+            assert!(sc.pairs_to_upgrade().is_empty());
+            assert!(
+                sc.load_operation::<UpgradeAllPairsStep<DebugApi>>() == UpgradeAllPairsStep::Start
+            );
+        })
+        .assert_ok();
+}
+
+#[test]
+fn test_router_fee_tier_pairs() {
+    let rust_zero = rust_biguint!(0u64);
+    let mut b_mock = BlockchainStateWrapper::new();
+    let owner = b_mock.create_user_account(&rust_zero);
+    let user = b_mock.create_user_account(&rust_zero);
+
+    let router_wrapper = b_mock.create_sc_account(
+        &rust_zero,
+        Some(&owner),
+        router::contract_obj,
+        ROUTER_WASM_PATH,
+    );
+
+    let pair_template_wrapper = b_mock.create_sc_account(
+        &rust_zero,
+        Some(router_wrapper.address_ref()),
+        pair::contract_obj,
+        PAIR_WASM_PATH,
+    );
+
+    b_mock
+        .execute_tx(&owner, &router_wrapper, &rust_zero, |sc| {
+            sc.init(OptionalValue::Some(managed_address!(
+                pair_template_wrapper.address_ref()
+            )));
+            sc.set_pair_creation_enabled(true);
+            sc.add_fee_tier(5, 0);
+            sc.add_fee_tier(1_000, 50);
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_tx(&owner, &router_wrapper, &rust_zero, |sc| {
+            sc.add_fee_tier(100, 200);
+        })
+        .assert_user_error("Bad percents");
+
+    b_mock
+        .execute_tx(&user, &router_wrapper, &rust_zero, |sc| {
+            let _ = sc.create_pair_with_fee_tier_endpoint(
+                managed_token_id!(CUSTOM_TOKEN_ID),
+                managed_token_id!(USDC_TOKEN_ID),
+                managed_address!(&user),
+                300,
+                MultiValueEncoded::new(),
+            );
+        })
+        .assert_user_error("Invalid fee tier");
+
+    // one pair for each fee tier, for the same tokens
+    let mut pair_wrappers = Vec::new();
+    for total_fee_percent in [5, 1_000] {
+        let pair_wrapper =
+            b_mock.prepare_deploy_from_sc(router_wrapper.address_ref(), pair::contract_obj);
+        b_mock
+            .execute_tx(&user, &router_wrapper, &rust_zero, |sc| {
+                let new_pair_address = sc.create_pair_with_fee_tier_endpoint(
+                    managed_token_id!(CUSTOM_TOKEN_ID),
+                    managed_token_id!(USDC_TOKEN_ID),
+                    managed_address!(&user),
+                    total_fee_percent,
+                    MultiValueEncoded::new(),
+                );
+                assert_eq!(
+                    new_pair_address,
+                    managed_address!(pair_wrapper.address_ref())
+                );
+            })
+            .assert_ok();
+
+        pair_wrappers.push(pair_wrapper);
+    }
+
+    b_mock
+        .execute_tx(&user, &router_wrapper, &rust_zero, |sc| {
+            let _ = sc.create_pair_with_fee_tier_endpoint(
+                managed_token_id!(USDC_TOKEN_ID),
+                managed_token_id!(CUSTOM_TOKEN_ID),
+                managed_address!(&user),
+                5,
+                MultiValueEncoded::new(),
+            );
+        })
+        .assert_user_error("Pair already exists");
+
+    b_mock
+        .execute_query(&pair_wrappers[0], |sc| {
+            assert_eq!(sc.total_fee_percent().get(), 5);
+            assert_eq!(sc.special_fee_percent().get(), 0);
+        })
+        .assert_ok();
+    b_mock
+        .execute_query(&pair_wrappers[1], |sc| {
+            assert_eq!(sc.total_fee_percent().get(), 1_000);
+            assert_eq!(sc.special_fee_percent().get(), 50);
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_query(&router_wrapper, |sc| {
+            let pair_address = sc.get_pair(
+                managed_token_id!(USDC_TOKEN_ID),
+                managed_token_id!(CUSTOM_TOKEN_ID),
+                OptionalValue::Some(1_000),
+            );
+            assert_eq!(
+                pair_address,
+                managed_address!(pair_wrappers[1].address_ref())
+            );
+
+            let default_pair_address = sc.get_pair(
+                managed_token_id!(CUSTOM_TOKEN_ID),
+                managed_token_id!(USDC_TOKEN_ID),
+                OptionalValue::None,
+            );
+            assert!(default_pair_address.is_zero());
+
+            assert_eq!(sc.get_all_pairs_addresses().len(), 2);
+            sc.check_is_pair_sc(&managed_address!(pair_wrappers[0].address_ref()));
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_tx(&owner, &router_wrapper, &rust_zero, |sc| {
+            sc.remove_fee_tier(5);
+
+            let removed_address = sc.remove_fee_tier_pair(
+                managed_token_id!(USDC_TOKEN_ID),
+                managed_token_id!(CUSTOM_TOKEN_ID),
+                5,
+            );
+            assert_eq!(
+                removed_address,
+                managed_address!(pair_wrappers[0].address_ref())
+            );
+        })
+        .assert_ok();
+
+    b_mock
+        .execute_query(&router_wrapper, |sc| {
+            sc.check_is_pair_sc(&managed_address!(pair_wrappers[0].address_ref()));
+        })
+        .assert_user_error("Not a pair SC");
+
+    b_mock
+        .execute_tx(&user, &router_wrapper, &rust_zero, |sc| {
+            let _ = sc.create_pair_with_fee_tier_endpoint(
+                managed_token_id!(CUSTOM_TOKEN_ID),
+                managed_token_id!(USDC_TOKEN_ID),
+                managed_address!(&user),
+                5,
+                MultiValueEncoded::new(),
+            );
+        })
+        .assert_user_error("Invalid fee tier");
+}
+
 #[test]
 fn test_multi_pair_swap() {
     let mut router_setup = RouterSetup::new(router::contract_obj, pair::contract_obj);
@@ -181,6 +487,608 @@ fn test_multi_pair_swap() {
     );
 }
 
+#[test]
+fn multi_pair_swap_egld_test() {
+    let mut router_setup = RouterSetup::new(router::contract_obj, pair::contract_obj);
+
+    router_setup.add_liquidity();
+
+    let rust_zero = rust_biguint!(0u64);
+    let owner = router_setup.owner_address.clone();
+    let user = router_setup.user_address.clone();
+
+    // the wrapped EGLD supply is already backed by EGLD
+    let egld_wrapper = router_setup.blockchain_wrapper.create_sc_account(
+        &rust_biguint!(USER_TOTAL_WEGLD_TOKENS),
+        Some(&owner),
+        egld_wrapper_mock::contract_obj,
+        EGLD_WRAPPER_WASM_PATH,
+    );
+    router_setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &egld_wrapper, &rust_zero, |sc| {
+            sc.init(managed_token_id!(WEGLD_TOKEN_ID));
+        })
+        .assert_ok();
+    router_setup.blockchain_wrapper.set_esdt_local_roles(
+        egld_wrapper.address_ref(),
+        WEGLD_TOKEN_ID,
+        &[EsdtLocalRole::Mint, EsdtLocalRole::Burn],
+    );
+
+    let usdc_pair_address = router_setup.usdc_pair_wrapper.address_ref().clone();
+    let mex_pair_address = router_setup.mex_pair_wrapper.address_ref().clone();
+
+    router_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &user,
+            &router_setup.router_wrapper,
+            &rust_biguint!(100_000),
+            |sc| {
+                let mut swap_operations = MultiValueEncoded::new();
+                swap_operations.push(
+                    (
+                        managed_address!(&usdc_pair_address),
+                        managed_buffer!(SWAP_TOKENS_FIXED_INPUT_FUNC_NAME),
+                        managed_token_id!(USDC_TOKEN_ID),
+                        managed_biguint!(1),
+                    )
+                        .into(),
+                );
+
+                let _ = sc.multi_pair_swap(swap_operations);
+            },
+        )
+        .assert_user_error("EGLD wrapper address not set");
+
+    router_setup
+        .blockchain_wrapper
+        .execute_tx(&owner, &router_setup.router_wrapper, &rust_zero, |sc| {
+            sc.set_egld_wrapper_address(managed_address!(egld_wrapper.address_ref()));
+        })
+        .assert_ok();
+
+    // EGLD is wrapped before the first swap
+    router_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &user,
+            &router_setup.router_wrapper,
+            &rust_biguint!(100_000),
+            |sc| {
+                let mut swap_operations = MultiValueEncoded::new();
+                swap_operations.push(
+                    (
+                        managed_address!(&usdc_pair_address),
+                        managed_buffer!(SWAP_TOKENS_FIXED_INPUT_FUNC_NAME),
+                        managed_token_id!(USDC_TOKEN_ID),
+                        managed_biguint!(1),
+                    )
+                        .into(),
+                );
+
+                let _ = sc.multi_pair_swap(swap_operations);
+            },
+        )
+        .assert_ok();
+
+    router_setup
+        .blockchain_wrapper
+        .check_egld_balance(&user, &rust_biguint!(100_000_000 - 100_000));
+    router_setup.blockchain_wrapper.check_esdt_balance(
+        &user,
+        USDC_TOKEN_ID,
+        &rust_biguint!(5_000_090_669),
+    );
+    router_setup.blockchain_wrapper.check_esdt_balance(
+        &user,
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(5_000_000_000),
+    );
+
+    // the wrapped EGLD output is unwrapped
+    router_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &user,
+            &router_setup.router_wrapper,
+            MEX_TOKEN_ID,
+            0,
+            &rust_biguint!(100_000),
+            |sc| {
+                let mut swap_operations = MultiValueEncoded::new();
+                swap_operations.push(
+                    (
+                        managed_address!(&mex_pair_address),
+                        managed_buffer!(SWAP_TOKENS_FIXED_INPUT_FUNC_NAME),
+                        managed_token_id!(WEGLD_TOKEN_ID),
+                        managed_biguint!(1),
+                    )
+                        .into(),
+                );
+
+                let payments = sc.multi_pair_swap_to_egld(swap_operations);
+                assert_eq!(payments.len(), 1);
+                assert_eq!(payments.get(0).amount, managed_biguint!(90_669));
+            },
+        )
+        .assert_ok();
+
+    router_setup
+        .blockchain_wrapper
+        .check_egld_balance(&user, &rust_biguint!(100_000_000 - 100_000 + 90_669));
+    router_setup.blockchain_wrapper.check_esdt_balance(
+        &user,
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(5_000_000_000),
+    );
+    router_setup.blockchain_wrapper.check_esdt_balance(
+        &user,
+        MEX_TOKEN_ID,
+        &rust_biguint!(4_999_900_000),
+    );
+
+    router_setup
+        .blockchain_wrapper
+        .execute_esdt_transfer(
+            &user,
+            &router_setup.router_wrapper,
+            MEX_TOKEN_ID,
+            0,
+            &rust_biguint!(100_000),
+            |sc| {
+                let mut swap_operations = MultiValueEncoded::new();
+                swap_operations.push(
+                    (
+                        managed_address!(&mex_pair_address),
+                        managed_buffer!(SWAP_TOKENS_FIXED_INPUT_FUNC_NAME),
+                        managed_token_id!(WEGLD_TOKEN_ID),
+                        managed_biguint!(1),
+                    )
+                        .into(),
+                );
+                swap_operations.push(
+                    (
+                        managed_address!(&usdc_pair_address),
+                        managed_buffer!(SWAP_TOKENS_FIXED_INPUT_FUNC_NAME),
+                        managed_token_id!(USDC_TOKEN_ID),
+                        managed_biguint!(1),
+                    )
+                        .into(),
+                );
+
+                let _ = sc.multi_pair_swap_to_egld(swap_operations);
+            },
+        )
+        .assert_user_error("Last swap should output the wrapped EGLD");
+
+    // multi path swaps also wrap the EGLD input
+    router_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &user,
+            &router_setup.router_wrapper,
+            &rust_biguint!(100_000),
+            |sc| {
+                let mut swap_operations = ManagedVec::new();
+                swap_operations.push(SwapOperation {
+                    pair_address: managed_address!(&usdc_pair_address),
+                    function: managed_buffer!(SWAP_TOKENS_FIXED_INPUT_FUNC_NAME),
+                    token_wanted: managed_token_id!(USDC_TOKEN_ID),
+                    amount_wanted: managed_biguint!(1),
+                });
+                let mut routes = ManagedVec::new();
+                routes.push(SwapRoute {
+                    weight: 1,
+                    swap_operations,
+                });
+
+                let payments = sc.multi_path_swap(managed_biguint!(1), routes, OptionalValue::None);
+                assert_eq!(payments.len(), 1);
+                assert_eq!(
+                    payments.get(0).token_identifier,
+                    managed_token_id!(USDC_TOKEN_ID)
+                );
+            },
+        )
+        .assert_ok();
+
+    router_setup
+        .blockchain_wrapper
+        .check_egld_balance(&user, &rust_biguint!(100_000_000 - 200_000 + 90_669));
+}
+
+#[test]
+fn multi_pair_swap_with_limits_test() {
+    let mut router_setup = RouterSetup::new(router::contract_obj, pair::contract_obj);
+
+    router_setup.add_liquidity();
+    router_setup.blockchain_wrapper.set_block_timestamp(100);
+
+    let ops = vec![
+        (
+            router_setup.mex_pair_wrapper.address_ref().clone(),
+            SWAP_TOKENS_FIXED_INPUT_FUNC_NAME,
+            WEGLD_TOKEN_ID,
+            1,
+        ),
+        (
+            router_setup.usdc_pair_wrapper.address_ref().clone(),
+            SWAP_TOKENS_FIXED_INPUT_FUNC_NAME,
+            USDC_TOKEN_ID,
+            1,
+        ),
+    ];
+    let destination = router_setup.owner_address.clone();
+
+    router_setup
+        .multi_pair_swap_with_limits(MEX_TOKEN_ID, 100_000, 1, 99, &destination, None, &ops)
+        .assert_user_error("Deadline passed");
+
+    router_setup
+        .multi_pair_swap_with_limits(MEX_TOKEN_ID, 100_000, 82_910, 100, &destination, None, &ops)
+        .assert_user_error("Slippage exceeded");
+
+    router_setup
+        .multi_pair_swap_with_limits(MEX_TOKEN_ID, 100_000, 82_909, 100, &destination, None, &ops)
+        .assert_ok();
+
+    router_setup.blockchain_wrapper.check_esdt_balance(
+        &router_setup.user_address,
+        MEX_TOKEN_ID,
+        &rust_biguint!(4_999_900_000), //spent 100_000
+    );
+    router_setup.blockchain_wrapper.check_esdt_balance(
+        &router_setup.user_address,
+        USDC_TOKEN_ID,
+        &rust_biguint!(5_000_000_000), //unchanged
+    );
+    router_setup.blockchain_wrapper.check_esdt_balance(
+        &router_setup.owner_address,
+        USDC_TOKEN_ID,
+        &rust_biguint!(82_909),
+    );
+
+    // the zero address sends the output to the caller
+    router_setup
+        .multi_pair_swap_with_limits(MEX_TOKEN_ID, 100_000, 1, 100, &Address::zero(), None, &ops)
+        .assert_ok();
+
+    router_setup.blockchain_wrapper.check_esdt_balance(
+        &router_setup.user_address,
+        USDC_TOKEN_ID,
+        &rust_biguint!(5_000_059_286),
+    );
+}
+
+#[test]
+fn multi_path_swap_test() {
+    let mut router_setup = RouterSetup::new(router::contract_obj, pair::contract_obj);
+
+    router_setup.add_liquidity();
+
+    let mex_pair_address = router_setup.mex_pair_wrapper.address_ref().clone();
+    let usdc_pair_address = router_setup.usdc_pair_wrapper.address_ref().clone();
+    let mex_to_usdc_ops = vec![
+        (
+            mex_pair_address.clone(),
+            SWAP_TOKENS_FIXED_INPUT_FUNC_NAME,
+            WEGLD_TOKEN_ID,
+            1,
+        ),
+        (
+            usdc_pair_address,
+            SWAP_TOKENS_FIXED_INPUT_FUNC_NAME,
+            USDC_TOKEN_ID,
+            1,
+        ),
+    ];
+    let mex_to_wegld_ops = vec![(
+        mex_pair_address,
+        SWAP_TOKENS_FIXED_INPUT_FUNC_NAME,
+        WEGLD_TOKEN_ID,
+        1,
+    )];
+
+    router_setup
+        .multi_path_swap(
+            MEX_TOKEN_ID,
+            100_000,
+            1,
+            None,
+            &[(1, mex_to_usdc_ops.clone()), (1, mex_to_wegld_ops)],
+        )
+        .assert_user_error("All routes should end in the same token");
+
+    router_setup
+        .multi_path_swap(
+            MEX_TOKEN_ID,
+            100_000,
+            1,
+            None,
+            &[(0, mex_to_usdc_ops.clone()), (1, mex_to_usdc_ops.clone())],
+        )
+        .assert_user_error("Invalid route weight. Should not be zero");
+
+    router_setup
+        .multi_path_swap(
+            MEX_TOKEN_ID,
+            100_000,
+            90_000,
+            None,
+            &[(3, mex_to_usdc_ops.clone()), (1, mex_to_usdc_ops.clone())],
+        )
+        .assert_user_error("Slippage exceeded");
+
+    // 75_000 and 25_000 MEX are swapped in sequence through the same route
+    router_setup
+        .multi_path_swap(
+            MEX_TOKEN_ID,
+            100_000,
+            80_000,
+            None,
+            &[(3, mex_to_usdc_ops.clone()), (1, mex_to_usdc_ops)],
+        )
+        .assert_ok();
+
+    router_setup.blockchain_wrapper.check_esdt_balance(
+        &router_setup.user_address,
+        MEX_TOKEN_ID,
+        &rust_biguint!(4_999_900_000),
+    );
+    router_setup.blockchain_wrapper.check_esdt_balance(
+        &router_setup.user_address,
+        WEGLD_TOKEN_ID,
+        &rust_biguint!(5_000_000_000),
+    );
+    router_setup.blockchain_wrapper.check_esdt_balance(
+        &router_setup.user_address,
+        USDC_TOKEN_ID,
+        &rust_biguint!(5_000_082_901),
+    );
+}
+
+#[test]
+fn referral_fee_test() {
+    let mut router_setup = RouterSetup::new(router::contract_obj, pair::contract_obj);
+
+    router_setup.add_liquidity();
+
+    let referrer = router_setup
+        .blockchain_wrapper
+        .create_user_account(&rust_biguint!(0));
+    let ops = vec![(
+        router_setup.mex_pair_wrapper.address_ref().clone(),
+        SWAP_TOKENS_FIXED_INPUT_FUNC_NAME,
+        WEGLD_TOKEN_ID,
+        1,
+    )];
+
+    router_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &router_setup.owner_address,
+            &router_setup.router_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_referral_fee_percent(101);
+            },
+        )
+        .assert_user_error("Referral fee percent too high");
+
+    router_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &router_setup.owner_address,
+            &router_setup.router_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_referral_fee_percent(100);
+            },
+        )
+        .assert_ok();
+
+    router_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &referrer,
+            &router_setup.router_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let _ = sc.claim_referral_fees();
+            },
+        )
+        .assert_user_error("No referral fees to claim");
+
+    // 1% of each input is credited to the referrer
+    router_setup
+        .multi_pair_swap_with_limits(
+            MEX_TOKEN_ID,
+            100_000,
+            1,
+            0,
+            &Address::zero(),
+            Some(&referrer),
+            &ops,
+        )
+        .assert_ok();
+    router_setup
+        .multi_path_swap(
+            MEX_TOKEN_ID,
+            50_000,
+            1,
+            Some(&referrer),
+            &[(1, ops.clone())],
+        )
+        .assert_ok();
+
+    router_setup
+        .multi_path_swap(
+            MEX_TOKEN_ID,
+            50_000,
+            1,
+            Some(&Address::zero()),
+            &[(1, ops.clone())],
+        )
+        .assert_user_error("Invalid referrer");
+
+    // no fee without a referrer
+    router_setup
+        .multi_pair_swap_with_limits(MEX_TOKEN_ID, 100_000, 1, 0, &Address::zero(), None, &ops)
+        .assert_ok();
+
+    router_setup
+        .blockchain_wrapper
+        .execute_query(&router_setup.router_wrapper, |sc| {
+            let fees = sc.get_referrer_fees(managed_address!(&referrer)).to_vec();
+            assert_eq!(fees.len(), 1);
+
+            let fee = fees.get(0);
+            assert_eq!(fee.token_identifier, managed_token_id!(MEX_TOKEN_ID));
+            assert_eq!(fee.amount, managed_biguint!(1_500));
+        })
+        .assert_ok();
+
+    router_setup.blockchain_wrapper.check_esdt_balance(
+        router_setup.router_wrapper.address_ref(),
+        MEX_TOKEN_ID,
+        &rust_biguint!(1_500),
+    );
+
+    router_setup
+        .blockchain_wrapper
+        .execute_tx(
+            &referrer,
+            &router_setup.router_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                let payments = sc.claim_referral_fees();
+                assert_eq!(payments.len(), 1);
+            },
+        )
+        .assert_ok();
+
+    router_setup.blockchain_wrapper.check_esdt_balance(
+        &referrer,
+        MEX_TOKEN_ID,
+        &rust_biguint!(1_500),
+    );
+    router_setup.blockchain_wrapper.check_esdt_balance(
+        router_setup.router_wrapper.address_ref(),
+        MEX_TOKEN_ID,
+        &rust_biguint!(0),
+    );
+
+    router_setup
+        .blockchain_wrapper
+        .execute_query(&router_setup.router_wrapper, |sc| {
+            assert!(sc.get_referrer_fees(managed_address!(&referrer)).is_empty());
+        })
+        .assert_ok();
+}
+
+#[test]
+fn get_best_swap_path_test() {
+    let mut router_setup = RouterSetup::new(router::contract_obj, pair::contract_obj);
+
+    // no liquidity yet
+    router_setup
+        .blockchain_wrapper
+        .execute_query(&router_setup.router_wrapper, |sc| {
+            let _ = sc.get_best_swap_path(
+                managed_token_id!(MEX_TOKEN_ID),
+                managed_token_id!(USDC_TOKEN_ID),
+                managed_biguint!(100_000),
+                2,
+            );
+        })
+        .assert_user_error("No swap path found");
+
+    router_setup.add_liquidity();
+
+    router_setup
+        .blockchain_wrapper
+        .execute_query(&router_setup.router_wrapper, |sc| {
+            let _ = sc.get_best_swap_path(
+                managed_token_id!(MEX_TOKEN_ID),
+                managed_token_id!(USDC_TOKEN_ID),
+                managed_biguint!(100_000),
+                1,
+            );
+        })
+        .assert_user_error("No swap path found");
+
+    router_setup
+        .blockchain_wrapper
+        .execute_query(&router_setup.router_wrapper, |sc| {
+            let _ = sc.get_best_swap_path(
+                managed_token_id!(MEX_TOKEN_ID),
+                managed_token_id!(USDC_TOKEN_ID),
+                managed_biguint!(100_000),
+                5,
+            );
+        })
+        .assert_user_error("Invalid number of hops");
+
+    let mex_pair_address = router_setup.mex_pair_wrapper.address_ref().clone();
+    let usdc_pair_address = router_setup.usdc_pair_wrapper.address_ref().clone();
+    router_setup
+        .blockchain_wrapper
+        .execute_query(&router_setup.router_wrapper, |sc| {
+            let (amount_out, swap_operations) = sc
+                .get_best_swap_path(
+                    managed_token_id!(MEX_TOKEN_ID),
+                    managed_token_id!(USDC_TOKEN_ID),
+                    managed_biguint!(100_000),
+                    3,
+                )
+                .into_tuple();
+            assert_eq!(amount_out, managed_biguint!(82_909));
+
+            assert_eq!(swap_operations.len(), 2);
+            let mut swap_operations = swap_operations.into_iter();
+
+            let (pair_address, function, token_wanted, amount_wanted) =
+                swap_operations.next().unwrap().into_tuple();
+            assert_eq!(pair_address, managed_address!(&mex_pair_address));
+            assert_eq!(function, managed_buffer!(SWAP_TOKENS_FIXED_INPUT_FUNC_NAME));
+            assert_eq!(token_wanted, managed_token_id!(WEGLD_TOKEN_ID));
+            assert_eq!(amount_wanted, managed_biguint!(90_669));
+
+            let (pair_address, function, token_wanted, amount_wanted) =
+                swap_operations.next().unwrap().into_tuple();
+            assert_eq!(pair_address, managed_address!(&usdc_pair_address));
+            assert_eq!(function, managed_buffer!(SWAP_TOKENS_FIXED_INPUT_FUNC_NAME));
+            assert_eq!(token_wanted, managed_token_id!(USDC_TOKEN_ID));
+            assert_eq!(amount_wanted, managed_biguint!(82_909));
+        })
+        .assert_ok();
+
+    // the returned path can be passed to multiPairSwap
+    let ops = vec![
+        (
+            mex_pair_address,
+            SWAP_TOKENS_FIXED_INPUT_FUNC_NAME,
+            WEGLD_TOKEN_ID,
+            90_669,
+        ),
+        (
+            usdc_pair_address,
+            SWAP_TOKENS_FIXED_INPUT_FUNC_NAME,
+            USDC_TOKEN_ID,
+            82_909,
+        ),
+    ];
+    router_setup.multi_pair_swap(MEX_TOKEN_ID, 100_000, &ops);
+
+    router_setup.blockchain_wrapper.check_esdt_balance(
+        &router_setup.user_address,
+        USDC_TOKEN_ID,
+        &rust_biguint!(5_000_082_909),
+    );
+}
+
 #[test]
 fn user_enable_pair_swaps_through_router_test() {
     let rust_zero = rust_biguint!(0u64);
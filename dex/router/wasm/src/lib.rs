@@ -6,9 +6,9 @@
 
 // Init:                                 1
 // Upgrade:                              1
-// Endpoints:                           30
+// Endpoints:                           49
 // Async Callback:                       1
-// Total number of exported functions:  33
+// Total number of exported functions:  52
 
 #![no_std]
 
@@ -23,12 +23,19 @@ multiversx_sc_wasm_adapter::endpoints! {
         pause => pause
         resume => resume
         createPair => create_pair_endpoint
+        createPairWithFeeTier => create_pair_with_fee_tier_endpoint
+        addFeeTier => add_fee_tier
+        removeFeeTier => remove_fee_tier
         upgradePair => upgrade_pair_endpoint
+        upgradeAllPairs => upgrade_all_pairs
         issueLpToken => issue_lp_token
         setLocalRoles => set_local_roles
         removePair => remove_pair
+        removeFeeTierPair => remove_fee_tier_pair
         setFeeOn => set_fee_on
         setFeeOff => set_fee_off
+        setPairEnergyFactoryAddress => set_pair_energy_factory_address
+        setPairsAcceptOriginalCaller => set_pairs_accept_original_caller
         claimDeveloperRewardsPairs => claim_developer_rewards_pairs
         setPairCreationEnabled => set_pair_creation_enabled
         getPairCreationEnabled => pair_creation_enabled
@@ -36,15 +43,27 @@ multiversx_sc_wasm_adapter::endpoints! {
         getOwner => owner
         setTemporaryOwnerPeriod => set_temporary_owner_period
         setPairTemplateAddress => set_pair_template_address
+        getFeeTiers => fee_tiers
         getPairTemplateAddress => pair_template_address
         getTemporaryOwnerPeriod => temporary_owner_period
         getCommonTokensForUserPairs => common_tokens_for_user_pairs
         getAllPairsManagedAddresses => get_all_pairs_addresses
         getAllPairTokens => get_all_token_pairs
         getAllPairContractMetadata => get_all_pair_contract_metadata
+        getAllFeeTierPairContractMetadata => get_all_fee_tier_pair_contract_metadata
         getPair => get_pair
         clearPairTemporaryOwnerStorage => clear_pair_temporary_owner_storage
+        getEgldWrapperAddress => egld_wrapper_address
+        setReferralFeePercent => set_referral_fee_percent
+        claimReferralFees => claim_referral_fees
+        getReferrerFees => get_referrer_fees
+        getReferralFeePercent => referral_fee_percent
+        setEgldWrapperAddress => set_egld_wrapper_address
         multiPairSwap => multi_pair_swap
+        multiPairSwapToEgld => multi_pair_swap_to_egld
+        multiPairSwapWithLimits => multi_pair_swap_with_limits
+        multiPathSwap => multi_path_swap
+        getBestSwapPath => get_best_swap_path
         configEnableByUserParameters => config_enable_by_user_parameters
         addCommonTokensForUserPairs => add_common_tokens_for_user_pairs
         removeCommonTokensForUserPairs => remove_common_tokens_for_user_pairs
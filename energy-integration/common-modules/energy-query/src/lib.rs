@@ -4,6 +4,8 @@ multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
 pub use energy_factory::energy::Energy;
+use energy_factory::energy::ProxyTrait as _;
+use energy_factory::energy_checkpoints::ProxyTrait as _;
 
 static USER_ENERGY_STORAGE_KEY: &[u8] = b"userEnergy";
 static LOCKED_TOKEN_ID_STORAGE_KEY: &[u8] = b"lockedTokenId";
@@ -52,6 +54,37 @@ pub trait EnergyQueryModule {
         }
     }
 
+    /// Energy the user had when the given epoch started, as tracked by the energy factory checkpoints
+    fn get_energy_amount_at_epoch(&self, user: &ManagedAddress, epoch: u64) -> BigUint {
+        let energy_factory_address = self.get_energy_factory_address_non_empty();
+        self.energy_factory_proxy(energy_factory_address)
+            .get_energy_amount_for_user_at_epoch(user.clone(), epoch)
+            .execute_on_dest_context()
+    }
+
+    /// Total energy of all users when the given epoch started
+    fn get_total_energy_amount_at_epoch(&self, epoch: u64) -> BigUint {
+        let energy_factory_address = self.get_energy_factory_address_non_empty();
+        self.energy_factory_proxy(energy_factory_address)
+            .get_total_energy_amount_at_epoch(epoch)
+            .execute_on_dest_context()
+    }
+
+    /// Whether the total energy includes all the users when the given epoch started
+    fn is_total_energy_complete_at_epoch(&self, epoch: u64) -> bool {
+        let energy_factory_address = self.get_energy_factory_address_non_empty();
+        self.energy_factory_proxy(energy_factory_address)
+            .is_total_energy_complete_at_epoch(epoch)
+            .execute_on_dest_context()
+    }
+
+    fn get_energy_factory_address_non_empty(&self) -> ManagedAddress {
+        let mapper = self.energy_factory_address();
+        require!(!mapper.is_empty(), "Energy factory address not set");
+
+        mapper.get()
+    }
+
     fn get_base_token_id(&self) -> TokenIdentifier {
         self.read_raw_storage_from_energy_factory(ManagedBuffer::new_from_bytes(
             BASE_TOKEN_ID_STORAGE_KEY,
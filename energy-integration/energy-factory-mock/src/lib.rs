@@ -5,7 +5,7 @@ multiversx_sc::imports!();
 use energy_query::Energy;
 
 #[multiversx_sc::contract]
-pub trait EnergyFactoryMock {
+pub trait EnergyFactoryMock: energy_factory::energy_checkpoints::EnergyCheckpointsModule {
     #[init]
     fn init(&self) {}
 
@@ -17,11 +17,26 @@ pub trait EnergyFactoryMock {
         total_locked_tokens: BigUint,
     ) {
         let current_epoch = self.blockchain().get_block_epoch();
-        self.user_energy(&user).set(&Energy::new(
+        let energy = Energy::new(
             BigInt::from(energy_amount),
             current_epoch,
             total_locked_tokens,
-        ));
+        );
+        self.set_energy_entry(&user, energy);
+    }
+
+    #[endpoint(checkpointLegacyEnergy)]
+    fn checkpoint_legacy_energy(&self, users: MultiValueEncoded<ManagedAddress>) {
+        for user in users {
+            if let Some(stored_entry) = self.get_stored_energy_entry(&user) {
+                self.checkpoint_legacy_energy_entry(&user, stored_entry);
+            }
+        }
+    }
+
+    #[endpoint(completeLegacyEnergyCheckpoints)]
+    fn complete_legacy_energy_checkpoints_endpoint(&self) {
+        self.complete_legacy_energy_checkpoints();
     }
 
     #[view(getEnergyAmountForUser)]
@@ -43,13 +58,20 @@ pub trait EnergyFactoryMock {
         }
     }
 
+    #[view(getEnergyAmountForUserAtEpoch)]
+    fn get_energy_amount_for_user_at_epoch(&self, user: ManagedAddress, epoch: u64) -> BigUint {
+        let stored_entry = self.get_stored_energy_entry(&user);
+        self.get_user_energy_entry_at_epoch(&user, stored_entry, epoch)
+            .get_energy_amount()
+    }
+
     #[endpoint(setUserEnergyAfterLockedTokenTransfer)]
     fn set_user_energy_after_locked_token_transfer(
         &self,
         user: ManagedAddress,
         energy: Energy<Self::Api>,
     ) {
-        self.user_energy(&user).set(&energy);
+        self.set_energy_entry(&user, energy);
     }
 
     #[endpoint(transferUnlockedToken)]
@@ -63,6 +85,21 @@ pub trait EnergyFactoryMock {
             .direct_esdt(&dest, &base_asset_token_id, 0, &amount);
     }
 
+    fn set_energy_entry(&self, user: &ManagedAddress, energy: Energy<Self::Api>) {
+        let old_stored_entry = self.get_stored_energy_entry(user);
+        self.checkpoint_energy_update(user, old_stored_entry, &energy);
+        self.user_energy(user).set(&energy);
+    }
+
+    fn get_stored_energy_entry(&self, user: &ManagedAddress) -> Option<Energy<Self::Api>> {
+        let mapper = self.user_energy(user);
+        if !mapper.is_empty() {
+            Some(mapper.get())
+        } else {
+            None
+        }
+    }
+
     #[storage_mapper("userEnergy")]
     fn user_energy(&self, user: &ManagedAddress) -> SingleValueMapper<Energy<Self::Api>>;
 
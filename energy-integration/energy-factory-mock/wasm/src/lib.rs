@@ -5,9 +5,9 @@
 ////////////////////////////////////////////////////
 
 // Init:                                 1
-// Endpoints:                            5
+// Endpoints:                           14
 // Async Callback (empty):               1
-// Total number of exported functions:   7
+// Total number of exported functions:  16
 
 #![no_std]
 
@@ -19,10 +19,19 @@ multiversx_sc_wasm_adapter::endpoints! {
     (
         init => init
         setUserEnergy => set_user_energy
+        checkpointLegacyEnergy => checkpoint_legacy_energy
+        completeLegacyEnergyCheckpoints => complete_legacy_energy_checkpoints_endpoint
         getEnergyAmountForUser => get_energy_amount_for_user
         getEnergyEntryForUser => get_energy_entry_for_user
+        getEnergyAmountForUserAtEpoch => get_energy_amount_for_user_at_epoch
         setUserEnergyAfterLockedTokenTransfer => set_user_energy_after_locked_token_transfer
         transferUnlockedToken => transfer_unlocked_token
+        getTotalEnergyEntryAtEpoch => get_total_energy_entry_at_epoch
+        getTotalEnergyAmountAtEpoch => get_total_energy_amount_at_epoch
+        getTotalEnergyEntry => get_updated_total_energy_entry
+        isTotalEnergyCompleteAtEpoch => is_total_energy_complete_at_epoch
+        getUserEnergyCheckpointEpochs => user_energy_checkpoint_epochs
+        getTotalEnergyCompleteEpoch => total_energy_complete_epoch
     )
 }
 
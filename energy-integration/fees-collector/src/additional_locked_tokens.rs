@@ -3,6 +3,7 @@ multiversx_sc::imports!();
 use common_types::Week;
 
 pub const BLOCKS_IN_WEEK: u64 = 100_800;
+pub const SECONDS_IN_WEEK: u64 = 604_800;
 
 #[multiversx_sc::module]
 pub trait AdditionalLockedTokensModule:
@@ -14,10 +15,48 @@ pub trait AdditionalLockedTokensModule:
     #[only_owner]
     #[endpoint(setLockedTokensPerBlock)]
     fn set_locked_tokens_per_block(&self, locked_tokens_per_block: BigUint) {
+        require!(
+            self.locked_tokens_per_second().is_empty(),
+            "Per block emission is no longer used"
+        );
+
         self.accumulate_additional_locked_tokens();
         self.locked_tokens_per_block().set(locked_tokens_per_block);
     }
 
+    /// Converts the per block amount to a per second one, keeping the same weekly amount.
+    #[only_owner]
+    #[endpoint(migrateToLockedTokensPerSecond)]
+    fn migrate_to_locked_tokens_per_second(&self) {
+        require!(
+            self.locked_tokens_per_second().is_empty(),
+            "Already using per second emission"
+        );
+
+        self.accumulate_additional_locked_tokens();
+
+        let locked_tokens_per_block = self.locked_tokens_per_block().take();
+        let locked_tokens_per_second = locked_tokens_per_block * BLOCKS_IN_WEEK / SECONDS_IN_WEEK;
+        require!(locked_tokens_per_second > 0, "Invalid locked tokens amount");
+
+        self.locked_tokens_per_second()
+            .set(locked_tokens_per_second);
+    }
+
+    #[only_owner]
+    #[endpoint(setLockedTokensPerSecond)]
+    fn set_locked_tokens_per_second(&self, locked_tokens_per_second: BigUint) {
+        require!(
+            !self.locked_tokens_per_second().is_empty(),
+            "Not using per second emission"
+        );
+        require!(locked_tokens_per_second > 0, "Invalid locked tokens amount");
+
+        self.accumulate_additional_locked_tokens();
+        self.locked_tokens_per_second()
+            .set(locked_tokens_per_second);
+    }
+
     fn accumulate_additional_locked_tokens(&self) {
         let last_update_week_mapper = self.last_locked_token_add_week();
         let mut last_update_week = last_update_week_mapper.get();
@@ -27,9 +66,12 @@ pub trait AdditionalLockedTokensModule:
         }
 
         last_update_week = current_week - 1;
-        let blocks_in_week = BLOCKS_IN_WEEK;
-        let amount_per_block = self.locked_tokens_per_block().get();
-        let new_tokens_amount = amount_per_block * blocks_in_week;
+        let locked_tokens_per_second_mapper = self.locked_tokens_per_second();
+        let new_tokens_amount = if !locked_tokens_per_second_mapper.is_empty() {
+            locked_tokens_per_second_mapper.get() * SECONDS_IN_WEEK
+        } else {
+            self.locked_tokens_per_block().get() * BLOCKS_IN_WEEK
+        };
 
         let locked_token_id = self.locked_token_id().get();
         self.accumulated_fees(last_update_week, &locked_token_id)
@@ -45,4 +87,8 @@ pub trait AdditionalLockedTokensModule:
     #[view(getLockedTokensPerBlock)]
     #[storage_mapper("lockedTokensPerBlock")]
     fn locked_tokens_per_block(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getLockedTokensPerSecond)]
+    #[storage_mapper("lockedTokensPerSecond")]
+    fn locked_tokens_per_second(&self) -> SingleValueMapper<BigUint>;
 }
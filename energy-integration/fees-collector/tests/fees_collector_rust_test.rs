@@ -3,7 +3,9 @@
 mod fees_collector_test_setup;
 
 use energy_query::Energy;
-use fees_collector::additional_locked_tokens::{AdditionalLockedTokensModule, BLOCKS_IN_WEEK};
+use fees_collector::additional_locked_tokens::{
+    AdditionalLockedTokensModule, BLOCKS_IN_WEEK, SECONDS_IN_WEEK,
+};
 use fees_collector::fees_accumulation::FeesAccumulationModule;
 use fees_collector_test_setup::*;
 use multiversx_sc::types::{BigInt, EsdtTokenPayment, ManagedVec};
@@ -1419,3 +1421,73 @@ fn additional_locked_tokens_test() {
         })
         .assert_ok();
 }
+
+#[test]
+fn additional_locked_tokens_per_second_test() {
+    let rust_zero = rust_biguint!(0);
+    let mut fc_setup =
+        FeesCollectorSetup::new(fees_collector::contract_obj, energy_factory::contract_obj);
+
+    fc_setup.advance_week();
+
+    fc_setup
+        .b_mock
+        .execute_tx(
+            &fc_setup.owner_address,
+            &fc_setup.fc_wrapper,
+            &rust_zero,
+            |sc| {
+                sc.set_locked_tokens_per_block(managed_biguint!(6_000));
+                sc.migrate_to_locked_tokens_per_second();
+            },
+        )
+        .assert_ok();
+
+    // the weekly amount stays the same
+    fc_setup
+        .b_mock
+        .execute_query(&fc_setup.fc_wrapper, |sc| {
+            assert_eq!(sc.locked_tokens_per_block().get(), 0u64);
+            assert_eq!(sc.locked_tokens_per_second().get(), 1_000u64);
+            assert_eq!(SECONDS_IN_WEEK * 1_000u64, BLOCKS_IN_WEEK * 6_000u64);
+        })
+        .assert_ok();
+
+    fc_setup
+        .b_mock
+        .execute_tx(
+            &fc_setup.owner_address,
+            &fc_setup.fc_wrapper,
+            &rust_zero,
+            |sc| {
+                sc.set_locked_tokens_per_block(managed_biguint!(6_000));
+            },
+        )
+        .assert_user_error("Per block emission is no longer used");
+
+    fc_setup.advance_week();
+
+    fc_setup
+        .b_mock
+        .execute_tx(
+            &fc_setup.owner_address,
+            &fc_setup.fc_wrapper,
+            &rust_zero,
+            |sc| {
+                sc.accumulate_additional_locked_tokens();
+            },
+        )
+        .assert_ok();
+
+    fc_setup
+        .b_mock
+        .execute_query(&fc_setup.fc_wrapper, |sc| {
+            let previous_week = sc.last_locked_token_add_week().get() - 1;
+            assert_eq!(
+                sc.accumulated_fees(previous_week, &managed_token_id!(LOCKED_TOKEN_ID))
+                    .get(),
+                SECONDS_IN_WEEK * 1_000u64
+            );
+        })
+        .assert_ok();
+}
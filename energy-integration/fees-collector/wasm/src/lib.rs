@@ -6,9 +6,9 @@
 
 // Init:                                 1
 // Upgrade:                              1
-// Endpoints:                           37
+// Endpoints:                           40
 // Async Callback (empty):               1
-// Total number of exported functions:  40
+// Total number of exported functions:  43
 
 #![no_std]
 
@@ -41,8 +41,11 @@ multiversx_sc_wasm_adapter::endpoints! {
         depositSwapFees => deposit_swap_fees
         getAccumulatedFees => accumulated_fees
         setLockedTokensPerBlock => set_locked_tokens_per_block
+        migrateToLockedTokensPerSecond => migrate_to_locked_tokens_per_second
+        setLockedTokensPerSecond => set_locked_tokens_per_second
         getLastLockedTokensAddWeek => last_locked_token_add_week
         getLockedTokensPerBlock => locked_tokens_per_block
+        getLockedTokensPerSecond => locked_tokens_per_second
         setLockingScAddress => set_locking_sc_address
         setLockEpochs => set_lock_epochs
         getLockingScAddress => locking_sc_address
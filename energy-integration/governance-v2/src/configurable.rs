@@ -18,6 +18,7 @@ multiversx_sc::imports!();
 /// - `votingDelayInBlocks` - Number of blocks to wait after a block is proposed before being able to vote/downvote that proposal
 /// - `votingPeriodInBlocks` - Number of blocks the voting period lasts (voting delay does not count towards this)  
 /// - `lockTimeAfterVotingEndsInBlocks` - Number of blocks to wait before a successful proposal can be executed  
+/// - `gracePeriodInBlocks` - Number of blocks after the lock time during which a queued proposal can be executed  
 ///
 /// The module also provides events for most actions that happen:
 /// - `proposalCreated` - triggers when a proposal is created. Also provoides all the relevant information, like proposer, actions etc.  
@@ -36,6 +37,12 @@ const MIN_VOTING_PERIOD: u64 = 14_400; // 24 Hours
 const MAX_VOTING_PERIOD: u64 = 201_600; // 2 Weeks
 const MIN_QUORUM: u64 = 1_000; // 10%
 const MAX_QUORUM: u64 = 6_000; // 60%
+const MIN_LOCK_TIME_AFTER_VOTING_ENDS: u64 = 1;
+const MAX_LOCK_TIME_AFTER_VOTING_ENDS: u64 = 201_600; // 2 Weeks
+const MIN_GRACE_PERIOD: u64 = 14_400; // 24 Hours
+const MAX_GRACE_PERIOD: u64 = 201_600; // 2 Weeks
+pub const DEFAULT_LOCK_TIME_AFTER_VOTING_ENDS: u64 = 28_800; // 48 Hours
+pub const DEFAULT_GRACE_PERIOD: u64 = 100_800; // 1 Week
 const MIN_MIN_FEE_FOR_PROPOSE: u64 = 2_000_000;
 const MAX_MIN_FEE_FOR_PROPOSE: u64 = 200_000_000_000;
 const DECIMALS_CONST: u64 = 1_000_000_000_000_000_000;
@@ -85,6 +92,18 @@ pub trait ConfigurablePropertiesModule:
         self.try_change_voting_period_in_blocks(new_value);
     }
 
+    #[only_owner]
+    #[endpoint(changeLockTimeAfterVotingEndsInBlocks)]
+    fn change_lock_time_after_voting_ends_in_blocks(&self, new_value: u64) {
+        self.try_change_lock_time_after_voting_ends_in_blocks(new_value);
+    }
+
+    #[only_owner]
+    #[endpoint(changeGracePeriodInBlocks)]
+    fn change_grace_period_in_blocks(&self, new_value: u64) {
+        self.try_change_grace_period_in_blocks(new_value);
+    }
+
     fn try_change_min_energy_for_propose(&self, new_value: BigUint) {
         self.min_energy_for_propose().set(&new_value);
     }
@@ -139,6 +158,26 @@ pub trait ConfigurablePropertiesModule:
             .set(new_withdraw_percentage);
     }
 
+    fn try_change_lock_time_after_voting_ends_in_blocks(&self, new_lock_time: u64) {
+        require!(
+            (MIN_LOCK_TIME_AFTER_VOTING_ENDS..MAX_LOCK_TIME_AFTER_VOTING_ENDS)
+                .contains(&new_lock_time),
+            "Not valid value for lock time after voting ends!"
+        );
+
+        self.lock_time_after_voting_ends_in_blocks()
+            .set(new_lock_time);
+    }
+
+    fn try_change_grace_period_in_blocks(&self, new_grace_period: u64) {
+        require!(
+            (MIN_GRACE_PERIOD..MAX_GRACE_PERIOD).contains(&new_grace_period),
+            "Not valid value for grace period!"
+        );
+
+        self.grace_period_in_blocks().set(new_grace_period);
+    }
+
     fn try_change_fee_token_id(&self, fee_token_id: TokenIdentifier) {
         require!(fee_token_id.is_valid_esdt_identifier(), ERROR_NOT_AN_ESDT);
         self.fee_token_id().set_if_empty(&fee_token_id);
@@ -168,6 +207,14 @@ pub trait ConfigurablePropertiesModule:
     #[storage_mapper("votingPeriodInBlocks")]
     fn voting_period_in_blocks(&self) -> SingleValueMapper<u64>;
 
+    #[view(getLockTimeAfterVotingEndsInBlocks)]
+    #[storage_mapper("lockTimeAfterVotingEndsInBlocks")]
+    fn lock_time_after_voting_ends_in_blocks(&self) -> SingleValueMapper<u64>;
+
+    #[view(getGracePeriodInBlocks)]
+    #[storage_mapper("gracePeriodInBlocks")]
+    fn grace_period_in_blocks(&self) -> SingleValueMapper<u64>;
+
     #[view(getFeeTokenId)]
     #[storage_mapper("feeTokenId")]
     fn fee_token_id(&self) -> SingleValueMapper<TokenIdentifier>;
@@ -12,3 +12,8 @@ pub const FEE_ALREADY_WITHDRAWN: &[u8] = b"Fee already withdrawn!";
 pub const NO_PROPOSAL: &[u8] = b"Proposal does not exist";
 pub const WITHDRAW_NOT_ALLOWED: &[u8] = b"You may not withdraw funds from this proposal!";
 pub const PROPOSAL_NOT_ALLOWED_FOR_SC: &[u8] = b"Smart Contracts are not allowed to propose!";
+pub const PROPOSAL_NOT_SUCCEEDED: &[u8] = b"Only succeeded proposals can be queued";
+pub const PROPOSAL_NOT_QUEUED: &[u8] = b"Proposal is not queued";
+pub const TIMELOCK_NOT_ENDED: &[u8] = b"Proposal timelock has not ended yet";
+pub const NOT_ENOUGH_GAS_FOR_ACTIONS: &[u8] = b"Not enough gas left to execute all actions";
+pub const NO_VOTING_ENERGY: &[u8] = b"No energy at proposal snapshot epoch";
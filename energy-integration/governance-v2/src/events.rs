@@ -53,6 +53,27 @@ pub trait EventsModule {
     #[event("proposalCanceled")]
     fn proposal_canceled_event(&self, #[indexed] proposal_id: ProposalId);
 
+    #[event("proposalQueued")]
+    fn proposal_queued_event(
+        &self,
+        #[indexed] proposal_id: ProposalId,
+        #[indexed] execution_start_block: u64,
+        #[indexed] execution_end_block: u64,
+    );
+
+    #[event("proposalExecuted")]
+    fn proposal_executed_event(&self, #[indexed] proposal_id: ProposalId);
+
+    #[event("proposalActionExecuted")]
+    fn proposal_action_executed_event(
+        &self,
+        #[indexed] proposal_id: ProposalId,
+        #[indexed] action_index: usize,
+        #[indexed] dest_address: &ManagedAddress,
+        #[indexed] function_name: &ManagedBuffer,
+        results: &ManagedVec<ManagedBuffer>,
+    );
+
     #[event("proposalWithdrawAfterDefeated")]
     fn proposal_withdraw_after_defeated_event(&self, #[indexed] proposal_id: ProposalId);
 }
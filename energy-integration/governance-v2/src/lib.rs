@@ -14,9 +14,12 @@ use proposal_storage::VoteType;
 use weekly_rewards_splitting::events::Week;
 use weekly_rewards_splitting::global_info::ProxyTrait as _;
 
-use crate::configurable::{FULL_PERCENTAGE, MAX_GAS_LIMIT_PER_BLOCK};
+use crate::configurable::{
+    DEFAULT_GRACE_PERIOD, DEFAULT_LOCK_TIME_AFTER_VOTING_ENDS, FULL_PERCENTAGE,
+    MAX_GAS_LIMIT_PER_BLOCK,
+};
 use crate::errors::*;
-use crate::proposal_storage::ProposalVotes;
+use crate::proposal_storage::{ProposalTimelock, ProposalVotes};
 
 /// An empty contract. To be used as a template when starting a new contract from scratch.
 #[multiversx_sc::contract]
@@ -59,16 +62,22 @@ pub trait GovernanceV2:
         self.set_energy_factory_address(energy_factory_address);
         self.fees_collector_address().set(&fees_collector_address);
         self.try_change_fee_token_id(fee_token);
+        self.set_default_timelock_params();
     }
 
     #[upgrade]
-    fn upgrade(&self) {}
+    fn upgrade(&self) {
+        self.set_default_timelock_params();
+    }
 
     /// Propose a list of actions.
     /// A maximum of MAX_GOVERNANCE_PROPOSAL_ACTIONS can be proposed at a time.
     ///
     /// The proposer's energy is NOT automatically used for voting. A separate vote is needed.
     ///
+    /// The quorum is computed from the total energy at the start of the current epoch,
+    /// the same snapshot used for the voting power.
+    ///
     /// Returns the ID of the newly created proposal.
     #[payable("*")]
     #[endpoint]
@@ -125,6 +134,12 @@ pub trait GovernanceV2:
         let voting_period_in_blocks = self.voting_period_in_blocks().get();
         let withdraw_percentage_defeated = self.withdraw_percentage_defeated().get();
         let current_block = self.blockchain().get_block_nonce();
+        let snapshot_epoch = self.blockchain().get_block_epoch();
+        let total_quorum = if self.is_total_energy_complete_at_epoch(snapshot_epoch) {
+            self.get_total_energy_amount_at_epoch(snapshot_epoch)
+        } else {
+            self.get_fees_collector_total_energy()
+        };
 
         let proposal = GovernanceProposal {
             proposal_id: self.proposals().len() + 1,
@@ -136,11 +151,13 @@ pub trait GovernanceV2:
             voting_delay_in_blocks,
             voting_period_in_blocks,
             withdraw_percentage_defeated,
-            total_quorum: BigUint::zero(),
+            total_quorum,
             proposal_start_block: current_block,
             fee_withdrawn: false,
         };
         let proposal_id = self.proposals().push(&proposal);
+        self.proposal_energy_snapshot_epoch(proposal_id)
+            .set(snapshot_epoch);
 
         self.proposal_votes(proposal_id)
             .set(ProposalVotes::default());
@@ -149,7 +166,8 @@ pub trait GovernanceV2:
         proposal_id
     }
 
-    /// Vote on a proposal. The voting power depends on the user's energy.
+    /// Vote on a proposal. The voting power depends on the user's energy
+    /// at the start of the epoch in which the proposal was created.
     #[endpoint]
     fn vote(&self, proposal_id: ProposalId, vote: VoteType) {
         self.require_valid_proposal_id(proposal_id);
@@ -164,25 +182,18 @@ pub trait GovernanceV2:
 
         let current_quorum = self.proposal_votes(proposal_id).get().quorum;
 
-        // First voter -> update total_energy
-        if current_quorum == BigUint::zero() {
-            let fees_collector_addr = self.fees_collector_address().get();
-            let last_global_update_week: Week = self
-                .fees_collector_proxy(fees_collector_addr.clone())
-                .last_global_update_week()
-                .execute_on_dest_context();
-
-            let total_quorum: BigUint = self
-                .fees_collector_proxy(fees_collector_addr)
-                .total_energy_for_week(last_global_update_week)
-                .execute_on_dest_context();
-
+        // First voter on a proposal created before energy snapshots -> update total_energy
+        if current_quorum == BigUint::zero()
+            && self.proposal_energy_snapshot_epoch(proposal_id).is_empty()
+        {
+            let total_quorum = self.get_fees_collector_total_energy();
             let mut proposal = self.proposals().get(proposal_id);
             proposal.total_quorum = total_quorum;
             self.proposals().set(proposal_id, &proposal);
         }
 
-        let user_quorum = self.get_energy_amount_non_zero(&voter);
+        let user_quorum = self.get_voting_energy(&voter, proposal_id);
+        require!(user_quorum > 0, NO_VOTING_ENERGY);
         let voting_power = self.smoothing_function(&user_quorum);
 
         match vote {
@@ -239,6 +250,74 @@ pub trait GovernanceV2:
         }
     }
 
+    /// Queue a succeeded proposal for execution.
+    /// The actions can be executed only after the lock time has passed,
+    /// and before the grace period ends. Anyone can queue a succeeded proposal.
+    #[endpoint]
+    fn queue(&self, proposal_id: ProposalId) {
+        require!(
+            self.get_proposal_status(proposal_id) == GovernanceProposalStatus::Succeeded,
+            PROPOSAL_NOT_SUCCEEDED
+        );
+
+        let current_block = self.blockchain().get_block_nonce();
+        let execution_start_block =
+            current_block + self.lock_time_after_voting_ends_in_blocks().get();
+        let execution_end_block = execution_start_block + self.grace_period_in_blocks().get();
+        self.proposal_timelock(proposal_id).set(ProposalTimelock {
+            execution_start_block,
+            execution_end_block,
+        });
+
+        self.proposal_queued_event(proposal_id, execution_start_block, execution_end_block);
+    }
+
+    /// Execute the actions of a queued proposal, once its timelock has ended.
+    /// Each action result is emitted in a separate event.
+    /// If any of the actions fails, the whole execution is reverted,
+    /// and the proposal can be executed again until it expires.
+    #[endpoint]
+    fn execute(&self, proposal_id: ProposalId) {
+        require!(
+            self.get_proposal_status(proposal_id) == GovernanceProposalStatus::Queued,
+            PROPOSAL_NOT_QUEUED
+        );
+
+        let current_block = self.blockchain().get_block_nonce();
+        let timelock = self.proposal_timelock(proposal_id).get();
+        require!(
+            current_block >= timelock.execution_start_block,
+            TIMELOCK_NOT_ENDED
+        );
+
+        let proposal = self.proposals().get(proposal_id);
+        require!(
+            self.blockchain().get_gas_left() > self.total_gas_needed(&proposal.actions),
+            NOT_ENOUGH_GAS_FOR_ACTIONS
+        );
+
+        self.proposal_executed(proposal_id).set(true);
+
+        for (action_index, action) in proposal.actions.iter().enumerate() {
+            let results: MultiValueEncoded<ManagedBuffer> = self
+                .send()
+                .contract_call::<()>(action.dest_address.clone(), action.function_name.clone())
+                .with_raw_arguments(ManagedArgBuffer::from(action.arguments.clone()))
+                .with_gas_limit(action.gas_limit)
+                .execute_on_dest_context();
+
+            self.proposal_action_executed_event(
+                proposal_id,
+                action_index,
+                &action.dest_address,
+                &action.function_name,
+                &results.to_vec(),
+            );
+        }
+
+        self.proposal_executed_event(proposal_id);
+    }
+
     /// When a proposal was defeated, the proposer can withdraw
     /// If DefeatedWithVeto only part of the fee  can be withdrawn
     #[endpoint(withdrawDeposit)]
@@ -249,7 +328,11 @@ pub trait GovernanceV2:
             GovernanceProposalStatus::None => {
                 sc_panic!(NO_PROPOSAL);
             }
-            GovernanceProposalStatus::Succeeded | GovernanceProposalStatus::Defeated => {
+            GovernanceProposalStatus::Succeeded
+            | GovernanceProposalStatus::Queued
+            | GovernanceProposalStatus::Executed
+            | GovernanceProposalStatus::Expired
+            | GovernanceProposalStatus::Defeated => {
                 let mut proposal = self.proposals().get(proposal_id);
 
                 require!(caller == proposal.proposer, ONLY_PROPOSER_WITHDRAW);
@@ -288,6 +371,27 @@ pub trait GovernanceV2:
         self.proposal_withdraw_after_defeated_event(proposal_id);
     }
 
+    fn set_default_timelock_params(&self) {
+        self.lock_time_after_voting_ends_in_blocks()
+            .set_if_empty(DEFAULT_LOCK_TIME_AFTER_VOTING_ENDS);
+        self.grace_period_in_blocks()
+            .set_if_empty(DEFAULT_GRACE_PERIOD);
+    }
+
+    /// The total energy of the last week for which the fees collector was updated.
+    /// Used while the energy factory total does not include all the users yet.
+    fn get_fees_collector_total_energy(&self) -> BigUint {
+        let fees_collector_addr = self.fees_collector_address().get();
+        let last_global_update_week: Week = self
+            .fees_collector_proxy(fees_collector_addr.clone())
+            .last_global_update_week()
+            .execute_on_dest_context();
+
+        self.fees_collector_proxy(fees_collector_addr)
+            .total_energy_for_week(last_global_update_week)
+            .execute_on_dest_context()
+    }
+
     fn total_gas_needed(
         &self,
         actions: &ArrayVec<GovernanceAction<Self::Api>, MAX_GOVERNANCE_PROPOSAL_ACTIONS>,
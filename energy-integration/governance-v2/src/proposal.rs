@@ -18,6 +18,9 @@ pub enum GovernanceProposalStatus {
     Defeated,
     DefeatedWithVeto,
     Succeeded,
+    Queued,
+    Executed,
+    Expired,
 }
 
 #[derive(TypeAbi, TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Debug)]
@@ -11,6 +11,14 @@ pub enum VoteType {
     AbstainVote,
 }
 
+#[derive(
+    TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi, Clone, Copy, PartialEq, Debug,
+)]
+pub struct ProposalTimelock {
+    pub execution_start_block: u64,
+    pub execution_end_block: u64,
+}
+
 #[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, TypeAbi)]
 pub struct ProposalVotes<M: ManagedTypeApi> {
     pub up_votes: BigUint<M>,
@@ -46,6 +54,9 @@ pub trait ProposalStorageModule {
     fn clear_proposal(&self, proposal_id: ProposalId) {
         self.proposals().clear_entry(proposal_id);
         self.proposal_votes(proposal_id).clear();
+        self.proposal_timelock(proposal_id).clear();
+        self.proposal_executed(proposal_id).clear();
+        self.proposal_energy_snapshot_epoch(proposal_id).clear();
     }
 
     #[view(getProposals)]
@@ -62,4 +73,17 @@ pub trait ProposalStorageModule {
         &self,
         proposal_id: ProposalId,
     ) -> SingleValueMapper<ProposalVotes<Self::Api>>;
+
+    #[view(getProposalTimelock)]
+    #[storage_mapper("proposalTimelock")]
+    fn proposal_timelock(&self, proposal_id: ProposalId) -> SingleValueMapper<ProposalTimelock>;
+
+    #[view(isProposalExecuted)]
+    #[storage_mapper("proposalExecuted")]
+    fn proposal_executed(&self, proposal_id: ProposalId) -> SingleValueMapper<bool>;
+
+    /// Votes are weighted by the energy users had when this epoch started
+    #[view(getProposalEnergySnapshotEpoch)]
+    #[storage_mapper("proposalEnergySnapshotEpoch")]
+    fn proposal_energy_snapshot_epoch(&self, proposal_id: ProposalId) -> SingleValueMapper<u64>;
 }
@@ -36,7 +36,7 @@ pub trait ViewsModule:
         }
 
         if self.quorum_reached(proposal_id) && self.vote_reached(proposal_id) {
-            GovernanceProposalStatus::Succeeded
+            self.get_succeeded_proposal_status(proposal_id, current_block)
         } else if self.vote_down_with_veto(proposal_id) {
             GovernanceProposalStatus::DefeatedWithVeto
         } else {
@@ -46,6 +46,28 @@ pub trait ViewsModule:
 
     // private
 
+    fn get_succeeded_proposal_status(
+        &self,
+        proposal_id: ProposalId,
+        current_block: u64,
+    ) -> GovernanceProposalStatus {
+        if self.proposal_executed(proposal_id).get() {
+            return GovernanceProposalStatus::Executed;
+        }
+
+        let timelock_mapper = self.proposal_timelock(proposal_id);
+        if timelock_mapper.is_empty() {
+            return GovernanceProposalStatus::Succeeded;
+        }
+
+        let timelock = timelock_mapper.get();
+        if current_block >= timelock.execution_end_block {
+            GovernanceProposalStatus::Expired
+        } else {
+            GovernanceProposalStatus::Queued
+        }
+    }
+
     fn vote_reached(&self, proposal_id: ProposalId) -> bool {
         let proposal_votes = self.proposal_votes(proposal_id).get();
         let total_votes = proposal_votes.get_total_votes();
@@ -79,6 +101,18 @@ pub trait ViewsModule:
         current_quorum * FULL_PERCENTAGE >= required_minimum_percentage * total_quorum_for_proposal
     }
 
+    /// The energy used as voting weight for the given proposal.
+    /// Proposals created before energy snapshots were introduced use the current energy.
+    #[view(getUserVotingEnergy)]
+    fn get_voting_energy(&self, user: &ManagedAddress, proposal_id: ProposalId) -> BigUint {
+        let snapshot_epoch_mapper = self.proposal_energy_snapshot_epoch(proposal_id);
+        if snapshot_epoch_mapper.is_empty() {
+            return self.get_energy_amount(user);
+        }
+
+        self.get_energy_amount_at_epoch(user, snapshot_epoch_mapper.get())
+    }
+
     fn require_valid_proposal_id(&self, proposal_id: ProposalId) {
         require!(
             self.is_valid_proposal_id(proposal_id),
@@ -2,13 +2,18 @@
 
 mod gov_test_setup;
 
+use energy_query::EnergyQueryModule;
 use gov_test_setup::*;
 use governance_v2::{
-    configurable::ConfigurablePropertiesModule, proposal::GovernanceProposalStatus,
-    proposal_storage::ProposalStorageModule, views::ViewsModule,
+    configurable::{
+        ConfigurablePropertiesModule, DEFAULT_GRACE_PERIOD, DEFAULT_LOCK_TIME_AFTER_VOTING_ENDS,
+    },
+    proposal::GovernanceProposalStatus,
+    proposal_storage::ProposalStorageModule,
+    views::ViewsModule,
 };
 use multiversx_sc::types::ManagedVec;
-use multiversx_sc_scenario::{managed_buffer, rust_biguint};
+use multiversx_sc_scenario::{managed_address, managed_biguint, managed_buffer, rust_biguint};
 
 #[test]
 fn init_gov_test() {
@@ -732,3 +737,271 @@ fn gov_propose_cancel_proposal_id_test() {
         .check_proposal_id_consistency(&first_user_addr, proposal_id)
         .assert_ok();
 }
+
+#[test]
+fn gov_queue_and_execute_proposal_test() {
+    let mut gov_setup = GovSetup::new(governance_v2::contract_obj);
+
+    let first_user_addr = gov_setup.first_user.clone();
+    let second_user_addr = gov_setup.second_user.clone();
+    let no_energy_user_addr = gov_setup.no_energy_user.clone();
+    let energy_factory_addr = gov_setup.energy_factory_wrapper.address_ref().clone();
+    let min_fee = rust_biguint!(MIN_FEE_FOR_PROPOSE) * DECIMALS_CONST;
+    gov_setup
+        .b_mock
+        .set_esdt_balance(&first_user_addr, MEX_TOKEN_ID, &min_fee);
+
+    let new_energy = 5_000u64;
+    let (result, proposal_id) = gov_setup.propose(
+        &first_user_addr,
+        &min_fee,
+        &energy_factory_addr,
+        b"setUserEnergy",
+        vec![
+            no_energy_user_addr.to_vec(),
+            new_energy.to_be_bytes().to_vec(),
+            vec![],
+        ],
+    );
+    result.assert_ok();
+
+    gov_setup.increment_block_nonce(VOTING_DELAY_BLOCKS);
+    gov_setup.up_vote(&first_user_addr, proposal_id).assert_ok();
+    gov_setup
+        .up_vote(&second_user_addr, proposal_id)
+        .assert_ok();
+
+    // cannot queue or execute while voting
+    gov_setup
+        .queue(&first_user_addr, proposal_id)
+        .assert_user_error("Only succeeded proposals can be queued");
+    gov_setup
+        .execute(&first_user_addr, proposal_id)
+        .assert_user_error("Proposal is not queued");
+
+    gov_setup.increment_block_nonce(VOTING_PERIOD_BLOCKS);
+    gov_setup.queue(&second_user_addr, proposal_id).assert_ok();
+
+    gov_setup
+        .b_mock
+        .execute_query(&gov_setup.gov_wrapper, |sc| {
+            assert!(sc.get_proposal_status(proposal_id) == GovernanceProposalStatus::Queued);
+        })
+        .assert_ok();
+
+    // already queued
+    gov_setup
+        .queue(&second_user_addr, proposal_id)
+        .assert_user_error("Only succeeded proposals can be queued");
+
+    gov_setup
+        .execute(&second_user_addr, proposal_id)
+        .assert_user_error("Proposal timelock has not ended yet");
+
+    gov_setup.increment_block_nonce(DEFAULT_LOCK_TIME_AFTER_VOTING_ENDS);
+    gov_setup
+        .execute(&second_user_addr, proposal_id)
+        .assert_ok();
+
+    gov_setup
+        .b_mock
+        .execute_query(&gov_setup.gov_wrapper, |sc| {
+            assert!(sc.get_proposal_status(proposal_id) == GovernanceProposalStatus::Executed);
+            assert_eq!(
+                sc.get_energy_amount(&managed_address!(&no_energy_user_addr)),
+                managed_biguint!(new_energy)
+            );
+        })
+        .assert_ok();
+
+    // cannot execute twice
+    gov_setup
+        .execute(&second_user_addr, proposal_id)
+        .assert_user_error("Proposal is not queued");
+
+    // the proposer can still get the fee back
+    gov_setup
+        .withdraw_after_defeated(&first_user_addr, proposal_id)
+        .assert_ok();
+    gov_setup
+        .b_mock
+        .check_esdt_balance(&first_user_addr, MEX_TOKEN_ID, &min_fee);
+}
+
+#[test]
+fn gov_queued_proposal_expired_test() {
+    let mut gov_setup = GovSetup::new(governance_v2::contract_obj);
+
+    let first_user_addr = gov_setup.first_user.clone();
+    let second_user_addr = gov_setup.second_user.clone();
+    let sc_addr = gov_setup.gov_wrapper.address_ref().clone();
+    let min_fee = rust_biguint!(MIN_FEE_FOR_PROPOSE) * DECIMALS_CONST;
+    gov_setup
+        .b_mock
+        .set_esdt_balance(&first_user_addr, MEX_TOKEN_ID, &min_fee);
+
+    let (result, proposal_id) = gov_setup.propose(
+        &first_user_addr,
+        &min_fee,
+        &sc_addr,
+        b"changeTODO",
+        vec![1_000u64.to_be_bytes().to_vec()],
+    );
+    result.assert_ok();
+
+    gov_setup.increment_block_nonce(VOTING_DELAY_BLOCKS);
+    gov_setup.up_vote(&first_user_addr, proposal_id).assert_ok();
+    gov_setup
+        .up_vote(&second_user_addr, proposal_id)
+        .assert_ok();
+
+    gov_setup.increment_block_nonce(VOTING_PERIOD_BLOCKS);
+    gov_setup.queue(&first_user_addr, proposal_id).assert_ok();
+
+    gov_setup.increment_block_nonce(DEFAULT_LOCK_TIME_AFTER_VOTING_ENDS + DEFAULT_GRACE_PERIOD);
+    gov_setup
+        .b_mock
+        .execute_query(&gov_setup.gov_wrapper, |sc| {
+            assert!(sc.get_proposal_status(proposal_id) == GovernanceProposalStatus::Expired);
+        })
+        .assert_ok();
+
+    gov_setup
+        .execute(&first_user_addr, proposal_id)
+        .assert_user_error("Proposal is not queued");
+}
+
+#[test]
+fn gov_vote_with_energy_snapshot_test() {
+    let mut gov_setup = GovSetup::new(governance_v2::contract_obj);
+
+    let first_user_addr = gov_setup.first_user.clone();
+    let no_energy_user_addr = gov_setup.no_energy_user.clone();
+    let sc_addr = gov_setup.gov_wrapper.address_ref().clone();
+    let min_fee = rust_biguint!(MIN_FEE_FOR_PROPOSE) * DECIMALS_CONST;
+    gov_setup
+        .b_mock
+        .set_esdt_balance(&first_user_addr, MEX_TOKEN_ID, &min_fee);
+
+    gov_setup.complete_legacy_energy_checkpoints().assert_ok();
+
+    let snapshot_epoch = 5;
+    gov_setup.b_mock.set_block_epoch(snapshot_epoch);
+
+    let (result, proposal_id) = gov_setup.propose(
+        &first_user_addr,
+        &min_fee,
+        &sc_addr,
+        b"changeTODO",
+        vec![1_000u64.to_be_bytes().to_vec()],
+    );
+    result.assert_ok();
+
+    gov_setup
+        .b_mock
+        .execute_query(&gov_setup.gov_wrapper, |sc| {
+            assert_eq!(
+                sc.proposal_energy_snapshot_epoch(proposal_id).get(),
+                snapshot_epoch
+            );
+            assert_eq!(
+                sc.proposals().get(proposal_id).total_quorum,
+                managed_biguint!(3 * USER_ENERGY + 210_000)
+            );
+        })
+        .assert_ok();
+
+    gov_setup.increment_block_nonce(VOTING_DELAY_BLOCKS);
+    gov_setup.up_vote(&first_user_addr, proposal_id).assert_ok();
+
+    // first user moves the energy to another account after voting
+    gov_setup.set_user_energy(&first_user_addr, 0).assert_ok();
+    gov_setup
+        .set_user_energy(&no_energy_user_addr, USER_ENERGY)
+        .assert_ok();
+
+    gov_setup
+        .up_vote(&no_energy_user_addr, proposal_id)
+        .assert_user_error("No energy at proposal snapshot epoch");
+
+    // still not counted in later epochs
+    gov_setup.b_mock.set_block_epoch(snapshot_epoch + 1);
+    gov_setup
+        .up_vote(&no_energy_user_addr, proposal_id)
+        .assert_user_error("No energy at proposal snapshot epoch");
+
+    gov_setup
+        .b_mock
+        .execute_query(&gov_setup.gov_wrapper, |sc| {
+            assert_eq!(
+                sc.get_voting_energy(&managed_address!(&first_user_addr), proposal_id),
+                managed_biguint!(USER_ENERGY)
+            );
+            assert_eq!(
+                sc.get_voting_energy(&managed_address!(&no_energy_user_addr), proposal_id),
+                managed_biguint!(0)
+            );
+            assert_eq!(sc.proposal_votes(proposal_id).get().quorum, USER_ENERGY);
+        })
+        .assert_ok();
+}
+
+#[test]
+fn gov_propose_before_total_energy_complete_test() {
+    let mut gov_setup = GovSetup::new(governance_v2::contract_obj);
+
+    let first_user_addr = gov_setup.first_user.clone();
+    let second_user_addr = gov_setup.second_user.clone();
+    let sc_addr = gov_setup.gov_wrapper.address_ref().clone();
+    let min_fee = rust_biguint!(MIN_FEE_FOR_PROPOSE) * DECIMALS_CONST;
+    gov_setup
+        .b_mock
+        .set_esdt_balance(&second_user_addr, MEX_TOKEN_ID, &(&min_fee * 2u64));
+
+    gov_setup.b_mock.set_block_epoch(3);
+    gov_setup.set_user_energy(&first_user_addr, 0).assert_ok();
+
+    // the legacy entries are not all checkpointed, so the fees collector total is used
+    gov_setup.b_mock.set_block_epoch(5);
+    let (result, proposal_id) = gov_setup.propose(
+        &second_user_addr,
+        &min_fee,
+        &sc_addr,
+        b"changeTODO",
+        vec![1_000u64.to_be_bytes().to_vec()],
+    );
+    result.assert_ok();
+
+    gov_setup
+        .b_mock
+        .execute_query(&gov_setup.gov_wrapper, |sc| {
+            assert_eq!(
+                sc.proposals().get(proposal_id).total_quorum,
+                managed_biguint!(3 * USER_ENERGY + 210_000)
+            );
+        })
+        .assert_ok();
+
+    gov_setup.complete_legacy_energy_checkpoints().assert_ok();
+
+    // complete starting with the next epoch
+    gov_setup.b_mock.set_block_epoch(6);
+    let (result, proposal_id) = gov_setup.propose(
+        &second_user_addr,
+        &min_fee,
+        &sc_addr,
+        b"changeTODO",
+        vec![1_000u64.to_be_bytes().to_vec()],
+    );
+    result.assert_ok();
+
+    gov_setup
+        .b_mock
+        .execute_query(&gov_setup.gov_wrapper, |sc| {
+            assert_eq!(
+                sc.proposals().get(proposal_id).total_quorum,
+                managed_biguint!(2 * USER_ENERGY + 210_000)
+            );
+        })
+        .assert_ok();
+}
@@ -45,6 +45,10 @@ where
     pub third_user: Address,
     pub no_energy_user: Address,
     pub gov_wrapper: ContractObjWrapper<governance_v2::ContractObj<DebugApi>, GovBuilder>,
+    pub energy_factory_wrapper: ContractObjWrapper<
+        energy_factory_mock::ContractObj<DebugApi>,
+        fn() -> energy_factory_mock::ContractObj<DebugApi>,
+    >,
     pub current_block: u64,
 }
 
@@ -65,7 +69,7 @@ where
         let energy_factory_wrapper = b_mock.create_sc_account(
             &rust_zero,
             Some(&owner),
-            energy_factory_mock::contract_obj,
+            energy_factory_mock::contract_obj as fn() -> energy_factory_mock::ContractObj<DebugApi>,
             "energy factory path",
         );
 
@@ -104,6 +108,13 @@ where
                         0,
                         managed_biguint!(0),
                     ));
+
+                let mut users = MultiValueEncoded::new();
+                users.push(managed_address!(&first_user));
+                users.push(managed_address!(&second_user));
+                users.push(managed_address!(&third_user));
+                users.push(managed_address!(&no_energy_user));
+                sc.checkpoint_legacy_energy(users);
             })
             .assert_ok();
 
@@ -186,10 +197,22 @@ where
             third_user,
             no_energy_user,
             gov_wrapper,
+            energy_factory_wrapper,
             current_block: 0,
         }
     }
 
+    pub fn complete_legacy_energy_checkpoints(&mut self) -> TxResult {
+        self.b_mock.execute_tx(
+            &self.owner,
+            &self.energy_factory_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.complete_legacy_energy_checkpoints_endpoint();
+            },
+        )
+    }
+
     pub fn propose(
         &mut self,
         proposer: &Address,
@@ -257,6 +280,20 @@ where
             })
     }
 
+    pub fn queue(&mut self, caller: &Address, proposal_id: usize) -> TxResult {
+        self.b_mock
+            .execute_tx(caller, &self.gov_wrapper, &rust_biguint!(0), |sc| {
+                sc.queue(proposal_id);
+            })
+    }
+
+    pub fn execute(&mut self, caller: &Address, proposal_id: usize) -> TxResult {
+        self.b_mock
+            .execute_tx(caller, &self.gov_wrapper, &rust_biguint!(0), |sc| {
+                sc.execute(proposal_id);
+            })
+    }
+
     pub fn withdraw_after_defeated(&mut self, caller: &Address, proposal_id: usize) -> TxResult {
         self.b_mock
             .execute_tx(caller, &self.gov_wrapper, &rust_biguint!(0), |sc| {
@@ -286,6 +323,21 @@ where
             })
     }
 
+    pub fn set_user_energy(&mut self, user: &Address, energy: u64) -> TxResult {
+        self.b_mock.execute_tx(
+            &self.owner,
+            &self.energy_factory_wrapper,
+            &rust_biguint!(0),
+            |sc| {
+                sc.set_user_energy(
+                    managed_address!(user),
+                    managed_biguint!(energy),
+                    managed_biguint!(0),
+                );
+            },
+        )
+    }
+
     pub fn check_proposal_id_consistency(
         &mut self,
         caller: &Address,
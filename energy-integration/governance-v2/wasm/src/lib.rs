@@ -6,9 +6,9 @@
 
 // Init:                                 1
 // Upgrade:                              1
-// Endpoints:                           29
+// Endpoints:                           39
 // Async Callback (empty):               1
-// Total number of exported functions:  32
+// Total number of exported functions:  42
 
 #![no_std]
 
@@ -23,6 +23,8 @@ multiversx_sc_wasm_adapter::endpoints! {
         propose => propose
         vote => vote
         cancel => cancel
+        queue => queue
+        execute => execute
         withdrawDeposit => withdraw_deposit
         changeMinEnergyForProposal => change_min_energy_for_propose
         changeMinFeeForProposal => change_min_fee_for_propose
@@ -30,17 +32,25 @@ multiversx_sc_wasm_adapter::endpoints! {
         changeWithdrawPercentage => change_withdraw_percentage
         changeVotingDelayInBlocks => change_voting_delay_in_blocks
         changeVotingPeriodInBlocks => change_voting_period_in_blocks
+        changeLockTimeAfterVotingEndsInBlocks => change_lock_time_after_voting_ends_in_blocks
+        changeGracePeriodInBlocks => change_grace_period_in_blocks
         getMinEnergyForPropose => min_energy_for_propose
         getMinFeeForPropose => min_fee_for_propose
         getQuorum => quorum_percentage
         getVotingDelayInBlocks => voting_delay_in_blocks
         getVotingPeriodInBlocks => voting_period_in_blocks
+        getLockTimeAfterVotingEndsInBlocks => lock_time_after_voting_ends_in_blocks
+        getGracePeriodInBlocks => grace_period_in_blocks
         getFeeTokenId => fee_token_id
         getWithdrawPercentageDefeated => withdraw_percentage_defeated
         getProposals => proposals
         getUserVotedProposals => user_voted_proposals
         getProposalVotes => proposal_votes
+        getProposalTimelock => proposal_timelock
+        isProposalExecuted => proposal_executed
+        getProposalEnergySnapshotEpoch => proposal_energy_snapshot_epoch
         getProposalStatus => get_proposal_status
+        getUserVotingEnergy => get_voting_energy
         changeFeesCollectorAddress => change_fees_collector_address
         getFeesCollectorAddress => fees_collector_address
         setEnergyFactoryAddress => set_energy_factory_address
@@ -2,7 +2,7 @@ multiversx_sc::imports!();
 
 use farm::{
     base_functions::{ClaimRewardsResultType, DoubleMultiPayment},
-    EnterFarmResultType, ExitFarmWithPartialPosResultType,
+    AllRewardsResultType, EnterFarmResultType, ExitFarmWithPartialPosResultType,
 };
 use farm_staking::{
     claim_stake_farm_rewards::ProxyTrait as _, stake_farm::ProxyTrait as _,
@@ -32,16 +32,18 @@ pub trait ExternalContractsInteractionsModule:
         lp_farm_token_amount: BigUint,
     ) -> LpFarmClaimRewardsResult<Self::Api> {
         let lp_farm_address = self.lp_farm_address().get();
-        let lp_farm_result: ClaimRewardsResultType<Self::Api> = self
+        let lp_farm_result: AllRewardsResultType<Self::Api> = self
             .lp_farm_proxy_obj(lp_farm_address)
             .claim_rewards_endpoint(OptionalValue::Some(orig_caller))
             .with_esdt_transfer((lp_farm_token_id, lp_farm_token_nonce, lp_farm_token_amount))
             .execute_on_dest_context();
-        let (new_lp_farm_tokens, lp_farm_rewards) = lp_farm_result.into_tuple();
+        let (new_lp_farm_tokens, lp_farm_rewards, lp_farm_additional_rewards) =
+            lp_farm_result.into_tuple();
 
         LpFarmClaimRewardsResult {
             new_lp_farm_tokens,
             lp_farm_rewards,
+            lp_farm_additional_rewards: lp_farm_additional_rewards.to_vec(),
         }
     }
 
@@ -53,16 +55,18 @@ pub trait ExternalContractsInteractionsModule:
     ) -> LpFarmExitResult<Self::Api> {
         let lp_farm_token_id = self.lp_farm_token_id().get();
         let lp_farm_address = self.lp_farm_address().get();
-        let exit_farm_result: ExitFarmWithPartialPosResultType<Self::Api> = self
+        let exit_farm_result: AllRewardsResultType<Self::Api> = self
             .lp_farm_proxy_obj(lp_farm_address)
             .exit_farm_endpoint(OptionalValue::Some(orig_caller))
             .with_esdt_transfer((lp_farm_token_id, lp_farm_token_nonce, lp_farm_token_amount))
             .execute_on_dest_context();
-        let (lp_tokens, lp_farm_rewards) = exit_farm_result.into_tuple();
+        let (lp_tokens, lp_farm_rewards, lp_farm_additional_rewards) =
+            exit_farm_result.into_tuple();
 
         LpFarmExitResult {
             lp_tokens,
             lp_farm_rewards,
+            lp_farm_additional_rewards: lp_farm_additional_rewards.to_vec(),
         }
     }
 
@@ -89,6 +89,7 @@ pub trait ProxyClaimModule:
             lp_farm_rewards,
             staking_farm_rewards,
             new_dual_yield_tokens,
+            lp_farm_additional_rewards: lp_farm_claim_rewards_result.lp_farm_additional_rewards,
         }
     }
 }
@@ -53,6 +53,10 @@ pub trait ProxyExternalInteractionsModule:
 
         self.send_payment_non_zero(&original_owner, &claim_result.lp_farm_rewards);
         self.send_payment_non_zero(&original_owner, &claim_result.staking_farm_rewards);
+        self.send_multiple_tokens_if_not_zero(
+            &original_owner,
+            &claim_result.lp_farm_additional_rewards,
+        );
         self.send_payment_non_zero(&caller, &claim_result.new_dual_yield_tokens);
 
         claim_result
@@ -59,6 +59,7 @@ pub trait ProxyUnstakeModule:
             lp_farm_rewards: lp_farm_exit_result.lp_farm_rewards,
             staking_rewards: staking_farm_exit_result.staking_rewards,
             unbond_staking_farm_token: staking_farm_exit_result.unbond_staking_farm_token,
+            lp_farm_additional_rewards: lp_farm_exit_result.lp_farm_additional_rewards,
         };
 
         dual_yield_token_mapper.nft_burn(payment.token_nonce, &payment.amount);
@@ -1101,7 +1101,11 @@ fn total_farm_position_after_claim_and_exit_metastaking_test() {
             0,
             &rust_biguint!(100_000_000u64),
             |sc| {
-                sc.swap_tokens_fixed_input(managed_token_id!(RIDE_TOKEN_ID), managed_biguint!(1));
+                sc.swap_tokens_fixed_input(
+                    managed_token_id!(RIDE_TOKEN_ID),
+                    managed_biguint!(1),
+                    OptionalValue::None,
+                );
             },
         )
         .assert_ok();
@@ -1117,7 +1121,11 @@ fn total_farm_position_after_claim_and_exit_metastaking_test() {
             0,
             &rust_biguint!(100_000_000u64),
             |sc| {
-                sc.swap_tokens_fixed_input(managed_token_id!(RIDE_TOKEN_ID), managed_biguint!(1));
+                sc.swap_tokens_fixed_input(
+                    managed_token_id!(RIDE_TOKEN_ID),
+                    managed_biguint!(1),
+                    OptionalValue::None,
+                );
             },
         )
         .assert_ok();
@@ -1133,7 +1141,11 @@ fn total_farm_position_after_claim_and_exit_metastaking_test() {
             0,
             &rust_biguint!(100_000_000u64),
             |sc| {
-                sc.swap_tokens_fixed_input(managed_token_id!(RIDE_TOKEN_ID), managed_biguint!(1));
+                sc.swap_tokens_fixed_input(
+                    managed_token_id!(RIDE_TOKEN_ID),
+                    managed_biguint!(1),
+                    OptionalValue::None,
+                );
             },
         )
         .assert_ok();
@@ -525,7 +525,7 @@ where
                 farm_token_nonce,
                 &rust_biguint!(farm_token_amount),
                 |sc| {
-                    let (_lp_tokens, _boosted_rewards_payment) =
+                    let (_lp_tokens, _boosted_rewards_payment, _) =
                         sc.exit_farm_endpoint(OptionalValue::None).into_tuple();
                 },
             )
@@ -548,7 +548,7 @@ where
                 farm_token_nonce,
                 &rust_biguint!(farm_token_amount),
                 |sc| {
-                    let (output_farm_token, boosted_rewards_payment) =
+                    let (output_farm_token, boosted_rewards_payment, _) =
                         sc.claim_rewards_endpoint(OptionalValue::None).into_tuple();
                     assert_eq!(output_farm_token.amount, farm_token_amount);
                     assert_eq!(boosted_rewards_payment.amount, expected_lp_farm_rewards);
@@ -730,11 +730,14 @@ where
                 &self.energy_factory_wrapper,
                 &rust_biguint!(0),
                 |sc| {
-                    sc.user_energy(&managed_address!(user)).set(&Energy::new(
-                        BigInt::from(managed_biguint!(energy)),
-                        last_update_epoch,
-                        managed_biguint!(locked_tokens),
-                    ));
+                    sc.set_energy_entry(
+                        &managed_address!(user),
+                        Energy::new(
+                            BigInt::from(managed_biguint!(energy)),
+                            last_update_epoch,
+                            managed_biguint!(locked_tokens),
+                        ),
+                    );
                 },
             )
             .assert_ok();
@@ -7,6 +7,7 @@ use contexts::storage_cache::StorageCache;
 use farm_base_impl::base_traits_impl::FarmContract;
 use multiversx_sc_modules::transfer_role_proxy::PaymentsVec;
 
+use crate::custom_rewards::{BLOCKS_IN_YEAR, SECONDS_IN_YEAR};
 use crate::token_attributes::StakingFarmTokenAttributes;
 
 pub trait FarmStakingTraits:
@@ -82,10 +83,40 @@ where
     ) {
     }
 
-    fn mint_per_block_rewards(
+    fn mint_per_second_rewards(
         sc: &Self::FarmSc,
         _token_id: &TokenIdentifier<<Self::FarmSc as ContractBase>::Api>,
     ) -> BigUint<<Self::FarmSc as ContractBase>::Api> {
+        let current_timestamp = sc.blockchain().get_block_timestamp();
+        let last_reward_timestamp = sc.last_reward_timestamp().get();
+
+        if current_timestamp <= last_reward_timestamp {
+            return BigUint::zero();
+        }
+
+        let extra_rewards_unbounded =
+            Self::calculate_per_second_rewards(sc, current_timestamp, last_reward_timestamp);
+
+        let farm_token_supply = sc.farm_token_supply().get();
+        let extra_rewards_apr_bounded_per_second =
+            sc.get_amount_apr_bounded(&farm_token_supply, SECONDS_IN_YEAR);
+
+        let timestamp_diff = current_timestamp - last_reward_timestamp;
+        let extra_rewards_apr_bounded = extra_rewards_apr_bounded_per_second * timestamp_diff;
+
+        sc.last_reward_timestamp().set(current_timestamp);
+
+        core::cmp::min(extra_rewards_unbounded, extra_rewards_apr_bounded)
+    }
+
+    fn mint_per_block_rewards(
+        sc: &Self::FarmSc,
+        token_id: &TokenIdentifier<<Self::FarmSc as ContractBase>::Api>,
+    ) -> BigUint<<Self::FarmSc as ContractBase>::Api> {
+        if sc.is_per_second_emission() {
+            return Self::mint_per_second_rewards(sc, token_id);
+        }
+
         let current_block_nonce = sc.blockchain().get_block_nonce();
         let last_reward_nonce = sc.last_reward_block_nonce().get();
 
@@ -97,7 +128,8 @@ where
             Self::calculate_per_block_rewards(sc, current_block_nonce, last_reward_nonce);
 
         let farm_token_supply = sc.farm_token_supply().get();
-        let extra_rewards_apr_bounded_per_block = sc.get_amount_apr_bounded(&farm_token_supply);
+        let extra_rewards_apr_bounded_per_block =
+            sc.get_amount_apr_bounded(&farm_token_supply, BLOCKS_IN_YEAR);
 
         let block_nonce_diff = current_block_nonce - last_reward_nonce;
         let extra_rewards_apr_bounded = extra_rewards_apr_bounded_per_block * block_nonce_diff;
@@ -4,11 +4,13 @@ multiversx_sc::derive_imports!();
 use common_structs::Epoch;
 use contexts::storage_cache::StorageCache;
 use farm_base_impl::base_traits_impl::FarmContract;
+use rewards::emission_schedule::EmissionSegment;
 
 use crate::base_impl_wrapper::FarmStakingWrapper;
 
 pub const MAX_PERCENT: u64 = 10_000;
-pub const BLOCKS_IN_YEAR: u64 = 31_536_000 / 6; // seconds_in_year / 6_seconds_per_block
+pub const SECONDS_IN_YEAR: u64 = 31_536_000;
+pub const BLOCKS_IN_YEAR: u64 = SECONDS_IN_YEAR / 6; // seconds_in_year / 6_seconds_per_block
 pub const MAX_MIN_UNBOND_EPOCHS: u64 = 30;
 pub const WITHDRAW_AMOUNT_TOO_HIGH: &str =
     "Withdraw amount is higher than the remaining uncollected rewards!";
@@ -89,12 +91,50 @@ pub trait CustomRewardsModule:
     fn set_per_block_rewards(&self, per_block_amount: BigUint) {
         self.require_caller_has_admin_permissions();
         require!(per_block_amount != 0, "Amount cannot be zero");
+        require!(
+            !self.is_per_second_emission(),
+            "Per block rewards are no longer used"
+        );
 
         let mut storage_cache = StorageCache::new(self);
         FarmStakingWrapper::<Self>::generate_aggregated_rewards(self, &mut storage_cache);
         self.per_block_reward_amount().set(&per_block_amount);
     }
 
+    /// Switches the emission from per block to per second, converting the current rate
+    /// with the given block time. The APR bound is then also computed per second.
+    #[endpoint(migrateToPerSecondRewards)]
+    fn migrate_to_per_second_rewards_endpoint(&self, seconds_per_block: u64) {
+        self.require_caller_has_admin_permissions();
+
+        let mut storage_cache = StorageCache::new(self);
+        FarmStakingWrapper::<Self>::generate_aggregated_rewards(self, &mut storage_cache);
+        self.migrate_to_per_second_rewards(seconds_per_block);
+    }
+
+    #[endpoint(setPerSecondRewardAmount)]
+    fn set_per_second_rewards_endpoint(&self, per_second_amount: BigUint) {
+        self.require_caller_has_admin_permissions();
+
+        let mut storage_cache = StorageCache::new(self);
+        FarmStakingWrapper::<Self>::generate_aggregated_rewards(self, &mut storage_cache);
+        self.set_per_second_rewards(per_second_amount);
+    }
+
+    /// Replaces the emission schedule. The rewards are still bounded by the max APR
+    /// and the remaining capacity.
+    #[endpoint(setEmissionSchedule)]
+    fn set_emission_schedule_endpoint(
+        &self,
+        segments: MultiValueEncoded<EmissionSegment<Self::Api>>,
+    ) {
+        self.require_caller_has_admin_permissions();
+
+        let mut storage_cache = StorageCache::new(self);
+        FarmStakingWrapper::<Self>::generate_aggregated_rewards(self, &mut storage_cache);
+        self.set_emission_schedule(segments);
+    }
+
     #[endpoint(setMaxApr)]
     fn set_max_apr(&self, max_apr: BigUint) {
         self.require_caller_has_admin_permissions();
@@ -120,9 +160,10 @@ pub trait CustomRewardsModule:
         self.min_unbond_epochs().set(min_unbond_epochs);
     }
 
-    fn get_amount_apr_bounded(&self, amount: &BigUint) -> BigUint {
+    /// The maximum rewards of `amount` for one period, i.e. one block or one second.
+    fn get_amount_apr_bounded(&self, amount: &BigUint, periods_in_year: u64) -> BigUint {
         let max_apr = self.max_annual_percentage_rewards().get();
-        amount * &max_apr / MAX_PERCENT / BLOCKS_IN_YEAR
+        amount * &max_apr / MAX_PERCENT / periods_in_year
     }
 
     #[endpoint(startProduceRewards)]
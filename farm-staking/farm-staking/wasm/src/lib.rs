@@ -6,9 +6,9 @@
 
 // Init:                                 1
 // Upgrade:                              1
-// Endpoints:                           72
+// Endpoints:                           79
 // Async Callback:                       1
-// Total number of exported functions:  75
+// Total number of exported functions:  82
 
 #![no_std]
 
@@ -27,6 +27,9 @@ multiversx_sc_wasm_adapter::endpoints! {
         withdrawRewards => withdraw_rewards
         endProduceRewards => end_produce_rewards
         setPerBlockRewardAmount => set_per_block_rewards
+        migrateToPerSecondRewards => migrate_to_per_second_rewards_endpoint
+        setPerSecondRewardAmount => set_per_second_rewards_endpoint
+        setEmissionSchedule => set_emission_schedule_endpoint
         setMaxApr => set_max_apr
         setMinUnbondEpochs => set_min_unbond_epochs_endpoint
         startProduceRewards => start_produce_rewards_endpoint
@@ -34,11 +37,15 @@ multiversx_sc_wasm_adapter::endpoints! {
         getRewardCapacity => reward_capacity
         getAnnualPercentageRewards => max_annual_percentage_rewards
         getMinUnbondEpochs => min_unbond_epochs
+        getUpcomingEmissionSchedule => get_upcoming_emission_schedule
+        getProjectedEmissions => get_projected_emissions
         getRewardPerShare => reward_per_share
         getRewardReserve => reward_reserve
         getFarmingTokenId => farming_token_id
         getRewardTokenId => reward_token_id
         getPerBlockRewardAmount => per_block_reward_amount
+        getPerSecondRewardAmount => per_second_reward_amount
+        getLastRewardTimestamp => last_reward_timestamp
         getLastRewardBlockNonce => last_reward_block_nonce
         getDivisionSafetyConstant => division_safety_constant
         getUserTotalFarmPosition => user_total_farm_position
@@ -88,6 +88,15 @@ impl<M: ManagedTypeApi> Energy<M> {
         self.amount -= BigInt::from(energy_amount);
     }
 
+    /// Replaces the contribution of `old_entry` with the one of `new_entry`.
+    /// Used for aggregated entries, like the total energy.
+    pub fn replace_entry(&mut self, old_entry: &Self, new_entry: &Self) {
+        self.amount += &new_entry.amount;
+        self.amount -= &old_entry.amount;
+        self.total_locked_tokens += &new_entry.total_locked_tokens;
+        self.total_locked_tokens -= &old_entry.total_locked_tokens;
+    }
+
     pub fn add_after_token_lock(
         &mut self,
         lock_amount: &BigUint<M>,
@@ -164,10 +173,29 @@ impl<M: ManagedTypeApi> Energy<M> {
     pub fn get_energy_amount_raw(&self) -> &BigInt<M> {
         &self.amount
     }
+
+    /// The first epoch in which the entry has no positive energy left,
+    /// or None if its energy never runs out
+    pub fn get_energy_end_epoch(&self) -> Option<Epoch> {
+        if self.amount <= 0 {
+            return Some(self.last_update_epoch);
+        }
+        if self.total_locked_tokens == 0 {
+            return None;
+        }
+
+        let epochs_left = (self.amount.magnitude() + &self.total_locked_tokens - 1u64)
+            / &self.total_locked_tokens;
+        let epochs_left = epochs_left.to_u64().unwrap_or(Epoch::MAX);
+
+        Some(self.last_update_epoch.saturating_add(epochs_left))
+    }
 }
 
 #[multiversx_sc::module]
-pub trait EnergyModule: crate::events::EventsModule {
+pub trait EnergyModule:
+    crate::events::EventsModule + crate::energy_checkpoints::EnergyCheckpointsModule
+{
     fn update_energy<T, F: FnOnce(&mut Energy<Self::Api>) -> T>(
         &self,
         user: &ManagedAddress,
@@ -182,10 +210,39 @@ pub trait EnergyModule: crate::events::EventsModule {
 
     fn set_energy_entry(&self, user: &ManagedAddress, new_energy: Energy<Self::Api>) {
         let prev_energy = self.get_updated_energy_entry_for_user(user);
-        self.user_energy(user).set(&new_energy);
+        let energy_mapper = self.user_energy(user);
+        let old_stored_entry = if !energy_mapper.is_empty() {
+            Some(energy_mapper.get())
+        } else {
+            None
+        };
+        self.checkpoint_energy_update(user, old_stored_entry, &new_energy);
+
+        energy_mapper.set(&new_energy);
         self.emit_energy_updated_event(user, prev_energy, new_energy);
     }
 
+    /// Adds the energy of users which had no update since checkpoints were introduced
+    /// to the total energy. The total is complete once all of them were processed.
+    #[only_owner]
+    #[endpoint(checkpointLegacyEnergy)]
+    fn checkpoint_legacy_energy(&self, users: MultiValueEncoded<ManagedAddress>) {
+        for user in users {
+            let energy_mapper = self.user_energy(&user);
+            if !energy_mapper.is_empty() {
+                self.checkpoint_legacy_energy_entry(&user, energy_mapper.get());
+            }
+        }
+    }
+
+    /// Called once all the legacy users were passed to checkpointLegacyEnergy.
+    /// The total energy is complete starting with the next epoch.
+    #[only_owner]
+    #[endpoint(completeLegacyEnergyCheckpoints)]
+    fn complete_legacy_energy_checkpoints_endpoint(&self) {
+        self.complete_legacy_energy_checkpoints();
+    }
+
     #[view(getEnergyEntryForUser)]
     fn get_updated_energy_entry_for_user(&self, user: &ManagedAddress) -> Energy<Self::Api> {
         let current_epoch = self.blockchain().get_block_epoch();
@@ -207,6 +264,31 @@ pub trait EnergyModule: crate::events::EventsModule {
         energy.get_energy_amount()
     }
 
+    /// Returns the energy the user had when the given epoch started.
+    /// Updates done during `epoch` itself are not taken into account.
+    #[view(getEnergyEntryForUserAtEpoch)]
+    fn get_energy_entry_for_user_at_epoch(
+        &self,
+        user: &ManagedAddress,
+        epoch: Epoch,
+    ) -> Energy<Self::Api> {
+        let energy_mapper = self.user_energy(user);
+        let stored_entry = if !energy_mapper.is_empty() {
+            Some(energy_mapper.get())
+        } else {
+            None
+        };
+
+        self.get_user_energy_entry_at_epoch(user, stored_entry, epoch)
+    }
+
+    #[view(getEnergyAmountForUserAtEpoch)]
+    fn get_energy_amount_for_user_at_epoch(&self, user: ManagedAddress, epoch: Epoch) -> BigUint {
+        let energy = self.get_energy_entry_for_user_at_epoch(&user, epoch);
+
+        energy.get_energy_amount()
+    }
+
     #[storage_mapper("userEnergy")]
     fn user_energy(&self, user: &ManagedAddress) -> SingleValueMapper<Energy<Self::Api>>;
 }
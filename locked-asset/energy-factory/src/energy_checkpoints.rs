@@ -0,0 +1,345 @@
+multiversx_sc::imports!();
+
+use common_structs::Epoch;
+
+use crate::energy::Energy;
+
+/// Keeps the last energy entry of every epoch in which an update happened,
+/// both per user and for the whole system.
+///
+/// An entry stored for epoch `E` is the state at the end of `E`, so the energy
+/// "at epoch `E`" is taken from the last checkpoint strictly before `E`.
+/// This way, updates done during the queried epoch can never affect the result.
+///
+/// Like the energy of a user, which is never negative, the total only includes the
+/// entries with energy left. Each of them is removed from the total in the epoch in which
+/// its energy runs out, as scheduled in `totalEnergyRemoval`. Applying the removals takes
+/// a storage read for each epoch without a total energy checkpoint.
+#[multiversx_sc::module]
+pub trait EnergyCheckpointsModule {
+    /// `old_stored_entry` is the raw entry found in storage before the update, if any.
+    fn checkpoint_energy_update(
+        &self,
+        user: &ManagedAddress,
+        old_stored_entry: Option<Energy<Self::Api>>,
+        new_entry: &Energy<Self::Api>,
+    ) {
+        let current_epoch = self.blockchain().get_block_epoch();
+        let mut old_tracked_entry = Energy::new_zero_energy(current_epoch);
+        if let Some(old_entry) = old_stored_entry {
+            if self.user_energy_checkpoint_epochs(user).is_empty() {
+                // entry saved before checkpoints were introduced, not part of the total yet
+                let last_update_epoch = old_entry.get_last_update_epoch();
+                if last_update_epoch < current_epoch {
+                    self.save_user_energy_checkpoint(user, last_update_epoch, &old_entry);
+                }
+            } else {
+                old_tracked_entry = self.untrack_energy_entry(&old_entry);
+            }
+        }
+
+        self.save_user_energy_checkpoint(user, current_epoch, new_entry);
+
+        let new_tracked_entry = self.track_energy_entry(new_entry);
+        self.update_total_energy(&old_tracked_entry, &new_tracked_entry);
+    }
+
+    /// Adds an entry saved before checkpoints were introduced to the total energy,
+    /// without waiting for the user to update it. Users that are already tracked are skipped.
+    fn checkpoint_legacy_energy_entry(
+        &self,
+        user: &ManagedAddress,
+        stored_entry: Energy<Self::Api>,
+    ) {
+        if !self.user_energy_checkpoint_epochs(user).is_empty() {
+            return;
+        }
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        self.save_user_energy_checkpoint(user, stored_entry.get_last_update_epoch(), &stored_entry);
+
+        let new_tracked_entry = self.track_energy_entry(&stored_entry);
+        self.update_total_energy(&Energy::new_zero_energy(current_epoch), &new_tracked_entry);
+    }
+
+    /// Marks all the legacy entries as added to the total energy, which is then
+    /// complete for the epochs after the current one
+    fn complete_legacy_energy_checkpoints(&self) {
+        let current_epoch = self.blockchain().get_block_epoch();
+        let zero_entry = Energy::new_zero_energy(current_epoch);
+        self.update_total_energy(&zero_entry, &zero_entry);
+        self.total_energy_complete_epoch()
+            .set_if_empty(current_epoch + 1);
+    }
+
+    /// Returns the contribution of the entry to the total energy, depleted to the current
+    /// epoch, and schedules its removal. Entries without energy left are not tracked.
+    fn track_energy_entry(&self, entry: &Energy<Self::Api>) -> Energy<Self::Api> {
+        let current_epoch = self.blockchain().get_block_epoch();
+        let mut tracked_entry = entry.clone();
+        tracked_entry.deplete(current_epoch);
+        if tracked_entry.get_energy_amount() == 0 {
+            return Energy::new_zero_energy(current_epoch);
+        }
+
+        if let Some(end_epoch) = entry.get_energy_end_epoch() {
+            self.update_total_energy_removal(end_epoch, &Energy::new_zero_energy(end_epoch), entry);
+        }
+
+        tracked_entry
+    }
+
+    /// Returns the contribution of a previously tracked entry to the total energy,
+    /// depleted to the current epoch, and cancels its scheduled removal
+    fn untrack_energy_entry(&self, entry: &Energy<Self::Api>) -> Energy<Self::Api> {
+        let current_epoch = self.blockchain().get_block_epoch();
+        let mut tracked_entry = entry.clone();
+        tracked_entry.deplete(current_epoch);
+        if tracked_entry.get_energy_amount() == 0 {
+            // already removed from the total
+            return Energy::new_zero_energy(current_epoch);
+        }
+
+        if let Some(end_epoch) = entry.get_energy_end_epoch() {
+            self.update_total_energy_removal(end_epoch, entry, &Energy::new_zero_energy(end_epoch));
+        }
+
+        tracked_entry
+    }
+
+    fn update_total_energy_removal(
+        &self,
+        end_epoch: Epoch,
+        old_entry: &Energy<Self::Api>,
+        new_entry: &Energy<Self::Api>,
+    ) {
+        let mut old_removed_entry = old_entry.clone();
+        old_removed_entry.deplete(end_epoch);
+        let mut new_removed_entry = new_entry.clone();
+        new_removed_entry.deplete(end_epoch);
+
+        let removal_mapper = self.total_energy_removal(end_epoch);
+        let mut removal = if !removal_mapper.is_empty() {
+            removal_mapper.get()
+        } else {
+            Energy::new_zero_energy(end_epoch)
+        };
+        removal.replace_entry(&old_removed_entry, &new_removed_entry);
+        if removal == Energy::new_zero_energy(end_epoch) {
+            removal_mapper.clear();
+        } else {
+            removal_mapper.set(removal);
+        }
+    }
+
+    /// Depletes the total energy up to the given epoch, removing the entries
+    /// whose energy ran out in the meantime
+    fn deplete_total_energy(&self, total_energy: &mut Energy<Self::Api>, epoch: Epoch) {
+        for removal_epoch in total_energy.get_last_update_epoch() + 1..=epoch {
+            let removal_mapper = self.total_energy_removal(removal_epoch);
+            if removal_mapper.is_empty() {
+                continue;
+            }
+
+            total_energy.deplete(removal_epoch);
+            total_energy.replace_entry(
+                &removal_mapper.get(),
+                &Energy::new_zero_energy(removal_epoch),
+            );
+        }
+
+        total_energy.deplete(epoch);
+    }
+
+    /// Both entries must be depleted up to the current epoch
+    fn update_total_energy(
+        &self,
+        old_tracked_entry: &Energy<Self::Api>,
+        new_tracked_entry: &Energy<Self::Api>,
+    ) {
+        let current_epoch = self.blockchain().get_block_epoch();
+        let mut total_energy = self.get_updated_total_energy_entry();
+        total_energy.replace_entry(old_tracked_entry, new_tracked_entry);
+        self.total_energy().set(&total_energy);
+        self.save_checkpoint(
+            &mut self.total_energy_checkpoint_epochs(),
+            &self.total_energy_checkpoint(current_epoch),
+            current_epoch,
+            &total_energy,
+        );
+    }
+
+    fn save_user_energy_checkpoint(
+        &self,
+        user: &ManagedAddress,
+        epoch: Epoch,
+        entry: &Energy<Self::Api>,
+    ) {
+        self.save_checkpoint(
+            &mut self.user_energy_checkpoint_epochs(user),
+            &self.user_energy_checkpoint(user, epoch),
+            epoch,
+            entry,
+        );
+    }
+
+    fn save_checkpoint(
+        &self,
+        epochs_mapper: &mut VecMapper<Epoch>,
+        checkpoint_mapper: &SingleValueMapper<Energy<Self::Api>>,
+        epoch: Epoch,
+        entry: &Energy<Self::Api>,
+    ) {
+        let opt_last_epoch = self.get_last_checkpoint_epoch(epochs_mapper);
+        match opt_last_epoch {
+            Some(last_epoch) if last_epoch == epoch => {}
+            Some(last_epoch) if last_epoch > epoch => {
+                sc_panic!("Cannot checkpoint a past epoch");
+            }
+            _ => {
+                epochs_mapper.push(&epoch);
+            }
+        }
+
+        checkpoint_mapper.set(entry);
+    }
+
+    /// `stored_entry` is the current raw entry of the user, used for users
+    /// that had no energy update since checkpoints were introduced.
+    fn get_user_energy_entry_at_epoch(
+        &self,
+        user: &ManagedAddress,
+        stored_entry: Option<Energy<Self::Api>>,
+        epoch: Epoch,
+    ) -> Energy<Self::Api> {
+        let epochs_mapper = self.user_energy_checkpoint_epochs(user);
+        let opt_checkpoint = match self.find_checkpoint_epoch_before(&epochs_mapper, epoch) {
+            Some(checkpoint_epoch) => {
+                Some(self.user_energy_checkpoint(user, checkpoint_epoch).get())
+            }
+            None if epochs_mapper.is_empty() => {
+                stored_entry.filter(|entry| entry.get_last_update_epoch() < epoch)
+            }
+            None => None,
+        };
+
+        self.deplete_checkpoint(opt_checkpoint, epoch)
+    }
+
+    #[view(getTotalEnergyEntryAtEpoch)]
+    fn get_total_energy_entry_at_epoch(&self, epoch: Epoch) -> Energy<Self::Api> {
+        let epochs_mapper = self.total_energy_checkpoint_epochs();
+        match self.find_checkpoint_epoch_before(&epochs_mapper, epoch) {
+            Some(checkpoint_epoch) => {
+                let mut total_energy = self.total_energy_checkpoint(checkpoint_epoch).get();
+                self.deplete_total_energy(&mut total_energy, epoch);
+
+                total_energy
+            }
+            None => Energy::new_zero_energy(epoch),
+        }
+    }
+
+    #[view(getTotalEnergyAmountAtEpoch)]
+    fn get_total_energy_amount_at_epoch(&self, epoch: Epoch) -> BigUint {
+        self.get_total_energy_entry_at_epoch(epoch)
+            .get_energy_amount()
+    }
+
+    #[view(getTotalEnergyEntry)]
+    fn get_updated_total_energy_entry(&self) -> Energy<Self::Api> {
+        let current_epoch = self.blockchain().get_block_epoch();
+        let total_energy_mapper = self.total_energy();
+        if total_energy_mapper.is_empty() {
+            return Energy::new_zero_energy(current_epoch);
+        }
+
+        let mut total_energy = total_energy_mapper.get();
+        self.deplete_total_energy(&mut total_energy, current_epoch);
+
+        total_energy
+    }
+
+    #[view(isTotalEnergyCompleteAtEpoch)]
+    fn is_total_energy_complete_at_epoch(&self, epoch: Epoch) -> bool {
+        let complete_epoch_mapper = self.total_energy_complete_epoch();
+        !complete_epoch_mapper.is_empty() && complete_epoch_mapper.get() <= epoch
+    }
+
+    fn deplete_checkpoint(
+        &self,
+        opt_checkpoint: Option<Energy<Self::Api>>,
+        epoch: Epoch,
+    ) -> Energy<Self::Api> {
+        match opt_checkpoint {
+            Some(mut entry) => {
+                entry.deplete(epoch);
+                entry
+            }
+            None => Energy::new_zero_energy(epoch),
+        }
+    }
+
+    fn get_last_checkpoint_epoch(&self, epochs_mapper: &VecMapper<Epoch>) -> Option<Epoch> {
+        let len = epochs_mapper.len();
+        if len > 0 {
+            Some(epochs_mapper.get(len))
+        } else {
+            None
+        }
+    }
+
+    /// Binary search for the last checkpoint epoch strictly lower than `epoch`
+    fn find_checkpoint_epoch_before(
+        &self,
+        epochs_mapper: &VecMapper<Epoch>,
+        epoch: Epoch,
+    ) -> Option<Epoch> {
+        let mut low = 1;
+        let mut high = epochs_mapper.len();
+        let mut result = None;
+        while low <= high {
+            let mid = low + (high - low) / 2;
+            let mid_epoch = epochs_mapper.get(mid);
+            if mid_epoch < epoch {
+                result = Some(mid_epoch);
+                low = mid + 1;
+            } else {
+                high = mid - 1;
+            }
+        }
+
+        result
+    }
+
+    #[view(getUserEnergyCheckpointEpochs)]
+    #[storage_mapper("userEnergyCheckpointEpochs")]
+    fn user_energy_checkpoint_epochs(&self, user: &ManagedAddress) -> VecMapper<Epoch>;
+
+    #[storage_mapper("userEnergyCheckpoint")]
+    fn user_energy_checkpoint(
+        &self,
+        user: &ManagedAddress,
+        epoch: Epoch,
+    ) -> SingleValueMapper<Energy<Self::Api>>;
+
+    #[storage_mapper("totalEnergy")]
+    fn total_energy(&self) -> SingleValueMapper<Energy<Self::Api>>;
+
+    #[storage_mapper("totalEnergyCheckpointEpochs")]
+    fn total_energy_checkpoint_epochs(&self) -> VecMapper<Epoch>;
+
+    #[storage_mapper("totalEnergyCheckpoint")]
+    fn total_energy_checkpoint(&self, epoch: Epoch) -> SingleValueMapper<Energy<Self::Api>>;
+
+    /// The sum of the tracked entries whose energy runs out in the given epoch,
+    /// depleted up to it
+    #[storage_mapper("totalEnergyRemoval")]
+    fn total_energy_removal(&self, epoch: Epoch) -> SingleValueMapper<Energy<Self::Api>>;
+
+    /// The first epoch for which the total energy includes all the users,
+    /// i.e. the one after all the legacy entries were checkpointed
+    #[view(getTotalEnergyCompleteEpoch)]
+    #[storage_mapper("totalEnergyCompleteEpoch")]
+    fn total_energy_complete_epoch(&self) -> SingleValueMapper<Epoch>;
+}
@@ -3,6 +3,7 @@
 multiversx_sc::imports!();
 
 pub mod energy;
+pub mod energy_checkpoints;
 pub mod events;
 pub mod extend_lock;
 pub mod local_roles;
@@ -33,6 +34,7 @@ pub trait SimpleLockEnergy:
     + simple_lock::token_attributes::TokenAttributesModule
     + token_whitelist::TokenWhitelistModule
     + energy::EnergyModule
+    + energy_checkpoints::EnergyCheckpointsModule
     + lock_options::LockOptionsModule
     + lock_options_endpoints::LockOptionsEndpointsModule
     + unlock_with_penalty::UnlockWithPenaltyModule
@@ -90,6 +92,9 @@ pub trait SimpleLockEnergy:
         self.old_locked_asset_factory_address()
             .set_if_empty(&old_locked_asset_factory_address);
 
+        // a new deployment has no entries saved before checkpoints were introduced
+        self.complete_legacy_energy_checkpoints();
+
         self.set_paused(true);
     }
 
@@ -16,6 +16,7 @@ pub trait SimpleLockMigrationModule:
     + multiversx_sc_modules::default_issue_callbacks::DefaultIssueCallbacksModule
     + crate::token_whitelist::TokenWhitelistModule
     + crate::energy::EnergyModule
+    + crate::energy_checkpoints::EnergyCheckpointsModule
     + crate::events::EventsModule
     + crate::lock_options::LockOptionsModule
     + multiversx_sc_modules::pause::PauseModule
@@ -4,8 +4,8 @@
 pub mod unbond_sc_mock;
 
 use energy_factory::{
-    energy::EnergyModule, unlock_with_penalty::UnlockWithPenaltyModule, unstake::UnstakeModule,
-    SimpleLockEnergy,
+    energy::EnergyModule, energy_checkpoints::EnergyCheckpointsModule,
+    unlock_with_penalty::UnlockWithPenaltyModule, unstake::UnstakeModule, SimpleLockEnergy,
 };
 use multiversx_sc::{
     codec::multi_types::OptionalValue,
@@ -275,6 +275,31 @@ where
         result
     }
 
+    pub fn get_user_energy_at_epoch(&mut self, user: &Address, epoch: u64) -> num_bigint::BigUint {
+        let mut result = rust_biguint!(0);
+        self.b_mock
+            .execute_query(&self.sc_wrapper, |sc| {
+                let managed_result =
+                    sc.get_energy_amount_for_user_at_epoch(managed_address!(user), epoch);
+                result = to_rust_biguint(managed_result);
+            })
+            .assert_ok();
+
+        result
+    }
+
+    pub fn get_total_energy_at_epoch(&mut self, epoch: u64) -> num_bigint::BigUint {
+        let mut result = rust_biguint!(0);
+        self.b_mock
+            .execute_query(&self.sc_wrapper, |sc| {
+                let managed_result = sc.get_total_energy_amount_at_epoch(epoch);
+                result = to_rust_biguint(managed_result);
+            })
+            .assert_ok();
+
+        result
+    }
+
     pub fn get_user_energy_raw(&mut self, user: &Address) -> num_bigint::BigInt {
         let mut result = num_bigint::BigInt::from_biguint(Sign::NoSign, rust_biguint!(0));
         self.b_mock
@@ -3,10 +3,13 @@
 mod energy_factory_setup;
 
 use energy_factory::{
-    energy::EnergyModule, locked_token_transfer::LockedTokenTransferModule, SimpleLockEnergy,
+    energy::{Energy, EnergyModule},
+    energy_checkpoints::EnergyCheckpointsModule,
+    locked_token_transfer::LockedTokenTransferModule,
+    SimpleLockEnergy,
 };
 use energy_factory_setup::*;
-use multiversx_sc::types::BigUint;
+use multiversx_sc::types::{BigUint, MultiValueEncoded};
 use num_bigint::BigInt;
 use num_traits::{FromPrimitive, Zero};
 use simple_lock::locked_token::LockedTokenAttributes;
@@ -456,6 +459,227 @@ fn test_same_token_nonce() {
     );
 }
 
+#[test]
+fn energy_checkpoints_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let first_user = setup.first_user.clone();
+    let second_user = setup.second_user.clone();
+    let half_balance = USER_BALANCE / 2;
+
+    let first_lock_epoch = 1;
+    setup.b_mock.set_block_epoch(first_lock_epoch);
+    setup
+        .lock(
+            &first_user,
+            BASE_ASSET_TOKEN_ID,
+            half_balance,
+            LOCK_OPTIONS[0],
+        )
+        .assert_ok();
+    let first_unlock_epoch = to_start_of_month(first_lock_epoch + LOCK_OPTIONS[0]);
+
+    // updates from the queried epoch are not taken into account
+    assert_eq!(
+        setup.get_user_energy_at_epoch(&first_user, first_lock_epoch),
+        rust_biguint!(0)
+    );
+    assert_eq!(
+        setup.get_total_energy_at_epoch(first_lock_epoch),
+        rust_biguint!(0)
+    );
+
+    let second_lock_epoch = 10;
+    setup.b_mock.set_block_epoch(second_lock_epoch);
+    setup
+        .lock(
+            &second_user,
+            BASE_ASSET_TOKEN_ID,
+            half_balance,
+            LOCK_OPTIONS[0],
+        )
+        .assert_ok();
+    let second_unlock_epoch = to_start_of_month(second_lock_epoch + LOCK_OPTIONS[0]);
+
+    // first user locks more tokens, which must not change the past
+    setup
+        .lock(
+            &first_user,
+            BASE_ASSET_TOKEN_ID,
+            half_balance,
+            LOCK_OPTIONS[0],
+        )
+        .assert_ok();
+
+    let first_user_energy_at_5 = rust_biguint!(half_balance) * (first_unlock_epoch - 5);
+    assert_eq!(
+        setup.get_user_energy_at_epoch(&first_user, 5),
+        first_user_energy_at_5
+    );
+    assert_eq!(setup.get_total_energy_at_epoch(5), first_user_energy_at_5);
+    assert_eq!(
+        setup.get_user_energy_at_epoch(&second_user, second_lock_epoch),
+        rust_biguint!(0)
+    );
+
+    let query_epoch = second_lock_epoch + 1;
+    let first_user_energy = rust_biguint!(half_balance) * (first_unlock_epoch - query_epoch)
+        + rust_biguint!(half_balance) * (second_unlock_epoch - query_epoch);
+    let second_user_energy = rust_biguint!(half_balance) * (second_unlock_epoch - query_epoch);
+    assert_eq!(
+        setup.get_user_energy_at_epoch(&first_user, query_epoch),
+        first_user_energy
+    );
+    assert_eq!(
+        setup.get_user_energy_at_epoch(&second_user, query_epoch),
+        second_user_energy
+    );
+    assert_eq!(
+        setup.get_total_energy_at_epoch(query_epoch),
+        first_user_energy + second_user_energy
+    );
+
+    // checkpoints match the live entries
+    setup.b_mock.set_block_epoch(query_epoch);
+    assert_eq!(
+        setup.get_user_energy_at_epoch(&first_user, query_epoch),
+        setup.get_user_energy(&first_user)
+    );
+}
+
+#[test]
+fn total_energy_after_unlock_epoch_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let first_user = setup.first_user.clone();
+    let second_user = setup.second_user.clone();
+    let half_balance = USER_BALANCE / 2;
+
+    // a new deployment has no legacy entries
+    setup
+        .b_mock
+        .execute_query(&setup.sc_wrapper, |sc| {
+            assert!(!sc.is_total_energy_complete_at_epoch(0));
+            assert!(sc.is_total_energy_complete_at_epoch(1));
+        })
+        .assert_ok();
+
+    let lock_epoch = 1;
+    setup.b_mock.set_block_epoch(lock_epoch);
+    setup
+        .lock(
+            &first_user,
+            BASE_ASSET_TOKEN_ID,
+            half_balance,
+            LOCK_OPTIONS[0],
+        )
+        .assert_ok();
+    setup
+        .lock(
+            &second_user,
+            BASE_ASSET_TOKEN_ID,
+            half_balance,
+            LOCK_OPTIONS[1],
+        )
+        .assert_ok();
+    let first_unlock_epoch = to_start_of_month(lock_epoch + LOCK_OPTIONS[0]);
+    let second_unlock_epoch = to_start_of_month(lock_epoch + LOCK_OPTIONS[1]);
+
+    // the first user did not unlock, but the total only includes positive energy
+    let query_epoch = first_unlock_epoch + 10;
+    let second_user_energy = rust_biguint!(half_balance) * (second_unlock_epoch - query_epoch);
+    assert_eq!(
+        setup.get_user_energy_at_epoch(&first_user, query_epoch),
+        rust_biguint!(0)
+    );
+    assert_eq!(
+        setup.get_total_energy_at_epoch(query_epoch),
+        second_user_energy
+    );
+
+    // updates of the first user, whose energy ran out, keep the total unchanged
+    setup.b_mock.set_block_epoch(query_epoch);
+    setup
+        .lock(
+            &first_user,
+            BASE_ASSET_TOKEN_ID,
+            half_balance,
+            LOCK_OPTIONS[0],
+        )
+        .assert_ok();
+
+    let first_user_energy = setup.get_user_energy(&first_user);
+    setup.b_mock.set_block_epoch(query_epoch + 1);
+    let second_user_energy = rust_biguint!(half_balance) * (second_unlock_epoch - query_epoch - 1);
+    let first_user_energy = first_user_energy - rust_biguint!(half_balance) * 2u64;
+    assert_eq!(setup.get_user_energy(&first_user), first_user_energy);
+    assert_eq!(
+        setup.get_total_energy_at_epoch(query_epoch + 1),
+        first_user_energy + second_user_energy
+    );
+}
+
+#[test]
+fn checkpoint_legacy_energy_test() {
+    let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
+    let first_user = setup.first_user.clone();
+    let second_user = setup.second_user.clone();
+    let half_balance = USER_BALANCE / 2;
+
+    // entry saved before checkpoints were introduced
+    let legacy_energy = 1_000_000u64;
+    let legacy_tokens = 1_000u64;
+    setup
+        .b_mock
+        .execute_tx(&setup.owner, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+            sc.user_energy(&managed_address!(&first_user))
+                .set(&Energy::new(
+                    multiversx_sc::types::BigInt::from(managed_biguint!(legacy_energy)),
+                    0,
+                    managed_biguint!(legacy_tokens),
+                ));
+        })
+        .assert_ok();
+
+    setup.b_mock.set_block_epoch(2);
+    assert_eq!(setup.get_total_energy_at_epoch(3), rust_biguint!(0));
+
+    // users without an entry and already tracked users are skipped
+    for _ in 0..2 {
+        setup
+            .b_mock
+            .execute_tx(&setup.owner, &setup.sc_wrapper, &rust_biguint!(0), |sc| {
+                let mut users = MultiValueEncoded::new();
+                users.push(managed_address!(&first_user));
+                users.push(managed_address!(&second_user));
+                sc.checkpoint_legacy_energy(users);
+            })
+            .assert_ok();
+    }
+
+    let legacy_energy_at_3 = rust_biguint!(legacy_energy - legacy_tokens * 3);
+    assert_eq!(
+        setup.get_user_energy_at_epoch(&first_user, 1),
+        rust_biguint!(legacy_energy - legacy_tokens)
+    );
+    assert_eq!(setup.get_total_energy_at_epoch(3), legacy_energy_at_3);
+
+    // a later update replaces the legacy entry in the total
+    setup.b_mock.set_block_epoch(4);
+    setup
+        .lock(
+            &first_user,
+            BASE_ASSET_TOKEN_ID,
+            half_balance,
+            LOCK_OPTIONS[0],
+        )
+        .assert_ok();
+
+    setup.b_mock.set_block_epoch(5);
+    assert_eq!(
+        setup.get_total_energy_at_epoch(5),
+        setup.get_user_energy(&first_user)
+    );
+}
+
 #[test]
 fn energy_deplete_test() {
     let mut setup = SimpleLockEnergySetup::new(energy_factory::contract_obj);
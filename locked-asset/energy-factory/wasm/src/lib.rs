@@ -6,9 +6,9 @@
 
 // Init:                                 1
 // Upgrade:                              1
-// Endpoints:                           39
+// Endpoints:                           49
 // Async Callback:                       1
-// Total number of exported functions:  42
+// Total number of exported functions:  52
 
 #![no_std]
 
@@ -28,8 +28,18 @@ multiversx_sc_wasm_adapter::endpoints! {
         getLockedTokenId => locked_token
         getBaseAssetTokenId => base_asset_token_id
         getLegacyLockedTokenId => legacy_locked_token_id
+        checkpointLegacyEnergy => checkpoint_legacy_energy
+        completeLegacyEnergyCheckpoints => complete_legacy_energy_checkpoints_endpoint
         getEnergyEntryForUser => get_updated_energy_entry_for_user
         getEnergyAmountForUser => get_energy_amount_for_user
+        getEnergyEntryForUserAtEpoch => get_energy_entry_for_user_at_epoch
+        getEnergyAmountForUserAtEpoch => get_energy_amount_for_user_at_epoch
+        getTotalEnergyEntryAtEpoch => get_total_energy_entry_at_epoch
+        getTotalEnergyAmountAtEpoch => get_total_energy_amount_at_epoch
+        getTotalEnergyEntry => get_updated_total_energy_entry
+        isTotalEnergyCompleteAtEpoch => is_total_energy_complete_at_epoch
+        getUserEnergyCheckpointEpochs => user_energy_checkpoint_epochs
+        getTotalEnergyCompleteEpoch => total_energy_complete_epoch
         addLockOptions => add_lock_options
         getLockOptions => get_lock_options_view
         unlockEarly => unlock_early
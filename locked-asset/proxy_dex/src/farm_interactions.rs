@@ -1,9 +1,6 @@
 multiversx_sc::imports!();
 
-use farm::{
-    base_functions::{ClaimRewardsResultType, ClaimRewardsResultWrapper},
-    EnterFarmResultType, ExitFarmWithPartialPosResultType,
-};
+use farm::{AllRewardsResultType, EnterFarmResultType};
 use farm_with_locked_rewards::ProxyTrait as _;
 
 pub struct EnterFarmResultWrapper<M: ManagedTypeApi> {
@@ -14,6 +11,13 @@ pub struct EnterFarmResultWrapper<M: ManagedTypeApi> {
 pub struct ExitFarmResultWrapper<M: ManagedTypeApi> {
     pub farming_tokens: EsdtTokenPayment<M>,
     pub reward_tokens: EsdtTokenPayment<M>,
+    pub additional_rewards: ManagedVec<M, EsdtTokenPayment<M>>,
+}
+
+pub struct ClaimRewardsResultWrapper<M: ManagedTypeApi> {
+    pub new_farm_token: EsdtTokenPayment<M>,
+    pub rewards: EsdtTokenPayment<M>,
+    pub additional_rewards: ManagedVec<M, EsdtTokenPayment<M>>,
 }
 
 #[multiversx_sc::module]
@@ -45,16 +49,17 @@ pub trait FarmInteractionsModule {
         farm_address: ManagedAddress,
         farm_token: EsdtTokenPayment,
     ) -> ExitFarmResultWrapper<Self::Api> {
-        let raw_result: ExitFarmWithPartialPosResultType<Self::Api> = self
+        let raw_result: AllRewardsResultType<Self::Api> = self
             .farm_contract_proxy(farm_address)
             .exit_farm_endpoint(OptionalValue::Some(user))
             .with_esdt_transfer(farm_token)
             .execute_on_dest_context();
-        let (farming_tokens, reward_tokens) = raw_result.into_tuple();
+        let (farming_tokens, reward_tokens, additional_rewards) = raw_result.into_tuple();
 
         ExitFarmResultWrapper {
             farming_tokens,
             reward_tokens,
+            additional_rewards: additional_rewards.to_vec(),
         }
     }
 
@@ -64,16 +69,17 @@ pub trait FarmInteractionsModule {
         farm_address: ManagedAddress,
         farm_token: EsdtTokenPayment,
     ) -> ClaimRewardsResultWrapper<Self::Api> {
-        let raw_result: ClaimRewardsResultType<Self::Api> = self
+        let raw_result: AllRewardsResultType<Self::Api> = self
             .farm_contract_proxy(farm_address)
             .claim_rewards_endpoint(OptionalValue::Some(user))
             .with_esdt_transfer(farm_token)
             .execute_on_dest_context();
-        let (new_farm_token, rewards) = raw_result.into_tuple();
+        let (new_farm_token, rewards, additional_rewards) = raw_result.into_tuple();
 
         ClaimRewardsResultWrapper {
             new_farm_token,
             rewards,
+            additional_rewards: additional_rewards.to_vec(),
         }
     }
 
@@ -220,6 +220,7 @@ pub trait ProxyFarmModule:
 
         self.send_payment_non_zero(&caller, &initial_proxy_farming_tokens);
         self.send_payment_non_zero(&caller, &exit_result.reward_tokens);
+        self.send_multiple_tokens_if_not_zero(&caller, &exit_result.additional_rewards);
 
         wrapped_farm_token_mapper.nft_burn(payment.token_nonce, &payment.amount);
 
@@ -323,6 +324,7 @@ pub trait ProxyFarmModule:
 
         self.send_payment_non_zero(&caller, &new_wrapped_token);
         self.send_payment_non_zero(&caller, &claim_result.rewards);
+        self.send_multiple_tokens_if_not_zero(&caller, &claim_result.additional_rewards);
 
         // Burn farm token
         wrapped_farm_token_mapper.nft_burn(payment.token_nonce, &payment.amount);
@@ -5,14 +5,18 @@ multiversx_sc::derive_imports!();
 
 type EnterFarmResultType<BigUint> =
     MultiValue2<EsdtTokenPayment<BigUint>, EsdtTokenPayment<BigUint>>;
-type ExitFarmResultType<BigUint> =
-    MultiValue2<EsdtTokenPayment<BigUint>, EsdtTokenPayment<BigUint>>;
-type ClaimRewardsResultType<BigUint> =
-    MultiValue2<EsdtTokenPayment<BigUint>, EsdtTokenPayment<BigUint>>;
+type ExitFarmResultType<BigUint> = MultiValue3<
+    EsdtTokenPayment<BigUint>,
+    EsdtTokenPayment<BigUint>,
+    MultiValueEncoded<BigUint, EsdtTokenPayment<BigUint>>,
+>;
+type ClaimRewardsResultType<BigUint> = MultiValue3<
+    EsdtTokenPayment<BigUint>,
+    EsdtTokenPayment<BigUint>,
+    MultiValueEncoded<BigUint, EsdtTokenPayment<BigUint>>,
+>;
 
 const ENTER_FARM_RESULTS_LEN: usize = 2;
-const EXIT_FARM_RESULTS_LEN: usize = 2;
-const CLAIM_REWARDS_RESULTS_LEN: usize = 2;
 
 pub struct EnterFarmResultWrapper<M: ManagedTypeApi> {
     pub farm_tokens: EsdtTokenPayment<M>,
@@ -22,11 +26,13 @@ pub struct EnterFarmResultWrapper<M: ManagedTypeApi> {
 pub struct ExitFarmResultWrapper<M: ManagedTypeApi> {
     pub initial_farming_tokens: EsdtTokenPayment<M>,
     pub reward_tokens: EsdtTokenPayment<M>,
+    pub additional_rewards: ManagedVec<M, EsdtTokenPayment<M>>,
 }
 
 pub struct FarmClaimRewardsResultWrapper<M: ManagedTypeApi> {
     pub new_farm_tokens: EsdtTokenPayment<M>,
     pub reward_tokens: EsdtTokenPayment<M>,
+    pub additional_rewards: ManagedVec<M, EsdtTokenPayment<M>>,
 }
 
 pub struct FarmCompoundRewardsResultWrapper<M: ManagedTypeApi> {
@@ -116,15 +122,16 @@ pub trait FarmInteractionsModule {
             ))
             .execute_on_dest_context();
 
+        // the farm returns the additional rewards after the first two results
         let mut results_wrapper = RawResultWrapper::new(raw_results);
-        results_wrapper.trim_results_front(EXIT_FARM_RESULTS_LEN);
-
         let initial_farming_tokens = results_wrapper.decode_next_result();
         let reward_tokens = results_wrapper.decode_next_result();
+        let additional_rewards = results_wrapper.decode_remaining_results();
 
         ExitFarmResultWrapper {
             initial_farming_tokens,
             reward_tokens,
+            additional_rewards,
         }
     }
 
@@ -146,15 +153,16 @@ pub trait FarmInteractionsModule {
             ))
             .execute_on_dest_context();
 
+        // the farm returns the additional rewards after the first two results
         let mut results_wrapper = RawResultWrapper::new(raw_results);
-        results_wrapper.trim_results_front(CLAIM_REWARDS_RESULTS_LEN);
-
         let new_farm_tokens = results_wrapper.decode_next_result();
         let reward_tokens = results_wrapper.decode_next_result();
+        let additional_rewards = results_wrapper.decode_remaining_results();
 
         FarmClaimRewardsResultWrapper {
             new_farm_tokens,
             reward_tokens,
+            additional_rewards,
         }
     }
 
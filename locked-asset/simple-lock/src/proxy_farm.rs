@@ -197,6 +197,7 @@ pub trait ProxyFarmModule:
     /// Output Payments:
     /// - original farming tokens
     /// - farm reward tokens
+    /// - the additional rewards of the farm, if any, which are only sent, not returned
     #[payable("*")]
     #[endpoint(exitFarmLockedToken)]
     fn exit_farm_locked_token(&self) -> ExitFarmThroughProxyResultType<Self::Api> {
@@ -253,6 +254,10 @@ pub trait ProxyFarmModule:
                 &exit_farm_result.reward_tokens.amount,
             );
         }
+        for additional_reward in exit_farm_result.additional_rewards.iter() {
+            self.send()
+                .direct_non_zero_esdt_payment(&caller, &additional_reward);
+        }
 
         (lp_proxy_token_payment, exit_farm_result.reward_tokens).into()
     }
@@ -266,6 +271,7 @@ pub trait ProxyFarmModule:
     /// Output payments:
     /// - a new FARM_PROXY token
     /// - farm reward tokens
+    /// - the additional rewards of the farm, if any, which are only sent, not returned
     #[payable("*")]
     #[endpoint(farmClaimRewardsLockedToken)]
     fn farm_claim_rewards_locked_token(&self) -> FarmClaimRewardsThroughProxyResultType<Self::Api> {
@@ -308,6 +314,10 @@ pub trait ProxyFarmModule:
                 &claim_rewards_result.reward_tokens.amount,
             );
         }
+        for additional_reward in claim_rewards_result.additional_rewards.iter() {
+            self.send()
+                .direct_non_zero_esdt_payment(&caller, &additional_reward);
+        }
 
         (new_proxy_token_payment, claim_rewards_result.reward_tokens).into()
     }